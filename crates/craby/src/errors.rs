@@ -0,0 +1,302 @@
+use std::{
+    backtrace::{Backtrace, BacktraceStatus},
+    cell::{Cell, RefCell},
+    fmt,
+    panic::AssertUnwindSafe,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Once,
+    },
+};
+
+/// Prefix of the structured error message passed to C++.
+///
+/// Decoded by the generated `makeJsError` helper (`CrabyUtils.hpp`).
+const FFI_ERROR_PREFIX: &str = "\u{1}craby-error:";
+
+/// Error code of the panics caught by the bindings (`error.code` in JavaScript).
+pub const PANIC_ERROR_CODE: &str = "RUST_PANIC";
+
+/// Structured error with a code and an optional details payload.
+///
+/// Rejected promises surface in JavaScript as an `Error` with `code` and `details` properties.
+///
+/// ```rust,ignore
+/// fn find_user(&mut self, id: Number) -> Promise<User> {
+///     match self.users.get(&id) {
+///         Some(user) => promise::resolve(user.clone()),
+///         None => Err(craby::Error::new("NOT_FOUND", "User not found")
+///             .with_details(serde_json::json!({ "id": id }))
+///             .into()),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Error {
+    code: std::string::String,
+    message: std::string::String,
+    details: Option<serde_json::Value>,
+}
+
+impl Error {
+    /// Creates an error with the code (eg. `"NOT_FOUND"` or a string enum member of the spec).
+    pub fn new(code: impl ErrorCode, message: impl Into<std::string::String>) -> Self {
+        Error {
+            code: code.code(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Attaches the details payload (`error.details` in JavaScript).
+    ///
+    /// The payload is discarded if it cannot be serialized into JSON.
+    pub fn with_details(mut self, details: impl serde::Serialize) -> Self {
+        self.details = serde_json::to_value(details).ok();
+        self
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn details(&self) -> Option<&serde_json::Value> {
+        self.details.as_ref()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Error code of the structured error.
+///
+/// Implemented for strings and the string enums referenced by the `@throws {Type}` tags of the spec.
+pub trait ErrorCode {
+    fn code(&self) -> std::string::String;
+}
+
+impl ErrorCode for &str {
+    fn code(&self) -> std::string::String {
+        self.to_string()
+    }
+}
+
+impl ErrorCode for std::string::String {
+    fn code(&self) -> std::string::String {
+        self.clone()
+    }
+}
+
+impl ErrorCode for &std::string::String {
+    fn code(&self) -> std::string::String {
+        (*self).clone()
+    }
+}
+
+/// Panic caught by [`catch_panic!`](crate::catch_panic) (`RUST_PANIC`).
+///
+/// Displayed as the panic message. The generated bindings pass it to JavaScript as the structured error
+/// (see [`into_ffi_error`]), so `error.code` is `RUST_PANIC` and `error.details` has the location and the backtrace.
+#[derive(Debug, Clone)]
+pub struct PanicError(Error);
+
+impl PanicError {
+    pub fn new(message: impl Into<std::string::String>) -> Self {
+        PanicError(Error::new(PANIC_ERROR_CODE, message))
+    }
+
+    /// Returns the structured error with the `RUST_PANIC` code.
+    pub fn error(&self) -> &Error {
+        &self.0
+    }
+}
+
+impl fmt::Display for PanicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.message)
+    }
+}
+
+impl std::error::Error for PanicError {}
+
+/// Encodes the structured error (if any) into the error message passed to C++.
+///
+/// The encoded message is only decoded by the generated C++ bindings, so the errors
+/// returned to Rust (eg. by [`catch_panic!`](crate::catch_panic)) are kept as they are until this call.
+///
+/// Used by the generated bindings.
+pub fn into_ffi_error(err: anyhow::Error) -> anyhow::Error {
+    let error = err
+        .downcast_ref::<Error>()
+        .or_else(|| err.downcast_ref::<PanicError>().map(PanicError::error));
+    match error {
+        Some(error) => {
+            let payload = serde_json::json!({
+                "code": error.code,
+                "message": error.message,
+                "details": error.details,
+            });
+            anyhow::anyhow!("{FFI_ERROR_PREFIX}{payload}")
+        }
+        None => err,
+    }
+}
+
+/// Whether the backtrace is captured even if `RUST_BACKTRACE` is not set.
+static FORCE_BACKTRACE: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Report of the last panic on the current thread (recorded by the panic hook).
+    static PANIC_REPORT: RefCell<Option<PanicReport>> = const { RefCell::new(None) };
+    /// Number of the nested [`catch_unwind`] calls on the current thread.
+    static CATCH_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+struct PanicReport {
+    location: Option<std::string::String>,
+    backtrace: Option<std::string::String>,
+}
+
+/// Captures the backtrace of the panics even if `RUST_BACKTRACE` is not set (`[project.panic] backtrace = true`).
+///
+/// Used by the generated bindings.
+pub fn set_panic_backtrace(enabled: bool) {
+    FORCE_BACKTRACE.store(enabled, Ordering::Relaxed);
+}
+
+/// Installs the panic hook that records the location and the backtrace of the panics (once per process).
+///
+/// The hook is chained to the previous hook (eg. printing the panic message to the log or the crash reporter
+/// of the app), and only records the panics caught by [`catch_unwind`]. The other panics of the process
+/// are handled by the previous hook only.
+pub fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let prev_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !CATCH_DEPTH
+                .try_with(|depth| depth.get() > 0)
+                .unwrap_or(false)
+            {
+                return prev_hook(info);
+            }
+
+            let backtrace = if FORCE_BACKTRACE.load(Ordering::Relaxed) {
+                Backtrace::force_capture()
+            } else {
+                Backtrace::capture()
+            };
+            let report = PanicReport {
+                location: info.location().map(|location| location.to_string()),
+                backtrace: (backtrace.status() == BacktraceStatus::Captured)
+                    .then(|| backtrace.to_string()),
+            };
+            let _ = PANIC_REPORT.try_with(|last| *last.borrow_mut() = Some(report));
+
+            prev_hook(info);
+        }));
+    });
+}
+
+/// Calls the function and catches its panic.
+///
+/// The panic hook records the location and the backtrace of the panics in this call (see [`install_panic_hook`]).
+///
+/// Used by [`catch_panic!`](crate::catch_panic) and the async executor.
+pub fn catch_unwind<R>(f: impl FnOnce() -> R) -> std::thread::Result<R> {
+    struct Scope;
+
+    impl Drop for Scope {
+        fn drop(&mut self) {
+            let _ = CATCH_DEPTH.try_with(|depth| depth.set(depth.get() - 1));
+        }
+    }
+
+    install_panic_hook();
+    let _ = CATCH_DEPTH.try_with(|depth| depth.set(depth.get() + 1));
+    let _scope = Scope;
+
+    std::panic::catch_unwind(AssertUnwindSafe(f))
+}
+
+/// Converts the caught panic into the [`PanicError`] (`RUST_PANIC`).
+///
+/// `details` has the `location` and the `backtrace` (`null` if not captured) of the panic.
+///
+/// Used by [`catch_panic!`](crate::catch_panic) and the async executor.
+pub fn panic_error(payload: &(dyn std::any::Any + Send)) -> anyhow::Error {
+    let report = PANIC_REPORT
+        .try_with(|last| last.borrow_mut().take())
+        .ok()
+        .flatten();
+    let (location, backtrace) = match report {
+        Some(report) => (report.location, report.backtrace),
+        None => (None, None),
+    };
+    let error = PanicError::new(panic_message(payload));
+    let error = PanicError(error.0.with_details(serde_json::json!({
+        "location": location,
+        "backtrace": backtrace,
+    })));
+
+    error.into()
+}
+
+/// Returns the message of the panic payload (`panic!("...")`).
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> std::string::String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<std::string::String>() {
+        s.clone()
+    } else {
+        "Unknown panic occurred".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panic_error() {
+        let err = crate::catch_panic!({
+            panic!("boom");
+        })
+        .unwrap_err();
+        // The panic message is kept until the error is passed to C++
+        assert_eq!(err.to_string(), "boom");
+
+        let err = into_ffi_error(err).to_string();
+        let payload = err.strip_prefix(FFI_ERROR_PREFIX).unwrap();
+        let payload = serde_json::from_str::<serde_json::Value>(payload).unwrap();
+
+        assert_eq!(payload["code"], PANIC_ERROR_CODE);
+        assert_eq!(payload["message"], "boom");
+        assert!(payload["details"]["location"]
+            .as_str()
+            .unwrap()
+            .starts_with("crates/craby/src/errors.rs:"));
+    }
+
+    #[test]
+    fn test_panic_hook_scope() {
+        install_panic_hook();
+
+        // Panics outside the bindings are left to the previous hook
+        let _ = std::panic::catch_unwind(|| panic!("outside"));
+        assert!(PANIC_REPORT.with(|last| last.borrow().is_none()));
+
+        let _ = catch_unwind(|| panic!("inside"));
+        assert!(PANIC_REPORT.with(|last| last.borrow().is_some()));
+    }
+}
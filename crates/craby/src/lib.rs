@@ -4,12 +4,18 @@ pub mod macros;
 /// This module provides the prelude for Craby Modules.
 pub mod prelude {
     pub use crate::context::*;
+    pub use crate::sync::ModuleRef;
     pub use crate::types::*;
     pub use craby_macro::craby_module;
 }
 
 pub mod context;
+pub mod errors;
+pub mod runtime;
+pub mod sync;
 pub mod types;
 
+pub use errors::Error;
+
 // craby_marco crate
 pub use craby_macro;
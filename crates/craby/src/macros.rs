@@ -6,19 +6,21 @@ macro_rules! throw {
     };
 }
 
-/// Catches a panic and returns a `Result` with the error message.
+/// Catches a panic and returns a `Result` with the [`PanicError`](crate::errors::PanicError) (`RUST_PANIC`).
+///
+/// The error is displayed as the panic message. The generated bindings encode it with
+/// [`into_ffi_error`](crate::errors::into_ffi_error) to throw the structured error to JavaScript.
+///
+/// With the module instance (eg. `catch_panic!(it_.0, { ... })`), a poisoned instance fails fast
+/// and a panic poisons the instance (if enabled).
 #[macro_export]
 macro_rules! catch_panic {
-    ($expr:expr) => {
-        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| $expr)).map_err(|e| {
-            let msg = if let Some(s) = e.downcast_ref::<&str>() {
-                (*s).to_string()
-            } else if let Some(s) = e.downcast_ref::<String>() {
-                s.clone()
-            } else {
-                "Unknown panic occurred".to_string()
-            };
-            anyhow::anyhow!(msg)
+    ($instance:expr, $expr:expr) => {
+        $crate::sync::Poison::check_poisoned(&$instance).and_then(|_| {
+            $crate::catch_panic!($expr).inspect_err(|_| $crate::sync::Poison::poison(&$instance))
         })
     };
+    ($expr:expr) => {
+        $crate::errors::catch_unwind(|| $expr).map_err(|e| $crate::errors::panic_error(&*e))
+    };
 }
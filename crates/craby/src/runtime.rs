@@ -0,0 +1,415 @@
+//! Embedded executor of the `async fn` methods (`@async` tag).
+//!
+//! Futures are driven by a single background thread (`craby-async`) that is spawned on the first use
+//! and shared by the `async fn` methods of every module. A future that blocks the thread while it is polled
+//! (eg. `std::thread::sleep`, blocking IO or a long computation) stalls all the other futures,
+//! so run the blocking work with [`spawn_blocking`] instead.
+//!
+//! The executor does not provide the IO reactor or timers, so runtime specific futures
+//! (eg. `tokio::net`) must be driven by their own runtime (eg. awaiting the `JoinHandle` of the spawned task).
+
+use std::{
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    sync::{mpsc, Arc, Mutex, OnceLock, PoisonError},
+    task::{Context, Poll, Wake, Waker},
+};
+
+use crate::{
+    errors::{self, panic_error},
+    sync::Poison,
+    types::{AsyncPromise, Promise},
+};
+
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+struct Task {
+    future: Mutex<Option<BoxFuture>>,
+    sender: mpsc::Sender<Arc<Task>>,
+}
+
+impl Task {
+    /// Polls the future once (on the executor thread).
+    fn run(self: Arc<Self>) {
+        let mut slot = self.future.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut future = match slot.take() {
+            Some(future) => future,
+            // Already completed (woken after completion)
+            None => return,
+        };
+
+        let waker = Waker::from(self.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        // The panicked future is dropped to keep the executor thread alive
+        if let Ok(Poll::Pending) = catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(&mut cx)))
+        {
+            *slot = Some(future);
+        }
+    }
+}
+
+impl Wake for Task {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        // The receiver lives as long as the process
+        let _ = self.sender.send(self.clone());
+    }
+}
+
+/// Returns the task queue of the executor thread (spawned on the first call).
+fn sender() -> &'static mpsc::Sender<Arc<Task>> {
+    static SENDER: OnceLock<mpsc::Sender<Arc<Task>>> = OnceLock::new();
+
+    SENDER.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Arc<Task>>();
+
+        std::thread::Builder::new()
+            .name("craby-async".to_string())
+            .spawn(move || {
+                for task in receiver {
+                    task.run();
+                }
+            })
+            .expect("Failed to spawn the async executor thread");
+
+        sender
+    })
+}
+
+/// Spawns the future on the executor.
+///
+/// The future runs in the background and its output is dropped.
+///
+/// ```rust,ignore
+/// craby::runtime::spawn(async move {
+///     let _ = upload(data).await;
+/// });
+/// ```
+pub fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let sender = sender();
+    let task = Arc::new(Task {
+        future: Mutex::new(Some(Box::pin(future))),
+        sender: sender.clone(),
+    });
+
+    let _ = sender.send(task);
+}
+
+/// Runs the blocking function on a new thread and returns the future of its output.
+///
+/// The executor polls every future on a single thread, so the blocking work must not run in the future itself.
+///
+/// ```rust,ignore
+/// async fn read_file(&self, path: String) -> Promise<String> {
+///     craby::runtime::spawn_blocking(move || std::fs::read_to_string(path)).await?
+/// }
+/// ```
+///
+/// # Panics
+///
+/// The future panics if the function panics.
+pub fn spawn_blocking<F, T>(f: F) -> impl Future<Output = T> + Send + 'static
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let state = Arc::new(Mutex::new(BlockingState {
+        output: None,
+        waker: None,
+    }));
+
+    let shared = state.clone();
+    std::thread::Builder::new()
+        .name("craby-blocking".to_string())
+        .spawn(move || {
+            let output = catch_unwind(AssertUnwindSafe(f));
+            let mut state = shared.lock().unwrap_or_else(PoisonError::into_inner);
+            state.output = Some(output);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        })
+        .expect("Failed to spawn the blocking thread");
+
+    std::future::poll_fn(move |cx| {
+        let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+        match state.output.take() {
+            Some(Ok(output)) => Poll::Ready(output),
+            Some(Err(e)) => std::panic::resume_unwind(e),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    })
+}
+
+struct BlockingState<T> {
+    output: Option<std::thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// Creates the future with the module instance and spawns it on the executor,
+/// then settles the promise with its output.
+///
+/// A poisoned module instance rejects the promise without calling `f`. Panics (while creating or polling the future)
+/// poison the instance (if enabled) and reject the promise with the `RUST_PANIC` error.
+///
+/// The future owns a reference of the module instance, so the instance outlives the future
+/// even if the module is invalidated before the promise is settled.
+///
+/// Used by the generated bindings.
+pub fn spawn_promise<I, T, F, Fut>(instance: Arc<I>, promise: AsyncPromise<T>, f: F)
+where
+    I: Poison + Send + Sync + 'static,
+    T: 'static,
+    F: FnOnce(Arc<I>) -> Fut,
+    Fut: Future<Output = Promise<T>> + Send + 'static,
+{
+    if let Err(err) = instance.check_poisoned() {
+        return promise.settle(Err(err));
+    }
+
+    let future = match errors::catch_unwind(|| f(instance.clone())) {
+        Ok(future) => future,
+        Err(e) => {
+            instance.poison();
+            return promise.settle(Err(panic_error(&*e)));
+        }
+    };
+
+    spawn(async move {
+        let result = CatchUnwind(Box::pin(future)).await;
+        let result = result.unwrap_or_else(|e| {
+            instance.poison();
+            Err(panic_error(&*e))
+        });
+
+        // Released before the promise is settled (eg. the last reference drops the invalidated module)
+        drop(instance);
+        promise.settle(result);
+    });
+}
+
+/// Future that catches the panic of the inner future.
+struct CatchUnwind<F>(Pin<Box<F>>);
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = std::thread::Result<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = &mut self.get_mut().0;
+
+        match errors::catch_unwind(|| inner.as_mut().poll(cx)) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Err(e) => Poll::Ready(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{channel, Receiver, Sender};
+    use std::time::Duration;
+
+    use crate::sync::Shared;
+
+    use super::*;
+
+    /// Serializes the tests sharing `RESULTS`
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+    static RESULTS: Mutex<Option<Sender<(usize, std::string::String)>>> = Mutex::new(None);
+
+    fn report(id: usize, result: std::string::String) {
+        if let Some(sender) = RESULTS.lock().unwrap().as_ref() {
+            sender.send((id, result)).unwrap();
+        }
+    }
+
+    fn promise(id: usize) -> AsyncPromise<std::string::String> {
+        AsyncPromise::new(
+            id,
+            |id, val| report(id, format!("resolved: {val}")),
+            |id, message| report(id, format!("rejected: {}", decode(message))),
+            |id| report(id, "released".to_string()),
+        )
+    }
+
+    /// Formats the structured error as `[code] message`.
+    fn decode(message: &str) -> std::string::String {
+        match message.strip_prefix("\u{1}craby-error:") {
+            Some(json) => {
+                let error = serde_json::from_str::<serde_json::Value>(json).unwrap();
+                format!(
+                    "[{}] {}",
+                    error["code"].as_str().unwrap(),
+                    error["message"].as_str().unwrap()
+                )
+            }
+            None => message.to_string(),
+        }
+    }
+
+    /// Future that is pending until it is polled twice (woken by itself).
+    struct Yield(bool);
+
+    impl Future for Yield {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    fn instance(poison: bool) -> Arc<Shared<()>> {
+        let instance = Shared::new(());
+        let instance = if poison {
+            instance.poison_on_panic()
+        } else {
+            instance
+        };
+        Arc::new(instance)
+    }
+
+    fn collect(
+        receiver: &Receiver<(usize, std::string::String)>,
+        count: usize,
+    ) -> Vec<(usize, std::string::String)> {
+        let mut results = (0..count)
+            .map(|_| receiver.recv_timeout(Duration::from_secs(5)).unwrap())
+            .collect::<Vec<_>>();
+        results.sort();
+        results
+    }
+
+    #[test]
+    fn test_spawn_promise() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        let (sender, receiver) = channel();
+        *RESULTS.lock().unwrap() = Some(sender);
+
+        let instance = instance(false);
+        spawn_promise(instance.clone(), promise(1), |_| async {
+            Yield(false).await;
+            Ok("done".to_string())
+        });
+        spawn_promise(instance.clone(), promise(2), |_| async {
+            Err(anyhow::anyhow!("failed"))
+        });
+        spawn_promise(instance.clone(), promise(3), |_| async {
+            Yield(false).await;
+            panic!("boom");
+        });
+        spawn_promise(
+            instance,
+            promise(4),
+            |_| -> std::future::Ready<Promise<_>> {
+                panic!("sync boom");
+            },
+        );
+
+        assert_eq!(
+            collect(&receiver, 8),
+            [
+                (1, "released".to_string()),
+                (1, "resolved: done".to_string()),
+                (2, "rejected: failed".to_string()),
+                (2, "released".to_string()),
+                (3, "rejected: [RUST_PANIC] boom".to_string()),
+                (3, "released".to_string()),
+                (4, "rejected: [RUST_PANIC] sync boom".to_string()),
+                (4, "released".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spawn_promise_poison() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        let (sender, receiver) = channel();
+        *RESULTS.lock().unwrap() = Some(sender);
+
+        let instance = instance(true);
+        spawn_promise(instance.clone(), promise(1), |_| async {
+            Yield(false).await;
+            panic!("boom");
+        });
+        assert_eq!(
+            collect(&receiver, 2),
+            [
+                (1, "rejected: [RUST_PANIC] boom".to_string()),
+                (1, "released".to_string()),
+            ]
+        );
+
+        // The panicked future poisons the instance
+        spawn_promise(instance.clone(), promise(2), |_| async {
+            Ok("done".to_string())
+        });
+        assert_eq!(
+            collect(&receiver, 2),
+            [
+                (
+                    2,
+                    "rejected: [RUST_PANIC] The module instance is poisoned by a previous panic"
+                        .to_string()
+                ),
+                (2, "released".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spawn_blocking() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        let (sender, receiver) = channel();
+        *RESULTS.lock().unwrap() = Some(sender);
+
+        let (unblock, blocked) = channel::<()>();
+        let instance = instance(false);
+        spawn_promise(instance.clone(), promise(1), |_| async move {
+            let ret = spawn_blocking(move || {
+                blocked.recv().unwrap();
+                "blocking".to_string()
+            })
+            .await;
+            Ok(ret)
+        });
+        // The blocking function does not stall the other futures
+        spawn_promise(instance.clone(), promise(2), |_| async {
+            Ok("done".to_string())
+        });
+        assert_eq!(
+            collect(&receiver, 2),
+            [
+                (2, "released".to_string()),
+                (2, "resolved: done".to_string()),
+            ]
+        );
+
+        unblock.send(()).unwrap();
+        assert_eq!(
+            collect(&receiver, 2),
+            [
+                (1, "released".to_string()),
+                (1, "resolved: blocking".to_string()),
+            ]
+        );
+        // The settled futures release the module instance
+        assert_eq!(Arc::strong_count(&instance), 1);
+    }
+}
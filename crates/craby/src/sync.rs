@@ -0,0 +1,269 @@
+//! Module instances shared by the JS thread and the worker threads.
+//!
+//! The generated bindings wrap the module with one of the following types
+//! depending on the concurrency mode of the module (`[project.concurrency]` in `craby.toml`).
+
+use std::{
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+};
+
+use crate::errors::PanicError;
+
+/// Module instance that fails the later calls after a panic (`[project.panic] poison = true`).
+///
+/// Used by [`catch_panic!`](crate::catch_panic).
+pub trait Poison {
+    /// Returns the error if the instance is poisoned.
+    fn check_poisoned(&self) -> Result<(), anyhow::Error>;
+    /// Poisons the instance (no-op if poisoning is disabled).
+    fn poison(&self);
+}
+
+/// The generated bindings own the module instance with `Arc` to keep it alive in the spawned futures.
+impl<T: Poison + ?Sized> Poison for Arc<T> {
+    fn check_poisoned(&self) -> Result<(), anyhow::Error> {
+        (**self).check_poisoned()
+    }
+
+    fn poison(&self) {
+        (**self).poison();
+    }
+}
+
+#[derive(Default)]
+struct PoisonState {
+    enabled: bool,
+    poisoned: AtomicBool,
+}
+
+impl PoisonState {
+    fn check_poisoned(&self) -> Result<(), anyhow::Error> {
+        if self.poisoned.load(Ordering::Acquire) {
+            let error = PanicError::new("The module instance is poisoned by a previous panic");
+            return Err(error.into());
+        }
+        Ok(())
+    }
+
+    fn poison(&self) {
+        if self.enabled {
+            self.poisoned.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Module instance guarded by a per-instance lock (`lock` mode).
+///
+/// The module methods take `&mut self` and the calls are serialized.
+pub struct ModuleLock<T: ?Sized> {
+    poison: PoisonState,
+    inner: Mutex<T>,
+}
+
+impl<T: Send> ModuleLock<T> {
+    pub fn new(module: T) -> Self {
+        ModuleLock {
+            poison: PoisonState::default(),
+            inner: Mutex::new(module),
+        }
+    }
+
+    /// Fails the later calls after a panic.
+    pub fn poison_on_panic(mut self) -> Self {
+        self.poison.enabled = true;
+        self
+    }
+}
+
+impl<T: ?Sized> ModuleLock<T> {
+    /// Blocks the current thread until the lock is acquired.
+    ///
+    /// The panics of the previous calls are contained by the bindings, so the poisoned lock is recovered
+    /// (see [`ModuleLock::poison_on_panic`] to fail the calls instead).
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: ?Sized> Poison for ModuleLock<T> {
+    fn check_poisoned(&self) -> Result<(), anyhow::Error> {
+        self.poison.check_poisoned()
+    }
+
+    fn poison(&self) {
+        self.poison.poison();
+    }
+}
+
+/// Owned reference of the module instance in the `lock` mode.
+///
+/// Stream methods and `async fn` methods (`@async`) keep running after the call returns,
+/// so they take this reference instead of `&mut self` and lock the module only while accessing the state.
+///
+/// ```rust,ignore
+/// fn read_lines(this: ModuleRef<Self>, path: &str, stream: Stream<String>) {
+///     for line in std::fs::read_to_string(path).unwrap().lines() {
+///         this.lock().read_count += 1;
+///         stream.push(line.to_string());
+///     }
+/// }
+/// ```
+pub type ModuleRef<T> = Arc<ModuleLock<T>>;
+
+/// Module instance accessed concurrently without the lock (`shared` mode).
+///
+/// The module methods take `&self`, so the mutable state must be synchronized by the module
+/// (eg. `Mutex`, `RwLock` and atomics).
+pub struct Shared<T: Send + Sync> {
+    inner: T,
+    poison: PoisonState,
+}
+
+impl<T: Send + Sync> Shared<T> {
+    pub fn new(module: T) -> Self {
+        Shared {
+            inner: module,
+            poison: PoisonState::default(),
+        }
+    }
+
+    /// Fails the later calls after a panic.
+    pub fn poison_on_panic(mut self) -> Self {
+        self.poison.enabled = true;
+        self
+    }
+}
+
+impl<T: Send + Sync> Poison for Shared<T> {
+    fn check_poisoned(&self) -> Result<(), anyhow::Error> {
+        self.poison.check_poisoned()
+    }
+
+    fn poison(&self) {
+        self.poison.poison();
+    }
+}
+
+/// Poison state of a host object (`HostObject<T>` in the spec).
+///
+/// Host object methods are only called by the JS thread, so the object is not locked.
+#[derive(Default)]
+pub struct PoisonFlag(PoisonState);
+
+impl PoisonFlag {
+    /// Creates the state that fails the later calls after a panic if `enabled` (`[project.panic] poison = true`).
+    pub fn new(enabled: bool) -> Self {
+        PoisonFlag(PoisonState {
+            enabled,
+            poisoned: AtomicBool::new(false),
+        })
+    }
+}
+
+impl Poison for PoisonFlag {
+    fn check_poisoned(&self) -> Result<(), anyhow::Error> {
+        self.0.check_poisoned()
+    }
+
+    fn poison(&self) {
+        self.0.poison();
+    }
+}
+
+impl<T: Send + Sync> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{atomic::AtomicUsize, Arc};
+
+    use super::*;
+
+    #[test]
+    fn test_module_lock() {
+        let module = Arc::new(ModuleLock::new(Vec::new()));
+
+        let handles = (0..4)
+            .map(|n| {
+                let module = module.clone();
+                std::thread::spawn(move || {
+                    for i in 0..100 {
+                        module.lock().push(n * 100 + i);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        handles.into_iter().for_each(|h| h.join().unwrap());
+
+        let _ = std::thread::spawn({
+            let module = module.clone();
+            move || {
+                let _guard = module.lock();
+                panic!("boom");
+            }
+        })
+        .join();
+
+        let mut items = module.lock().clone();
+        items.sort();
+        assert_eq!(items, (0..400).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_drop_module() {
+        struct Module(Arc<AtomicUsize>);
+
+        impl Drop for Module {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+
+        // Released by the deleter of the C++ module (`rust::Box::from_raw`)
+        drop(Box::new(ModuleLock::new(Module(dropped.clone()))));
+        assert_eq!(dropped.load(Ordering::SeqCst), 1);
+
+        let module = Box::new(Shared::new(Module(dropped.clone())).poison_on_panic());
+        let _ = crate::catch_panic!(*module, panic!("boom"));
+        drop(module);
+        assert_eq!(dropped.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_poison_on_panic() {
+        let call = |module: &Shared<u32>, fail: bool| {
+            crate::catch_panic!(*module, {
+                if fail {
+                    panic!("boom");
+                }
+                **module
+            })
+        };
+
+        let module = Shared::new(1);
+        assert!(call(&module, true).is_err());
+        assert_eq!(call(&module, false).unwrap(), 1);
+
+        let module = Shared::new(1).poison_on_panic();
+        assert_eq!(call(&module, false).unwrap(), 1);
+        assert!(call(&module, true)
+            .unwrap_err()
+            .to_string()
+            .contains("boom"));
+        assert!(call(&module, false)
+            .unwrap_err()
+            .to_string()
+            .contains("poisoned by a previous panic"));
+    }
+}
@@ -1,7 +1,13 @@
 pub type Boolean = bool;
 pub type Number = f64;
+pub type Int32 = i32;
+pub type Int64 = i64;
+pub type UInt8 = u8;
+pub type Float = f32;
 pub type String = std::string::String;
 pub type Array<T> = std::vec::Vec<T>;
+pub type ArrayBuffer = std::vec::Vec<u8>;
+pub type Map<T> = std::collections::HashMap<String, T>;
 pub type Promise<T> = std::result::Result<T, anyhow::Error>;
 pub type Void = ();
 
@@ -22,6 +28,50 @@ pub mod promise {
     }
 }
 
+/// Pending JavaScript promise of the `async fn` method (`@async` tag).
+///
+/// The promise is settled with the output of the future by the async executor.
+/// If the handle is dropped before it is settled, the promise is rejected.
+pub struct AsyncPromise<T> {
+    id: usize,
+    resolve: fn(usize, T),
+    reject: fn(usize, &str),
+    release: fn(usize),
+}
+
+impl<T> AsyncPromise<T> {
+    /// Creates a new `AsyncPromise` with the registered promise id.
+    ///
+    /// Used by the generated bindings.
+    pub fn new(
+        id: usize,
+        resolve: fn(usize, T),
+        reject: fn(usize, &str),
+        release: fn(usize),
+    ) -> Self {
+        AsyncPromise {
+            id,
+            resolve,
+            reject,
+            release,
+        }
+    }
+
+    /// Settles the promise with the result (the structured error is kept for the rejection).
+    pub(crate) fn settle(self, result: Promise<T>) {
+        match result {
+            Ok(val) => (self.resolve)(self.id, val),
+            Err(err) => (self.reject)(self.id, &crate::errors::into_ffi_error(err).to_string()),
+        }
+    }
+}
+
+impl<T> Drop for AsyncPromise<T> {
+    fn drop(&mut self) {
+        (self.release)(self.id)
+    }
+}
+
 /// JavaScript-like Nullable utilities.
 ///
 /// Used to represent optional values.
@@ -67,3 +117,203 @@ impl<T> Nullable<T> {
         self.val
     }
 }
+
+/// JavaScript callback function handle.
+///
+/// The handle can be cloned and moved to other threads.
+/// Calls are scheduled on the JS thread, and the JS function is released when the last handle is dropped.
+///
+/// ```typescript
+/// subscribe(onData: (chunk: Chunk) => void): void;
+/// ```
+pub struct Callback<T> {
+    inner: std::sync::Arc<CallbackRef<T>>,
+}
+
+struct CallbackRef<T> {
+    id: usize,
+    invoke: fn(usize, T),
+    release: fn(usize),
+}
+
+impl<T> Callback<T> {
+    /// Creates a new `Callback` with the registered callback id.
+    ///
+    /// Used by the generated bindings.
+    pub fn new(id: usize, invoke: fn(usize, T), release: fn(usize)) -> Self {
+        Callback {
+            inner: std::sync::Arc::new(CallbackRef {
+                id,
+                invoke,
+                release,
+            }),
+        }
+    }
+
+    /// Invokes the JS function with the given argument.
+    ///
+    /// The function is called asynchronously on the JS thread.
+    pub fn call(&self, arg: T) {
+        (self.inner.invoke)(self.inner.id, arg)
+    }
+}
+
+impl<T> Clone for Callback<T> {
+    fn clone(&self) -> Self {
+        Callback {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for CallbackRef<T> {
+    fn drop(&mut self) {
+        (self.release)(self.id)
+    }
+}
+
+/// JavaScript async iterable producer handle.
+///
+/// Items are delivered to the consumer (eg. `for await...of`) in order.
+/// The stream is finished when [`Stream::finish`] is called or the last handle is dropped.
+///
+/// ```typescript
+/// readLines(path: string): AsyncIterable<string>;
+/// ```
+pub struct Stream<T> {
+    inner: std::sync::Arc<StreamRef<T>>,
+}
+
+struct StreamRef<T> {
+    id: usize,
+    push: fn(usize, T) -> bool,
+    finish: fn(usize),
+    fail: fn(usize, &str),
+    release: fn(usize),
+}
+
+impl<T> Stream<T> {
+    /// Creates a new `Stream` with the registered stream id.
+    ///
+    /// Used by the generated bindings.
+    pub fn new(
+        id: usize,
+        push: fn(usize, T) -> bool,
+        finish: fn(usize),
+        fail: fn(usize, &str),
+        release: fn(usize),
+    ) -> Self {
+        Stream {
+            inner: std::sync::Arc::new(StreamRef {
+                id,
+                push,
+                finish,
+                fail,
+                release,
+            }),
+        }
+    }
+
+    /// Pushes an item to the stream.
+    ///
+    /// Blocks while the buffer is full until the consumer pulls the next item (backpressure).
+    /// Returns `false` if the stream is closed (eg. the consumer stopped iterating), so the producer can stop.
+    pub fn push(&self, item: T) -> bool {
+        (self.inner.push)(self.inner.id, item)
+    }
+
+    /// Finishes the stream.
+    ///
+    /// The consumer receives the buffered items before the end of the stream.
+    pub fn finish(&self) {
+        (self.inner.finish)(self.inner.id)
+    }
+
+    /// Finishes the stream with an error.
+    ///
+    /// The consumer receives the buffered items before the error is thrown.
+    pub fn fail(&self, err: impl AsRef<str>) {
+        (self.inner.fail)(self.inner.id, err.as_ref())
+    }
+}
+
+impl<T> Clone for Stream<T> {
+    fn clone(&self) -> Self {
+        Stream {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for StreamRef<T> {
+    fn drop(&mut self) {
+        (self.release)(self.id)
+    }
+}
+
+/// Cancellation token of the promise method (`AbortSignal` parameter).
+///
+/// The token is cancelled when the `AbortSignal` is aborted or the module is invalidated.
+/// The pending promise is rejected with an `AbortError` immediately, and the method can stop early by polling the token.
+///
+/// ```typescript
+/// hash(data: ArrayBuffer, signal?: AbortSignal): Promise<string>;
+/// ```
+pub struct CancellationToken {
+    inner: std::sync::Arc<CancellationTokenRef>,
+}
+
+struct CancellationTokenRef {
+    id: usize,
+    is_cancelled: fn(usize) -> bool,
+    release: fn(usize),
+}
+
+impl CancellationToken {
+    /// Creates a new `CancellationToken` with the registered token id.
+    ///
+    /// Used by the generated bindings.
+    pub fn new(id: usize, is_cancelled: fn(usize) -> bool, release: fn(usize)) -> Self {
+        CancellationToken {
+            inner: std::sync::Arc::new(CancellationTokenRef {
+                id,
+                is_cancelled,
+                release,
+            }),
+        }
+    }
+
+    /// Returns `true` if the operation is cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        (self.inner.is_cancelled)(self.inner.id)
+    }
+
+    /// Returns an error if the operation is cancelled.
+    ///
+    /// ```rust,ignore
+    /// for chunk in data.chunks(1024) {
+    ///     signal.check()?;
+    ///     hasher.update(chunk);
+    /// }
+    /// ```
+    pub fn check(&self) -> Result<(), anyhow::Error> {
+        if self.is_cancelled() {
+            anyhow::bail!("The operation was aborted");
+        }
+        Ok(())
+    }
+}
+
+impl Clone for CancellationToken {
+    fn clone(&self) -> Self {
+        CancellationToken {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Drop for CancellationTokenRef {
+    fn drop(&mut self) {
+        (self.release)(self.id)
+    }
+}
@@ -60,6 +60,18 @@ pub fn crate_libs(config: &CompleteConfig) -> Result<(), anyhow::Error> {
         replace_cxx_header(&signal_path)?;
     }
 
+    let streams_path = jni_base_path.join("include").join("CrabyStreams.h");
+    debug!("Post-processing CrabyStreams.h: {:?}", streams_path);
+    if streams_path.try_exists()? {
+        replace_cxx_header(&streams_path)?;
+    }
+
+    let promises_path = jni_base_path.join("include").join("CrabyPromises.h");
+    debug!("Post-processing CrabyPromises.h: {:?}", promises_path);
+    if promises_path.try_exists()? {
+        replace_cxx_header(&promises_path)?;
+    }
+
     let cxx_path = jni_base_path.join("include").join("cxx.h");
     debug!("Post-processing cxx.h: {:?}", cxx_path);
     if cxx_path.try_exists()? {
@@ -83,6 +83,16 @@ pub fn crate_libs(config: &CompleteConfig) -> Result<(), anyhow::Error> {
         replace_cxx_header(&signal_path)?;
     }
 
+    let streams_path = ios_base_path.join("include").join("CrabyStreams.h");
+    if streams_path.try_exists()? {
+        replace_cxx_header(&streams_path)?;
+    }
+
+    let promises_path = ios_base_path.join("include").join("CrabyPromises.h");
+    if promises_path.try_exists()? {
+        replace_cxx_header(&promises_path)?;
+    }
+
     let cxx_path = ios_base_path.join("include").join("cxx.h");
     if cxx_path.try_exists()? {
         replace_cxx_iter_template(&cxx_path)?;
@@ -53,7 +53,10 @@ pub fn perform(opts: CodegenOptions) -> anyhow::Result<()> {
             i + 1,
             total_schemas,
         );
-        print_schema(schema)?;
+        print_schema(
+            schema,
+            config.project.concurrency.resolve(&schema.module_name),
+        )?;
         println!();
     }
 
@@ -62,6 +65,9 @@ pub fn perform(opts: CodegenOptions) -> anyhow::Result<()> {
         root: opts.project_root,
         schemas,
         android_package_name: config.android.package_name,
+        worker_pool: config.project.worker_pool,
+        concurrency: config.project.concurrency,
+        panic: config.project.panic,
     };
 
     debug!("Cleaning up...");
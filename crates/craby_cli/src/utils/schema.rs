@@ -1,15 +1,15 @@
 use craby_codegen::types::Schema;
+use craby_common::config::ConcurrencyMode;
 use owo_colors::OwoColorize;
 
 use crate::utils::terminal::CodeHighlighter;
 
-pub fn print_schema(schema: &Schema) -> Result<(), anyhow::Error> {
+pub fn print_schema(schema: &Schema, concurrency: ConcurrencyMode) -> Result<(), anyhow::Error> {
     println!("├─ Methods ({})", schema.methods.len());
 
     let highlighter = CodeHighlighter::new();
-
     for (i, method) in schema.methods.iter().enumerate() {
-        match method.try_into_impl_sig() {
+        match method.try_into_impl_sig(method.as_rs_self_param(concurrency)) {
             Ok(method_sig) => {
                 let is_last = i == schema.methods.len() - 1;
                 let branch = if is_last { "└─" } else { "├─" };
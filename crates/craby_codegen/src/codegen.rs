@@ -1,12 +1,16 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use craby_common::{constants::SPEC_FILE_PREFIX, utils::fs::collect_files};
 use log::debug;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
     parser::{
-        native_spec_parser::try_parse_schema,
-        types::ParseError,
+        native_spec_parser::{try_parse_schema_with_imports, try_parse_types},
+        types::{ImportedTypes, ParseError},
         utils::{render_report, RenderReportOptions},
     },
     types::Schema,
@@ -17,6 +21,93 @@ pub struct CodegenOptions<'a> {
     pub source_dir: &'a PathBuf,
 }
 
+/// Types of the source files imported by the specs (eg. `import type { User } from './types'`).
+///
+/// Each source file is parsed once and shared across the spec files.
+struct TypeTable<'a> {
+    project_root: &'a PathBuf,
+    types: FxHashMap<PathBuf, ImportedTypes>,
+    /// Source files being parsed (to detect circular imports)
+    pending: FxHashSet<PathBuf>,
+}
+
+impl<'a> TypeTable<'a> {
+    fn new(project_root: &'a PathBuf) -> Self {
+        Self {
+            project_root,
+            types: FxHashMap::default(),
+            pending: FxHashSet::default(),
+        }
+    }
+
+    /// Returns the types of the import source relative to the importer file.
+    fn resolve(&mut self, importer: &Path, source: &str) -> Result<ImportedTypes, anyhow::Error> {
+        let path = resolve_import_path(importer, source)?;
+
+        if let Some(types) = self.types.get(&path) {
+            return Ok(types.clone());
+        }
+
+        if !self.pending.insert(path.clone()) {
+            anyhow::bail!("Circular type import: {}", path.display());
+        }
+
+        let src = fs::read_to_string(&path)?;
+        let res = try_parse_types(&src, &mut |source| self.resolve(&path, source));
+        self.pending.remove(&path);
+
+        let types = match res {
+            Ok(types) => types,
+            Err(ParseError::Oxc { diagnostics }) => {
+                render_report(
+                    diagnostics,
+                    RenderReportOptions {
+                        project_root: self.project_root,
+                        path: &path,
+                        src: &src,
+                    },
+                );
+                anyhow::bail!("Failed to parse types");
+            }
+            Err(ParseError::General(e)) => anyhow::bail!(e),
+        };
+
+        self.types.insert(path, types.clone());
+
+        Ok(types)
+    }
+}
+
+/// Resolves the relative import source (eg. `./types`) to the TypeScript file path.
+fn resolve_import_path(importer: &Path, source: &str) -> Result<PathBuf, anyhow::Error> {
+    let base = importer.parent().unwrap_or(Path::new(".")).join(source);
+    let with_suffix = |suffix: &str| {
+        let mut path = base.clone().into_os_string();
+        path.push(suffix);
+        PathBuf::from(path)
+    };
+    let candidates = [
+        base.clone(),
+        with_suffix(".ts"),
+        with_suffix(".tsx"),
+        base.join("index.ts"),
+    ];
+    let path = candidates.into_iter().find(|path| {
+        path.is_file()
+            && path
+                .extension()
+                .is_some_and(|ext| ext == "ts" || ext == "tsx")
+    });
+
+    match path {
+        Some(path) => Ok(path.canonicalize()?),
+        None => anyhow::bail!(
+            "Cannot resolve the type import: {source} (from {})",
+            importer.display()
+        ),
+    }
+}
+
 pub fn codegen<'a>(opts: CodegenOptions<'a>) -> Result<Vec<Schema>, anyhow::Error> {
     let srcs = collect_files(opts.source_dir, &|path: &PathBuf| {
         path.extension().unwrap_or_default() == "ts"
@@ -32,13 +123,15 @@ pub fn codegen<'a>(opts: CodegenOptions<'a>) -> Result<Vec<Schema>, anyhow::Erro
         anyhow::bail!("No native module specification files found.");
     }
 
+    let mut type_table = TypeTable::new(opts.project_root);
     let collected_schemas = srcs
         .iter()
         .map(|path| {
             let src = fs::read_to_string(path)?;
             let src = src.as_str();
 
-            match try_parse_schema(src) {
+            match try_parse_schema_with_imports(src, &mut |source| type_table.resolve(path, source))
+            {
                 Ok(schemas) => Ok(schemas),
                 Err(ParseError::Oxc { diagnostics }) => {
                     render_report(
@@ -3,16 +3,38 @@ pub const GENERATED_COMMENT: &str = "Auto generated by Craby. DO NOT EDIT.";
 pub mod specs {
     pub const NATIVE_MODULE_PKG: &str = "craby-modules";
     pub const NATIVE_MODULE_INTERFACE: &str = "NativeModule";
+    pub const HOST_OBJECT_INTERFACE: &str = "HostObject";
     pub const NATIVE_MODULE_REGISTRY: &str = "NativeModuleRegistry";
     pub const SIGNAL_TYPE: &str = "Signal";
+    pub const INT32_TYPE: &str = "Int32";
+    pub const INT64_TYPE: &str = "Int64";
+    pub const UINT8_TYPE: &str = "UInt8";
+    pub const FLOAT_TYPE: &str = "Float";
     pub const REGISTRY_GET: &str = "get";
     pub const REGISTRY_GET_ENFORCING: &str = "getEnforcing";
 
     pub const RESERVED_TYPE_PROMISE: &str = "Promise";
+    pub const RESERVED_TYPE_ASYNC_ITERABLE: &str = "AsyncIterable";
+    pub const RESERVED_TYPE_ABORT_SIGNAL: &str = "AbortSignal";
+    pub const RESERVED_TYPE_ARRAY_BUFFER: &str = "ArrayBuffer";
+    pub const RESERVED_TYPE_UINT8_ARRAY: &str = "Uint8Array";
+    pub const RESERVED_TYPE_RECORD: &str = "Record";
 
     /// `it_` is reserved for the `shared_ptr` of the module
     pub const RESERVED_ARG_NAME_MODULE: &str = "it_";
 
+    /// `stream` is reserved for the producer handle of the stream methods
+    pub const RESERVED_ARG_NAME_STREAM: &str = "stream";
+
+    /// `promise` is reserved for the promise handle of the async functions
+    pub const RESERVED_ARG_NAME_PROMISE: &str = "promise";
+
     /// `emit` is reserved for signals
     pub const RESERVED_METHOD_NAME_MODULE: &str = "emit";
+
+    /// Lifecycle hooks of the module spec trait (`on_invalidate`, `on_reload`)
+    pub const RESERVED_METHOD_NAMES_LIFECYCLE: [&str; 2] = ["on_invalidate", "on_reload"];
+
+    /// `getConstants` declares the module constants
+    pub const CONSTANTS_METHOD_NAME: &str = "getConstants";
 }
@@ -1,16 +1,20 @@
 use std::{fs, path::PathBuf};
 
 use craby_common::{
+    config::WorkerPool,
     constants::{cxx_bridge_include_dir, cxx_dir},
     utils::string::{camel_case, flat_case, pascal_case},
 };
 use indoc::formatdoc;
 
 use crate::{
-    constants::specs::RESERVED_ARG_NAME_MODULE,
-    platform::cxx::CxxMethod,
+    constants::specs::{CONSTANTS_METHOD_NAME, RESERVED_ARG_NAME_MODULE},
+    platform::{
+        cxx::{CxxHostObject, CxxMethod},
+        rust::as_rs_instance_type,
+    },
     types::{CodegenContext, CxxModuleName, CxxNamespace, Schema},
-    utils::indent_str,
+    utils::{indent_str, with_doc_comment},
 };
 
 use super::types::{GenerateResult, Generator, GeneratorInvoker, Template};
@@ -27,6 +31,14 @@ pub enum CxxFileType {
     UtilsHpp,
     /// CrabySignals.h
     SignalsH,
+    /// CrabyCallbacks.h
+    CallbacksH,
+    /// CrabyCancellation.h
+    CancellationH,
+    /// CrabyStreams.h
+    StreamsH,
+    /// CrabyPromises.h
+    PromisesH,
 }
 
 impl CxxTemplate {
@@ -60,15 +72,17 @@ impl CxxTemplate {
     ///        facebook::react::TurboModule &turboModule,
     ///        const facebook::jsi::Value args[], size_t count);
     /// ```
-    fn cxx_method_def(&self, name: &str) -> String {
+    fn cxx_method_def(&self, name: &str, doc: Option<&str>) -> String {
         let method_name = camel_case(name);
-        formatdoc! {
+        let method_def = formatdoc! {
             r#"
             static facebook::jsi::Value
             {method_name}(facebook::jsi::Runtime &rt,
                 facebook::react::TurboModule &turboModule,
                 const facebook::jsi::Value args[], size_t count);"#,
-        }
+        };
+
+        with_doc_comment(doc, method_def)
     }
 
     /// Returns the complete cxx TurboModule source/header files.
@@ -117,6 +131,7 @@ impl CxxTemplate {
     /// #include <ReactCommon/TurboModule.h>
     /// #include <jsi/jsi.h>
     /// #include <memory>
+    /// #include <optional>
     ///
     /// namespace craby {
     /// namespace myproject {
@@ -148,6 +163,7 @@ impl CxxTemplate {
         &self,
         schema: &Schema,
         project_name: &str,
+        worker_pool: &WorkerPool,
     ) -> Result<(String, String), anyhow::Error> {
         let cxx_ns = CxxNamespace::from(project_name);
         let cxx_mod = CxxModuleName::from(&schema.module_name);
@@ -167,7 +183,7 @@ impl CxxTemplate {
 
         let mut method_defs = cxx_methods
             .iter()
-            .map(|method| self.cxx_method_def(&method.name))
+            .map(|method| self.cxx_method_def(&method.name, method.doc.as_deref()))
             .collect::<Vec<_>>();
 
         // Functions implementations
@@ -193,7 +209,8 @@ impl CxxTemplate {
                 manager.registerDelegate(id,
                                          std::bind(&{cxx_mod}::emit,
                                          this,
-                                         std::placeholders::_1));"#,
+                                         std::placeholders::_1,
+                                         std::placeholders::_2));"#,
             };
 
             let unregister_stmt = formatdoc! {
@@ -280,13 +297,60 @@ impl CxxTemplate {
                 });
             }
 
-            method_defs.insert(0, "void emit(std::string name);".to_string());
+            method_defs.insert(
+                0,
+                "void emit(std::string name, std::shared_ptr<void> payload);".to_string(),
+            );
+
+            // Convert the type-erased signal payload to the JS value and invoke the listener
+            //
+            // ```cpp
+            // if (name == "onProgress") {
+            //   auto arg = react::bridging::toJs(rt, *std::static_pointer_cast<craby::mymodule::bridging::Progress>(payload));
+            //   listener->call(rt, arg);
+            // } else {
+            //   listener->call(rt);
+            // }
+            // ```
+            let mut invoke_stmts = schema
+                .signals
+                .iter()
+                .filter_map(|signal| {
+                    signal
+                        .payload_type
+                        .as_ref()
+                        .map(|payload_type| (&signal.name, payload_type))
+                })
+                .map(|(signal_name, payload_type)| -> Result<String, anyhow::Error> {
+                    let payload_type = payload_type.as_cxx_type(&cxx_ns)?;
+                    Ok(formatdoc! {
+                        r#"
+                        if (name == "{signal_name}") {{
+                          auto arg = react::bridging::toJs(rt, *std::static_pointer_cast<{payload_type}>(payload));
+                          listener->call(rt, arg);
+                        }}"#,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let invoke_stmts = if invoke_stmts.is_empty() {
+                "listener->call(rt);".to_string()
+            } else {
+                invoke_stmts.push(formatdoc! {
+                    r#"
+                    {{
+                      listener->call(rt);
+                    }}"#,
+                });
+                invoke_stmts.join(" else ")
+            };
+            let invoke_stmts = indent_str(&invoke_stmts, 8);
 
             method_impls.insert(
                 0,
                 formatdoc! {
                     r#"
-                    void {cxx_mod}::emit(std::string name) {{
+                    void {cxx_mod}::emit(std::string name, std::shared_ptr<void> payload) {{
                       std::vector<std::shared_ptr<facebook::jsi::Function>> listeners;
                       {{
                         std::lock_guard<std::mutex> lock(listenersMutex_);
@@ -300,8 +364,8 @@ impl CxxTemplate {
 
                       for (auto& listener : listeners) {{
                         try {{
-                          callInvoker_->invokeAsync([listener](jsi::Runtime &rt) {{
-                            listener->call(rt);
+                          callInvoker_->invokeAsync([name, listener, payload](jsi::Runtime &rt) {{
+                    {invoke_stmts}
                           }});
                         }} catch (const std::exception& err) {{
                           // Noop
@@ -316,8 +380,182 @@ impl CxxTemplate {
             (String::from("// No signals"), String::from("// No signals"))
         };
 
+        // Evaluate the module constants once and return the cached value without an FFI call
+        //
+        // ```cpp
+        // constants_ = craby::myproject::bridging::getMyModuleConstants(
+        //   reinterpret_cast<uintptr_t>(this),
+        //   rust::Str(dataPath.data(), dataPath.size()));
+        // ```
+        let (constants_stmt, constants_member) = if let Some(constants) = &schema.constants {
+            let constants_type = constants.as_cxx_type(&cxx_ns)?;
+            let rs_module_name = pascal_case(&schema.module_name);
+
+            method_maps.insert(
+                0,
+                format!("methodMap_[\"{CONSTANTS_METHOD_NAME}\"] = MethodMetadata{{0, &{cxx_mod}::{CONSTANTS_METHOD_NAME}}};"),
+            );
+            method_defs.insert(0, self.cxx_method_def(CONSTANTS_METHOD_NAME, None));
+            method_impls.insert(
+                0,
+                formatdoc! {
+                    r#"
+                    jsi::Value {cxx_mod}::{CONSTANTS_METHOD_NAME}(jsi::Runtime &rt,
+                                          react::TurboModule &turboModule,
+                                          const jsi::Value args[],
+                                          size_t count) {{
+                      auto &thisModule = static_cast<{cxx_mod} &>(turboModule);
+                      if (!thisModule.constants_.has_value()) {{
+                        throw jsi::JSError(rt, {cxx_ns}::utils::makeJsError(rt, thisModule.constantsError_));
+                      }}
+
+                      return react::bridging::toJs(rt, *thisModule.constants_);
+                    }}"#,
+                },
+            );
+
+            let constants_stmt = formatdoc! {
+                r#"
+                try {{
+                  constants_ = {cxx_ns}::bridging::get{rs_module_name}Constants(
+                    reinterpret_cast<uintptr_t>(this),
+                    rust::Str(dataPath.data(), dataPath.size()));
+                }} catch (const std::exception &err) {{
+                  constantsError_ = {cxx_ns}::utils::errorMessage(err);
+                }}"#,
+            };
+
+            let constants_member = formatdoc! {
+                r#"
+                std::optional<{constants_type}> constants_;
+                std::string constantsError_;"#,
+            };
+
+            (constants_stmt, constants_member)
+        } else {
+            (String::from("// No constants"), String::new())
+        };
+
+        let unregister_stmt = if schema.has_callbacks() {
+            formatdoc! {
+                r#"
+                {unregister_stmt}
+
+                // Release the retained callbacks
+                {cxx_ns}::callbacks::CallbackManager::getInstance().unregisterCallbacks(
+                  reinterpret_cast<uintptr_t>(this));"#,
+            }
+        } else {
+            unregister_stmt
+        };
+
+        let unregister_stmt = if schema.has_abort_signals() {
+            formatdoc! {
+                r#"
+                {unregister_stmt}
+
+                // Cancel the pending tokens (the pending promises are rejected with `AbortError`)
+                {cxx_ns}::cancellation::CancellationManager::getInstance().unregisterTokens(
+                  reinterpret_cast<uintptr_t>(this));"#,
+            }
+        } else {
+            unregister_stmt
+        };
+
+        let unregister_stmt = if schema.has_streams() {
+            formatdoc! {
+                r#"
+                {unregister_stmt}
+
+                // Cancel the running streams (unblocks the producers waiting for the consumer)
+                {cxx_ns}::streams::StreamManager::getInstance().unregisterStreams(
+                  reinterpret_cast<uintptr_t>(this));"#,
+            }
+        } else {
+            unregister_stmt
+        };
+
+        // Worker pool of the async methods (`[project.worker_pool]` in `craby.toml`)
+        //
+        // ```cpp
+        // threadPool_ = std::make_shared<craby::mymodule::utils::ThreadPool>(10);
+        // threadPool_ = craby::mymodule::utils::ThreadPool::shared(4, "", std::chrono::milliseconds(30000), 64);
+        // ```
+        let pool_args = if worker_pool.idle_timeout_ms == 0 && worker_pool.max_queue_size == 0 {
+            worker_pool.size.to_string()
+        } else {
+            format!(
+                "{}, \"\", std::chrono::milliseconds({}), {}",
+                worker_pool.size, worker_pool.idle_timeout_ms, worker_pool.max_queue_size
+            )
+        };
+        let (thread_pool_stmt, thread_pool_shutdown_stmt) = if worker_pool.shared {
+            (
+                format!("threadPool_ = {cxx_ns}::utils::ThreadPool::shared({pool_args});"),
+                format!("// Release the shared thread pool (shut down if no other module uses it)\n{cxx_ns}::utils::ThreadPool::releaseShared(threadPool_);"),
+            )
+        } else {
+            (
+                format!(
+                    "threadPool_ = std::make_shared<{cxx_ns}::utils::ThreadPool>({pool_args});"
+                ),
+                "// Shutdown thread pool\nthreadPool_->shutdown();".to_string(),
+            )
+        };
+
+        // Serial queue and named threads run the tasks one by one in call order
+        let dedicated_threads = schema.dedicated_threads();
+        let executor_stmts = dedicated_threads
+            .iter()
+            .map(|thread| {
+                format!(
+                    "\n  {} = std::make_shared<{cxx_ns}::utils::ThreadPool>(1, \"{}\");",
+                    thread.as_cxx_executor().unwrap(),
+                    thread.as_cxx_thread_name(),
+                )
+            })
+            .collect::<String>();
+        let executor_shutdown_stmts = dedicated_threads
+            .iter()
+            .map(|thread| format!("\n    {}->shutdown();", thread.as_cxx_executor().unwrap()))
+            .collect::<String>();
+        // Queued tasks of the module are dropped on invalidation (the running tasks are finished)
+        let cancel_stmts = std::iter::once("threadPool_".to_string())
+            .chain(
+                dedicated_threads
+                    .iter()
+                    .map(|thread| thread.as_cxx_executor().unwrap()),
+            )
+            .map(|executor| format!("\n  {executor}->cancel(reinterpret_cast<uintptr_t>(this));"))
+            .collect::<String>();
+        // The teardown thread owns the module and the executors (`this` may be destroyed first)
+        let teardown_captures = ["module_", "threadPool_"]
+            .into_iter()
+            .map(String::from)
+            .chain(
+                dedicated_threads
+                    .iter()
+                    .map(|thread| thread.as_cxx_executor().unwrap().to_string()),
+            )
+            .map(|member| format!("{member} = {member}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let executor_members = dedicated_threads
+            .iter()
+            .map(|thread| {
+                format!(
+                    "\n  std::shared_ptr<{cxx_ns}::utils::ThreadPool> {};",
+                    thread.as_cxx_executor().unwrap()
+                )
+            })
+            .collect::<String>();
+
         let rs_module_name = pascal_case(&schema.module_name);
+        // Module instance shared by the JS thread and the worker threads (see `[project.concurrency]`)
+        let rs_instance_type = as_rs_instance_type(&schema.module_name);
         let register_stmts = indent_str(&register_stmt, 2);
+        let thread_pool_shutdown_stmts = indent_str(&thread_pool_shutdown_stmt, 4);
+        let constants_stmts = indent_str(&constants_stmt, 2);
         let unregister_stmts = indent_str(&unregister_stmt, 2);
         let method_mapping_stmts = indent_str(&method_maps.join("\n"), 2);
         let method_impls = method_impls.join("\n\n");
@@ -330,32 +568,54 @@ impl CxxTemplate {
                 : TurboModule({cxx_mod}::kModuleName, jsInvoker) {{
             {register_stmts}
               callInvoker_ = std::move(jsInvoker);
-              module_ = std::shared_ptr<{cxx_ns}::bridging::{rs_module_name}>(
+              module_ = std::shared_ptr<{cxx_ns}::bridging::{rs_instance_type}>(
                 {cxx_ns}::bridging::create{rs_module_name}(
                   reinterpret_cast<uintptr_t>(this),
                   rust::Str(dataPath.data(), dataPath.size())).into_raw(),
-                []({cxx_ns}::bridging::{rs_module_name} *ptr) {{ rust::Box<{cxx_ns}::bridging::{rs_module_name}>::from_raw(ptr); }}
+                []({cxx_ns}::bridging::{rs_instance_type} *ptr) {{ rust::Box<{cxx_ns}::bridging::{rs_instance_type}>::from_raw(ptr); }}
               );
-              threadPool_ = std::make_shared<{cxx_ns}::utils::ThreadPool>(10);
+            {constants_stmts}
+              {thread_pool_stmt}{executor_stmts}
             {method_mapping_stmts}
             }}
 
             {cxx_mod}::~{cxx_mod}() {{
-              invalidate();
+              teardown(false);
             }}
 
+            // Called by the React Native runtime (eg. the JS bundle is reloaded)
             void {cxx_mod}::invalidate() {{
+              teardown(true);
+            }}
+
+            void {cxx_mod}::teardown(bool reload) {{
               if (invalidated_.exchange(true)) {{
                 return;
               }}
 
-              invalidated_.store(true);
               listenersMap_.clear();
             
             {unregister_stmts}
 
-              // Shutdown thread pool
-              threadPool_->shutdown();
+              // Drop the queued tasks of the module{cancel_stmts}
+
+              // The lifecycle hooks and the shutdown wait for the running calls (eg. the module lock),
+              // so the outgoing instance is notified on a background thread instead of blocking the JS thread
+              std::thread([{teardown_captures}, reload]() {{
+                // Errors of the lifecycle hooks are ignored
+                if (reload) {{
+                  try {{
+                    {cxx_ns}::bridging::onReload(*module_);
+                  }} catch (const std::exception &) {{
+                  }}
+                }}
+                try {{
+                  {cxx_ns}::bridging::onInvalidate(*module_);
+                }} catch (const std::exception &) {{
+                }}
+
+            {thread_pool_shutdown_stmts}{executor_shutdown_stmts}
+              }}).detach();
             }}
             
             {method_impls}"#,
@@ -376,8 +636,10 @@ impl CxxTemplate {
             {method_defs}
 
             protected:
+              void teardown(bool reload);
+
               std::shared_ptr<facebook::react::CallInvoker> callInvoker_;
-              std::shared_ptr<{cxx_ns}::bridging::{rs_module_name}> module_;
+              std::shared_ptr<{cxx_ns}::bridging::{rs_instance_type}> module_;
               std::atomic<bool> invalidated_{{false}};
               std::atomic<size_t> nextListenerId_{{0}};
               std::mutex listenersMutex_;
@@ -385,9 +647,14 @@ impl CxxTemplate {
                 std::string,
                 std::unordered_map<size_t, std::shared_ptr<facebook::jsi::Function>>>
                 listenersMap_;
-              std::shared_ptr<{cxx_ns}::utils::ThreadPool> threadPool_;
+              std::shared_ptr<{cxx_ns}::utils::ThreadPool> threadPool_;{executor_members}{constants_member}
             }};"#,
             turbo_module_name = schema.module_name,
+            constants_member = if constants_member.is_empty() {
+                constants_member
+            } else {
+                format!("\n{}", indent_str(&constants_member, 2))
+            },
         };
 
         let cpp_content = formatdoc! {
@@ -419,6 +686,7 @@ impl CxxTemplate {
             #include <ReactCommon/TurboModule.h>
             #include <jsi/jsi.h>
             #include <memory>
+            #include <optional>
             
             namespace craby {{
             namespace {project_ns} {{
@@ -441,6 +709,7 @@ impl CxxTemplate {
     /// ```cpp
     /// #pragma once
     ///
+    /// #include "CrabyUtils.hpp"
     /// #include "cxx.h"
     /// #include "ffi.rs.h"
     /// #include <react/bridging/Bridging.h>
@@ -462,23 +731,68 @@ impl CxxTemplate {
     ///   }
     /// };
     ///
+    /// template <>
+    /// struct Bridging<int64_t> {
+    ///   static int64_t fromJs(jsi::Runtime& rt, const jsi::Value &value, std::shared_ptr<CallInvoker> callInvoker) {
+    ///     return craby::mymodule::utils::asInt64(rt, value);
+    ///   }
+    ///
+    ///   static jsi::Value toJs(jsi::Runtime& rt, int64_t value) {
+    ///     return jsi::BigInt::fromInt64(rt, value);
+    ///   }
+    /// };
+    ///
+    /// // Bridging<uint8_t>, Bridging<rust::Vec<int32_t>>, Bridging<rust::Vec<float>>...
+    ///
+    /// template <>
+    /// struct Bridging<rust::Vec<uint8_t>> {
+    ///   static rust::Vec<uint8_t> fromJs(jsi::Runtime& rt, const jsi::Value &value, std::shared_ptr<CallInvoker> callInvoker) {
+    ///     // Copy bytes of the `ArrayBuffer` (or `Uint8Array`)
+    ///   }
+    ///
+    ///   static jsi::Value toJs(jsi::Runtime& rt, rust::Vec<uint8_t> vec) {
+    ///     // Wrap the `rust::Vec` with `jsi::MutableBuffer` without copying
+    ///     auto buf = std::make_shared<craby::mymodule::utils::RustBuffer>(std::move(vec));
+    ///     return jsi::ArrayBuffer(rt, buf);
+    ///   }
+    /// };
+    ///
     /// // Additional bridging templates for custom types...
     ///
     /// } // namespace react
     /// } // namespace facebook
+    ///
+    /// namespace craby {
+    /// namespace mymodule {
+    /// namespace handles {
+    ///
+    /// // `jsi::HostObject` classes of the host objects (eg. `ConnectionHostObject`)...
+    ///
+    /// } // namespace handles
+    /// } // namespace mymodule
+    /// } // namespace craby
     /// ```
     fn cxx_bridging(&self, ctx: &CodegenContext) -> Result<String, anyhow::Error> {
+        // Types shared across the modules (eg. imported types) are bridged once
         let bridging_templates = ctx
             .schemas
             .iter()
             .flat_map(|schema| schema.as_cxx_bridging_templates(&ctx.project_name))
             .flatten()
-            .collect::<Vec<_>>();
+            .fold(vec![], |mut templates, template| {
+                if !templates.contains(&template) {
+                    templates.push(template);
+                }
+                templates
+            });
 
+        let cxx_ns = CxxNamespace::from(&ctx.project_name);
+        let cxx_handles = self.cxx_handles(ctx)?;
         let cxx_bridging = formatdoc! {
             r#"
             #pragma once
 
+            #include "CrabyUtils.hpp"
             #include "cxx.h"
             #include "ffi.rs.h"
             #include <react/bridging/Bridging.h>
@@ -539,15 +853,185 @@ impl CxxTemplate {
                 return arr;
               }}
             }};
+
+            template <>
+            struct Bridging<int64_t> {{
+              static int64_t fromJs(jsi::Runtime& rt, const jsi::Value &value, std::shared_ptr<CallInvoker> callInvoker) {{
+                return {cxx_ns}::utils::asInt64(rt, value);
+              }}
+
+              static jsi::Value toJs(jsi::Runtime& rt, int64_t value) {{
+                return jsi::BigInt::fromInt64(rt, value);
+              }}
+            }};
+
+            template <>
+            struct Bridging<uint8_t> {{
+              static uint8_t fromJs(jsi::Runtime& rt, const jsi::Value &value, std::shared_ptr<CallInvoker> callInvoker) {{
+                return {cxx_ns}::utils::asUInt8(rt, value);
+              }}
+
+              static jsi::Value toJs(jsi::Runtime& rt, uint8_t value) {{
+                return jsi::Value(static_cast<double>(value));
+              }}
+            }};
+
+            // `Bridging<int32_t>` and `Bridging<float>` of React Native do not check the range of values
+            template <>
+            struct Bridging<rust::Vec<int32_t>> {{
+              static rust::Vec<int32_t> fromJs(jsi::Runtime& rt, const jsi::Value &value, std::shared_ptr<CallInvoker> callInvoker) {{
+                auto arr = value.asObject(rt).asArray(rt);
+                size_t len = arr.length(rt);
+                rust::Vec<int32_t> vec;
+                vec.reserve(len);
+
+                for (size_t i = 0; i < len; i++) {{
+                  vec.push_back({cxx_ns}::utils::asInt32(rt, arr.getValueAtIndex(rt, i)));
+                }}
+
+                return vec;
+              }}
+
+              static jsi::Array toJs(jsi::Runtime& rt, const rust::Vec<int32_t>& vec) {{
+                auto arr = jsi::Array(rt, vec.size());
+
+                for (size_t i = 0; i < vec.size(); i++) {{
+                  arr.setValueAtIndex(rt, i, jsi::Value(vec[i]));
+                }}
+
+                return arr;
+              }}
+            }};
+
+            template <>
+            struct Bridging<rust::Vec<float>> {{
+              static rust::Vec<float> fromJs(jsi::Runtime& rt, const jsi::Value &value, std::shared_ptr<CallInvoker> callInvoker) {{
+                auto arr = value.asObject(rt).asArray(rt);
+                size_t len = arr.length(rt);
+                rust::Vec<float> vec;
+                vec.reserve(len);
+
+                for (size_t i = 0; i < len; i++) {{
+                  vec.push_back({cxx_ns}::utils::asFloat(rt, arr.getValueAtIndex(rt, i)));
+                }}
+
+                return vec;
+              }}
+
+              static jsi::Array toJs(jsi::Runtime& rt, const rust::Vec<float>& vec) {{
+                auto arr = jsi::Array(rt, vec.size());
+
+                for (size_t i = 0; i < vec.size(); i++) {{
+                  arr.setValueAtIndex(rt, i, jsi::Value(static_cast<double>(vec[i])));
+                }}
+
+                return arr;
+              }}
+            }};
+
+            template <>
+            struct Bridging<rust::Slice<const uint8_t>> {{
+              static rust::Slice<const uint8_t> fromJs(jsi::Runtime& rt, const jsi::Value &value, std::shared_ptr<CallInvoker> callInvoker) {{
+                auto obj = value.asObject(rt);
+
+                if (obj.isArrayBuffer(rt)) {{
+                  auto buf = obj.getArrayBuffer(rt);
+                  return rust::Slice<const uint8_t>(buf.data(rt), buf.size(rt));
+                }}
+
+                // `TypedArray` (eg. `Uint8Array`) views
+                auto buf = obj.getPropertyAsObject(rt, "buffer").getArrayBuffer(rt);
+                auto offset = static_cast<size_t>(obj.getProperty(rt, "byteOffset").asNumber());
+                auto length = static_cast<size_t>(obj.getProperty(rt, "byteLength").asNumber());
+
+                return rust::Slice<const uint8_t>(buf.data(rt) + offset, length);
+              }}
+            }};
+
+            template <>
+            struct Bridging<rust::Vec<uint8_t>> {{
+              static rust::Vec<uint8_t> fromJs(jsi::Runtime& rt, const jsi::Value &value, std::shared_ptr<CallInvoker> callInvoker) {{
+                auto slice = react::bridging::fromJs<rust::Slice<const uint8_t>>(rt, value, callInvoker);
+                rust::Vec<uint8_t> vec;
+                vec.reserve(slice.size());
+
+                for (auto byte : slice) {{
+                  vec.push_back(byte);
+                }}
+
+                return vec;
+              }}
+
+              static jsi::Value toJs(jsi::Runtime& rt, rust::Vec<uint8_t> vec) {{
+                auto buf = std::make_shared<{cxx_ns}::utils::RustBuffer>(std::move(vec));
+                return jsi::ArrayBuffer(rt, buf);
+              }}
+            }};
             {bridging_templates}
             }} // namespace react
-            }} // namespace facebook"#,
+            }} // namespace facebook{cxx_handles}"#,
             bridging_templates = if bridging_templates.is_empty() { "".to_string() } else { format!("\n{}\n", bridging_templates.join("\n\n")) },
         };
 
         Ok(cxx_bridging)
     }
 
+    /// Generates the `jsi::HostObject` classes of the host objects returned by the module methods.
+    ///
+    /// The classes are declared first so that the host objects can return each other.
+    fn cxx_handles(&self, ctx: &CodegenContext) -> Result<String, anyhow::Error> {
+        let cxx_ns = CxxNamespace::from(&ctx.project_name);
+        let flat_name = flat_case(&ctx.project_name);
+
+        // Host objects returned by the multiple modules are generated once
+        let mut host_objects: Vec<CxxHostObject> = vec![];
+        for handle in ctx.schemas.iter().flat_map(|schema| &schema.handles) {
+            let host_object = handle.as_cxx_host_object(&cxx_ns)?;
+            if !host_objects.iter().any(|h| h.name == host_object.name) {
+                host_objects.push(host_object);
+            }
+        }
+
+        if host_objects.is_empty() {
+            return Ok(String::new());
+        }
+
+        let forward_decls = host_objects
+            .iter()
+            .map(|host_object| format!("class {};", host_object.name))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let class_defs = host_objects
+            .iter()
+            .map(|host_object| host_object.class_def.clone())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let method_impls = host_objects
+            .into_iter()
+            .flat_map(|host_object| host_object.method_impls)
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        Ok(formatdoc! {
+            r#"
+
+
+            namespace craby {{
+            namespace {flat_name} {{
+            namespace handles {{
+
+            {forward_decls}
+
+            {class_defs}
+
+            {method_impls}
+
+            }} // namespace handles
+            }} // namespace {flat_name}
+            }} // namespace craby"#,
+        })
+    }
+
     /// Generates C++ utils header file.
     ///
     /// # Generated Code
@@ -557,11 +1041,21 @@ impl CxxTemplate {
     ///
     /// #include "cxx.h"
     /// #include "ffi.rs.h"
+    /// #include <ReactCommon/CallInvoker.h>
+    /// #include <algorithm>
+    /// #include <chrono>
+    /// #include <cmath>
     /// #include <condition_variable>
+    /// #include <deque>
     /// #include <functional>
+    /// #include <jsi/jsi.h>
+    /// #include <limits>
+    /// #include <map>
     /// #include <mutex>
-    /// #include <queue>
+    /// #include <pthread.h>
+    /// #include <string>
     /// #include <thread>
+    /// #include <tuple>
     /// #include <vector>
     ///
     /// namespace craby {
@@ -569,180 +1063,1455 @@ impl CxxTemplate {
     /// namespace utils {
     ///
     /// class ThreadPool {
-    /// private:
-    ///   bool stop;
-    ///   std::mutex mutex;
-    ///   std::condition_variable condition;
-    ///   std::queue<std::function<void()>> tasks;
-    ///   std::vector<std::thread> workers;
-    /// }
-    ///
     /// public:
-    ///   ThreadPool(size_t num_threads = 10) : stop(false) {
-    ///     for (size_t i = 0; i < num_threads; ++i) {
-    ///       workers.emplace_back([this] {
-    ///         while (true) {
-    ///           std::function<void()> task;
-    ///
-    ///           {
-    ///             std::unique_lock<std::mutex> lock(this->mutex);
-    ///             this->condition.wait(
-    ///                 lock, [this] { return this->stop || !this->tasks.empty(); });
-    ///
-    ///           if (this->stop && this->tasks.empty()) {
-    ///             return;
-    ///           }
-    ///
-    ///           task = std::move(this->tasks.front());
-    ///           this->tasks.pop();
-    ///         }
-    ///
-    ///         task();
-    ///       }
-    ///     });
-    ///   }
+    ///   // Worker threads are spawned on demand (up to `num_threads`)
+    ///   ThreadPool(size_t num_threads = 10,
+    ///              std::string name = "",
+    ///              std::chrono::milliseconds idle_timeout = std::chrono::milliseconds(0),
+    ///              size_t max_queue_size = 0);
     ///
-    ///   template <class F> void enqueue(F &&f) {
-    ///     {
-    ///       std::unique_lock<std::mutex> lock(mutex);
-    ///       if (stop) {
-    ///         return;
-    ///       }
-    ///       tasks.emplace(std::forward<F>(f));
-    ///     }
-    ///     condition.notify_one();
-    ///   }
+    ///   // Worker pool shared by the modules with the same configuration (`shared = true`)
+    ///   static std::shared_ptr<ThreadPool> shared(size_t num_threads, ...);
+    ///   // Shuts down the shared pool when the last module releases it
+    ///   static void releaseShared(const std::shared_ptr<ThreadPool> &pool);
     ///
-    ///   void shutdown() {
-    ///     {
-    ///       std::unique_lock<std::mutex> lock(mutex);
-    ///       stop = true;
-    ///       std::queue<std::function<void()>> empty;
-    ///       std::swap(tasks, empty);
-    ///     }
+    ///   // Returns `false` if the task is rejected (the queue is full or the pool is shut down)
+    ///   template <class F> bool enqueue(F &&f, uintptr_t owner = 0);
+    ///   // Drops the queued tasks of the owner (eg. the invalidated module)
+    ///   void cancel(uintptr_t owner);
     ///
-    ///     condition.notify_all();
+    ///   void shutdown();
+    /// };
     ///
-    ///     for (std::thread &worker : workers) {
-    ///       if (worker.joinable()) {
-    ///         worker.join();
-    ///       }
-    ///     }
+    /// inline std::string errorMessage(const std::exception &err) {
+    ///   const auto* rs_err = dynamic_cast<const rust::Error*>(&err);
+    ///   return std::string(rs_err ? rs_err->what() : err.what());
+    /// }
+    ///
+    /// class RustBuffer : public facebook::jsi::MutableBuffer {
+    /// private:
+    ///   rust::Vec<uint8_t> vec;
+    ///
+    /// public:
+    ///   explicit RustBuffer(rust::Vec<uint8_t> vec) : vec(std::move(vec)) {}
+    ///
+    ///   size_t size() const override {
+    ///     return vec.size();
     ///   }
     ///
-    ///   ~ThreadPool() {
-    ///     shutdown();
+    ///   uint8_t *data() override {
+    ///     return vec.data();
     ///   }
     /// };
     ///
-    /// inline std::string errorMessage(const std::exception &err) {
-    ///   const auto* rs_err = dynamic_cast<const rust::Error*>(&err);
-    ///   return std::string(rs_err ? rs_err->what() : err.what());
+    /// inline std::shared_ptr<facebook::jsi::Function>
+    /// makeSharedFunction(facebook::jsi::Runtime &rt,
+    ///                    const facebook::jsi::Value &value,
+    ///                    std::shared_ptr<facebook::react::CallInvoker> callInvoker) {
+    ///   // Release the JS function on the JS thread
     /// }
     ///
+    /// inline int32_t asInt32(facebook::jsi::Runtime &rt, const facebook::jsi::Value &value) {
+    ///   // Throws `jsi::JSError` if the value is not an integer in range of `int32_t`
+    /// }
+    ///
+    /// // asInt64, asUInt8, asFloat...
+    ///
     /// } // namespace utils
     /// } // namespace mymodule
     /// } // namespace craby
     /// ```
-    fn cxx_utils(&self, project_name: &str) -> Result<String, anyhow::Error> {
+    fn cxx_utils(
+        &self,
+        project_name: &str,
+        has_abort_signals: bool,
+        has_streams: bool,
+    ) -> Result<String, anyhow::Error> {
         let flat_name = flat_case(project_name);
 
-        Ok(formatdoc! {
+        let mut includes = vec![];
+        let mut helpers = vec![];
+
+        // JS promise helpers for the promise methods and the stream iterators
+        helpers.push(formatdoc! {
             r#"
-            #pragma once
+            // Prefix of the structured errors (`craby::Error`) encoded as JSON by the Rust bindings
+            constexpr const char *structuredErrorPrefix = "\001craby-error:";
+
+            // Creates a JS `Error` from the error message (with `code` and `details` if structured)
+            inline facebook::jsi::Value makeJsError(facebook::jsi::Runtime &rt, const std::string &message) {{
+              std::string prefix(structuredErrorPrefix);
+              if (message.rfind(prefix, 0) != 0) {{
+                return facebook::jsi::JSError(rt, message).value();
+              }}
 
-            #include "cxx.h"
-            #include "ffi.rs.h"
-            #include <condition_variable>
-            #include <functional>
-            #include <mutex>
-            #include <queue>
-            #include <thread>
-            #include <vector>
+              auto json = rt.global()
+                              .getPropertyAsObject(rt, "JSON")
+                              .getPropertyAsFunction(rt, "parse")
+                              .call(rt, facebook::jsi::String::createFromUtf8(rt, message.substr(prefix.size())))
+                              .asObject(rt);
+              auto error = facebook::jsi::JSError(rt, json.getProperty(rt, "message").asString(rt).utf8(rt))
+                               .value()
+                               .asObject(rt);
+              error.setProperty(rt, "code", json.getProperty(rt, "code"));
+              error.setProperty(rt, "details", json.getProperty(rt, "details"));
+              return error;
+            }}
 
-            namespace craby {{
-            namespace {flat_name} {{
-            namespace utils {{
+            // Structured error of the task rejected by the worker pool (the queue is full or the module is invalidated)
+            inline std::string rejectedTaskError() {{
+              return std::string(structuredErrorPrefix) +
+                     R"({{"code":"TASK_REJECTED","message":"The task is rejected by the worker pool","details":null}})";
+            }}
 
-            class ThreadPool {{
-            private:
-              bool stop;
-              std::mutex mutex;
-              std::condition_variable condition;
-              std::queue<std::function<void()>> tasks;
-              std::vector<std::thread> workers;
+            using Executor = std::function<void(facebook::jsi::Runtime &rt,
+                                                std::shared_ptr<facebook::jsi::Function> resolve,
+                                                std::shared_ptr<facebook::jsi::Function> reject)>;
+
+            using ValueFactory = std::function<facebook::jsi::Value(facebook::jsi::Runtime &rt)>;
+            using Cleanup = std::function<void(facebook::jsi::Runtime &rt)>;
+
+            inline facebook::jsi::Value
+            makePromise(facebook::jsi::Runtime &rt,
+                        std::shared_ptr<facebook::react::CallInvoker> callInvoker,
+                        Executor executor) {{
+              auto promiseCtor = rt.global().getPropertyAsFunction(rt, "Promise");
+              auto fn = facebook::jsi::Function::createFromHostFunction(
+                  rt, facebook::jsi::PropNameID::forAscii(rt, "executor"), 2,
+                  [callInvoker, executor](facebook::jsi::Runtime &rt,
+                                          const facebook::jsi::Value &,
+                                          const facebook::jsi::Value *args,
+                                          size_t) -> facebook::jsi::Value {{
+                    executor(rt,
+                             makeSharedFunction(rt, args[0], callInvoker),
+                             makeSharedFunction(rt, args[1], callInvoker));
+                    return facebook::jsi::Value::undefined();
+                  }});
+
+              return promiseCtor.callAsConstructor(rt, fn);
+            }}
+
+            // JS promise that is settled once on the JS thread (by the result, or by the abort signal first)
+            class Promise {{
+            public:
+              explicit Promise(std::shared_ptr<facebook::react::CallInvoker> callInvoker)
+                  : callInvoker_(std::move(callInvoker)) {{}}
+
+              facebook::jsi::Value create(facebook::jsi::Runtime &rt) {{
+                return makePromise(rt, callInvoker_, [this](facebook::jsi::Runtime &,
+                                                            std::shared_ptr<facebook::jsi::Function> resolve,
+                                                            std::shared_ptr<facebook::jsi::Function> reject) {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  resolve_ = std::move(resolve);
+                  reject_ = std::move(reject);
+                }});
+              }}
+
+              void resolve(ValueFactory toJs) {{
+                settle([toJs](facebook::jsi::Runtime &rt,
+                              std::shared_ptr<facebook::jsi::Function> resolve,
+                              std::shared_ptr<facebook::jsi::Function>) {{
+                  resolve->call(rt, toJs(rt));
+                }});
+              }}
+
+              void reject(std::string message) {{
+                rejectWith([message](facebook::jsi::Runtime &rt) {{ return makeJsError(rt, message); }});
+              }}
+
+              void rejectWith(ValueFactory toError) {{
+                settle([toError](facebook::jsi::Runtime &rt,
+                                 std::shared_ptr<facebook::jsi::Function>,
+                                 std::shared_ptr<facebook::jsi::Function> reject) {{
+                  reject->call(rt, toError(rt));
+                }});
+              }}
+
+              // Runs the cleanup on the JS thread after the promise is settled (eg. removes the `AbortSignal` listener)
+              void onSettled(Cleanup cleanup) {{
+                {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  if (resolve_) {{
+                    onSettled_ = std::move(cleanup);
+                    return;
+                  }}
+                }}
+
+                callInvoker_->invokeAsync(std::move(cleanup));
+              }}
+
+            private:
+              void settle(Executor settler) {{
+                std::shared_ptr<facebook::jsi::Function> resolve;
+                std::shared_ptr<facebook::jsi::Function> reject;
+                Cleanup cleanup;
+                {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  if (!resolve_) {{
+                    return;
+                  }}
+                  resolve = std::move(resolve_);
+                  reject = std::move(reject_);
+                  cleanup = std::move(onSettled_);
+                }}
+
+                callInvoker_->invokeAsync([settler, resolve, reject, cleanup = std::move(cleanup)](facebook::jsi::Runtime &rt) {{
+                  settler(rt, resolve, reject);
+                  if (cleanup) {{
+                    cleanup(rt);
+                  }}
+                }});
+              }}
+
+              std::shared_ptr<facebook::react::CallInvoker> callInvoker_;
+              std::shared_ptr<facebook::jsi::Function> resolve_;
+              std::shared_ptr<facebook::jsi::Function> reject_;
+              Cleanup onSettled_;
+              std::mutex mutex_;
+            }};"#,
+        });
+
+        // Abortable promise helpers for the promise methods with `AbortSignal`
+        if has_abort_signals {
+            includes.push("CrabyCancellation.h");
+            helpers.push(formatdoc! {
+                r#"
+                inline facebook::jsi::Value makeAbortError(facebook::jsi::Runtime &rt) {{
+                  auto error = facebook::jsi::JSError(rt, "The operation was aborted").value().asObject(rt);
+                  error.setProperty(rt, "name", "AbortError");
+                  return error;
+                }}
+
+                // Registers the cancellation token and subscribes to the `AbortSignal` (if provided)
+                inline size_t registerAbortSignal(facebook::jsi::Runtime &rt,
+                                                  const facebook::jsi::Value &value,
+                                                  uintptr_t owner,
+                                                  std::shared_ptr<Promise> promise) {{
+                  auto &manager = cancellation::CancellationManager::getInstance();
+                  auto id = manager.registerToken(owner, [promise]() {{ promise->rejectWith(makeAbortError); }});
+
+                  if (value.isUndefined() || value.isNull()) {{
+                    return id;
+                  }}
+
+                  auto signal = value.asObject(rt);
+                  auto aborted = signal.getProperty(rt, "aborted");
+                  if (aborted.isBool() && aborted.getBool()) {{
+                    manager.cancel(id);
+                    return id;
+                  }}
+
+                  auto onAbort = facebook::jsi::Function::createFromHostFunction(
+                      rt, facebook::jsi::PropNameID::forAscii(rt, "onAbort"), 0,
+                      [id](facebook::jsi::Runtime &,
+                           const facebook::jsi::Value &,
+                           const facebook::jsi::Value *,
+                           size_t) -> facebook::jsi::Value {{
+                        cancellation::CancellationManager::getInstance().cancel(id);
+                        return facebook::jsi::Value::undefined();
+                      }});
+
+                  auto options = facebook::jsi::Object(rt);
+                  options.setProperty(rt, "once", true);
+                  signal.getPropertyAsFunction(rt, "addEventListener")
+                      .callWithThis(rt, signal, facebook::jsi::String::createFromAscii(rt, "abort"), onAbort, options);
+
+                  // Remove the listener when the promise is settled (the signal may outlive the promise)
+                  auto signalRef = std::make_shared<facebook::jsi::Object>(std::move(signal));
+                  auto onAbortRef = std::make_shared<facebook::jsi::Function>(std::move(onAbort));
+                  promise->onSettled([signalRef, onAbortRef](facebook::jsi::Runtime &rt) {{
+                    signalRef->getPropertyAsFunction(rt, "removeEventListener")
+                        .callWithThis(rt, *signalRef, facebook::jsi::String::createFromAscii(rt, "abort"), *onAbortRef);
+                  }});
+
+                  return id;
+                }}"#,
+            });
+        }
+
+        // JS async iterable helpers for the stream methods
+        if has_streams {
+            includes.push("CrabyStreams.h");
+            helpers.push(formatdoc! {
+                r#"
+                    using StreamItemToJs =
+                        std::function<facebook::jsi::Value(facebook::jsi::Runtime &rt, std::shared_ptr<void> item)>;
+
+                    // Cancels the stream when the JS iterator is garbage collected
+                    class StreamConsumer {{
+                    public:
+                      explicit StreamConsumer(std::shared_ptr<streams::Stream> stream) : stream(std::move(stream)) {{}}
+
+                      ~StreamConsumer() {{
+                        stream->cancel();
+                      }}
+
+                      std::shared_ptr<streams::Stream> stream;
+                    }};
+
+                    inline facebook::jsi::Object
+                    makeIteratorResult(facebook::jsi::Runtime &rt, facebook::jsi::Value value, bool done) {{
+                      auto result = facebook::jsi::Object(rt);
+                      result.setProperty(rt, "value", std::move(value));
+                      result.setProperty(rt, "done", done);
+                      return result;
+                    }}
+
+                    inline facebook::jsi::Object
+                    makeAsyncIterable(facebook::jsi::Runtime &rt,
+                                      std::shared_ptr<facebook::react::CallInvoker> callInvoker,
+                                      std::shared_ptr<streams::Stream> stream,
+                                      StreamItemToJs toJs) {{
+                      auto consumer = std::make_shared<StreamConsumer>(stream);
+                      auto iterator = facebook::jsi::Object(rt);
+
+                      // Each pull resolves the promise on the JS thread once the item is available
+                      auto next = facebook::jsi::Function::createFromHostFunction(
+                          rt, facebook::jsi::PropNameID::forAscii(rt, "next"), 0,
+                          [callInvoker, consumer, toJs](facebook::jsi::Runtime &rt,
+                                                        const facebook::jsi::Value &,
+                                                        const facebook::jsi::Value *,
+                                                        size_t) -> facebook::jsi::Value {{
+                            auto stream = consumer->stream;
+                            return makePromise(rt, callInvoker, [callInvoker, stream, toJs](
+                                                                    facebook::jsi::Runtime &,
+                                                                    std::shared_ptr<facebook::jsi::Function> resolve,
+                                                                    std::shared_ptr<facebook::jsi::Function> reject) {{
+                              stream->pull([callInvoker, toJs, resolve, reject](streams::Chunk chunk) {{
+                                callInvoker->invokeAsync([toJs, resolve, reject, chunk](facebook::jsi::Runtime &rt) {{
+                                  if (chunk.error.has_value()) {{
+                                    reject->call(rt, makeJsError(rt, *chunk.error));
+                                    return;
+                                  }}
+
+                                  auto value = chunk.done ? facebook::jsi::Value::undefined() : toJs(rt, chunk.item);
+                                  resolve->call(rt, makeIteratorResult(rt, std::move(value), chunk.done));
+                                }});
+                              }});
+                            }});
+                          }});
+
+                      // `return()` is called when the consumer stops iterating (eg. `break` in `for await`)
+                      auto cancel = facebook::jsi::Function::createFromHostFunction(
+                          rt, facebook::jsi::PropNameID::forAscii(rt, "return"), 0,
+                          [callInvoker, consumer](facebook::jsi::Runtime &rt,
+                                                  const facebook::jsi::Value &,
+                                                  const facebook::jsi::Value *,
+                                                  size_t) -> facebook::jsi::Value {{
+                            consumer->stream->cancel();
+                            return makePromise(rt, callInvoker, [](facebook::jsi::Runtime &rt,
+                                                                   std::shared_ptr<facebook::jsi::Function> resolve,
+                                                                   std::shared_ptr<facebook::jsi::Function>) {{
+                              resolve->call(rt, makeIteratorResult(rt, facebook::jsi::Value::undefined(), true));
+                            }});
+                          }});
+
+                      auto self = facebook::jsi::Function::createFromHostFunction(
+                          rt, facebook::jsi::PropNameID::forAscii(rt, "asyncIterator"), 0,
+                          [](facebook::jsi::Runtime &rt,
+                             const facebook::jsi::Value &thisValue,
+                             const facebook::jsi::Value *,
+                             size_t) -> facebook::jsi::Value {{
+                            return facebook::jsi::Value(rt, thisValue);
+                          }});
+
+                      iterator.setProperty(rt, "next", next);
+                      iterator.setProperty(rt, "return", cancel);
+
+                      // Fallback to the `@@asyncIterator` key if the runtime does not support `Symbol.asyncIterator`
+                      auto asyncIterator = rt.global().getPropertyAsObject(rt, "Symbol").getProperty(rt, "asyncIterator");
+                      if (asyncIterator.isSymbol()) {{
+                        iterator.setProperty(
+                            rt, facebook::jsi::PropNameID::forSymbol(rt, asyncIterator.getSymbol(rt)), self);
+                      }} else {{
+                        iterator.setProperty(rt, "@@asyncIterator", self);
+                      }}
+
+                      return iterator;
+                    }}"#,
+            });
+        }
+
+        let extra_includes = includes
+            .iter()
+            .map(|include| format!("\n#include \"{include}\""))
+            .collect::<String>();
+        let extra_utils = helpers
+            .iter()
+            .map(|helper| format!("\n\n{helper}"))
+            .collect::<String>();
+
+        Ok(formatdoc! {
+            r#"
+            #pragma once
+
+            #include "cxx.h"
+            #include "ffi.rs.h"{extra_includes}
+            #include <ReactCommon/CallInvoker.h>
+            #include <algorithm>
+            #include <chrono>
+            #include <cmath>
+            #include <condition_variable>
+            #include <deque>
+            #include <functional>
+            #include <jsi/jsi.h>
+            #include <limits>
+            #include <map>
+            #include <mutex>
+            #include <pthread.h>
+            #include <string>
+            #include <thread>
+            #include <tuple>
+            #include <vector>
+
+            namespace craby {{
+            namespace {flat_name} {{
+            namespace utils {{
+
+            class ThreadPool {{
+            private:
+              bool stop;
+              std::mutex mutex;
+              std::condition_variable condition;
+              // Queued tasks with their owner (eg. the module)
+              std::deque<std::pair<uintptr_t, std::function<void()>>> tasks;
+              std::vector<std::thread> workers;
+              // Worker threads exited by the idle timeout (joined on the next spawn)
+              std::vector<std::thread::id> exitedWorkers;
+              size_t idleWorkers;
+              size_t maxWorkers;
+              size_t maxQueueSize;
+              std::chrono::milliseconds idleTimeout;
+              std::string name;
 
             public:
-              ThreadPool(size_t num_threads = 10) : stop(false) {{
-                for (size_t i = 0; i < num_threads; ++i) {{
-                  workers.emplace_back([this] {{
-                    while (true) {{
-                      std::function<void()> task;
+              ThreadPool(size_t num_threads = 10,
+                         std::string name = "",
+                         std::chrono::milliseconds idle_timeout = std::chrono::milliseconds(0),
+                         size_t max_queue_size = 0)
+                  : stop(false),
+                    idleWorkers(0),
+                    maxWorkers(std::max<size_t>(num_threads, 1)),
+                    maxQueueSize(max_queue_size),
+                    idleTimeout(idle_timeout),
+                    name(std::move(name)) {{}}
+
+              // Worker pool shared by the modules (`shared = true` in `craby.toml`)
+              // Modules with the different configurations do not share the pool.
+              static std::shared_ptr<ThreadPool> shared(size_t num_threads,
+                                                        std::string name = "",
+                                                        std::chrono::milliseconds idle_timeout = std::chrono::milliseconds(0),
+                                                        size_t max_queue_size = 0) {{
+                std::lock_guard<std::mutex> lock(sharedMutex());
+                auto &entry = sharedPools()[std::make_tuple(num_threads, name, idle_timeout.count(), max_queue_size)];
+                if (!entry.first) {{
+                  entry.first = std::make_shared<ThreadPool>(num_threads, std::move(name), idle_timeout, max_queue_size);
+                }}
+                entry.second++;
+                return entry.first;
+              }}
+
+              // Shuts down the shared pool when the last module releases it
+              static void releaseShared(const std::shared_ptr<ThreadPool> &pool) {{
+                {{
+                  std::lock_guard<std::mutex> lock(sharedMutex());
+                  auto &pools = sharedPools();
+                  auto it = std::find_if(pools.begin(), pools.end(),
+                                         [&pool](const auto &entry) {{ return entry.second.first == pool; }});
+                  if (it == pools.end() || --it->second.second > 0) {{
+                    return;
+                  }}
+                  pools.erase(it);
+                }}
+
+                pool->shutdown();
+              }}
+
+              // Returns `false` if the task is rejected (the queue is full or the pool is shut down)
+              template <class F> bool enqueue(F &&f, uintptr_t owner = 0) {{
+                {{
+                  std::unique_lock<std::mutex> lock(mutex);
+                  if (stop || (maxQueueSize > 0 && tasks.size() >= maxQueueSize)) {{
+                    return false;
+                  }}
+                  tasks.emplace_back(owner, std::forward<F>(f));
+
+                  // Spawn a worker thread if the idle workers cannot take the pending tasks
+                  joinExitedWorkers();
+                  if (idleWorkers < tasks.size() && workers.size() < maxWorkers) {{
+                    workers.emplace_back([this] {{ work(); }});
+                  }}
+                }}
+                condition.notify_one();
+                return true;
+              }}
+
+              // Drops the queued tasks of the owner (the running tasks are not interrupted)
+              void cancel(uintptr_t owner) {{
+                // Destroy the tasks outside of the lock
+                std::vector<std::function<void()>> cancelled;
+                {{
+                  std::unique_lock<std::mutex> lock(mutex);
+                  for (auto it = tasks.begin(); it != tasks.end();) {{
+                    if (it->first == owner) {{
+                      cancelled.push_back(std::move(it->second));
+                      it = tasks.erase(it);
+                    }} else {{
+                      ++it;
+                    }}
+                  }}
+                }}
+              }}
+
+              void shutdown() {{
+                {{
+                  std::unique_lock<std::mutex> lock(mutex);
+                  stop = true;
+                  tasks.clear();
+                }}
+
+                condition.notify_all();
+
+                for (std::thread &worker : workers) {{
+                  if (worker.joinable()) {{
+                    worker.join();
+                  }}
+                }}
+              }}
+
+              ~ThreadPool() {{
+                shutdown();
+              }}
+
+            private:
+              void work() {{
+                if (!name.empty()) {{
+                  setThreadName(name);
+                }}
+
+                std::unique_lock<std::mutex> lock(mutex);
+                while (true) {{
+                  auto ready = [this] {{ return stop || !tasks.empty(); }};
+
+                  idleWorkers++;
+                  bool hasTask = true;
+                  if (idleTimeout.count() > 0) {{
+                    hasTask = condition.wait_for(lock, idleTimeout, ready);
+                  }} else {{
+                    condition.wait(lock, ready);
+                  }}
+                  idleWorkers--;
+
+                  // Shrink the pool if the worker has been idle for the timeout
+                  if (!hasTask) {{
+                    exitedWorkers.push_back(std::this_thread::get_id());
+                    return;
+                  }}
+
+                  if (stop && tasks.empty()) {{
+                    return;
+                  }}
+
+                  auto task = std::move(tasks.front().second);
+                  tasks.pop_front();
+
+                  lock.unlock();
+                  task();
+                  lock.lock();
+                }}
+              }}
+
+              void joinExitedWorkers() {{
+                for (auto &id : exitedWorkers) {{
+                  auto it = std::find_if(workers.begin(), workers.end(),
+                                         [&id](std::thread &worker) {{ return worker.get_id() == id; }});
+                  if (it != workers.end()) {{
+                    it->join();
+                    workers.erase(it);
+                  }}
+                }}
+                exitedWorkers.clear();
+              }}
+
+              // Shared pools and the number of the modules using them (key: pool configuration)
+              using SharedKey = std::tuple<size_t, std::string, std::chrono::milliseconds::rep, size_t>;
+
+              static std::map<SharedKey, std::pair<std::shared_ptr<ThreadPool>, size_t>> &sharedPools() {{
+                static std::map<SharedKey, std::pair<std::shared_ptr<ThreadPool>, size_t>> pools;
+                return pools;
+              }}
+
+              static std::mutex &sharedMutex() {{
+                static std::mutex mutex;
+                return mutex;
+              }}
+
+              // Shown in the debuggers and profilers (eg. `io` thread of `@thread io`)
+              static void setThreadName(const std::string &name) {{
+            #if defined(__APPLE__)
+                pthread_setname_np(name.c_str());
+            #else
+                // Thread name is limited to 16 bytes including the null terminator
+                pthread_setname_np(pthread_self(), name.substr(0, 15).c_str());
+            #endif
+              }}
+            }};
+
+            inline std::string errorMessage(const std::exception &err) {{
+              const auto* rs_err = dynamic_cast<const rust::Error*>(&err);
+              return std::string(rs_err ? rs_err->what() : err.what());
+            }}
+
+            class RustBuffer : public facebook::jsi::MutableBuffer {{
+            private:
+              rust::Vec<uint8_t> vec;
+
+            public:
+              explicit RustBuffer(rust::Vec<uint8_t> vec) : vec(std::move(vec)) {{}}
+
+              size_t size() const override {{
+                return vec.size();
+              }}
+
+              uint8_t *data() override {{
+                return vec.data();
+              }}
+            }};
+
+            inline std::shared_ptr<facebook::jsi::Function>
+            makeSharedFunction(facebook::jsi::Runtime &rt,
+                               const facebook::jsi::Value &value,
+                               std::shared_ptr<facebook::react::CallInvoker> callInvoker) {{
+              auto fn = new facebook::jsi::Function(value.asObject(rt).asFunction(rt));
+
+              // The last reference can be dropped on any thread (eg. Rust callback handles),
+              // but the JS function must be released on the JS thread
+              return std::shared_ptr<facebook::jsi::Function>(fn, [callInvoker](facebook::jsi::Function *fn) {{
+                callInvoker->invokeAsync([fn](facebook::jsi::Runtime &) {{ delete fn; }});
+              }});
+            }}
+
+            inline facebook::jsi::JSError
+            outOfRangeError(facebook::jsi::Runtime &rt, const std::string &type_name) {{
+              return facebook::jsi::JSError(rt, "Value is out of range (" + type_name + ")");
+            }}
+
+            template <typename T>
+            inline T asInteger(facebook::jsi::Runtime &rt,
+                               const facebook::jsi::Value &value,
+                               const std::string &type_name) {{
+              auto num = value.asNumber();
+
+              if (std::trunc(num) != num ||
+                  num < static_cast<double>(std::numeric_limits<T>::min()) ||
+                  num > static_cast<double>(std::numeric_limits<T>::max())) {{
+                throw outOfRangeError(rt, type_name);
+              }}
+
+              return static_cast<T>(num);
+            }}
+
+            inline int32_t asInt32(facebook::jsi::Runtime &rt, const facebook::jsi::Value &value) {{
+              return asInteger<int32_t>(rt, value, "Int32");
+            }}
+
+            inline uint8_t asUInt8(facebook::jsi::Runtime &rt, const facebook::jsi::Value &value) {{
+              return asInteger<uint8_t>(rt, value, "UInt8");
+            }}
+
+            inline int64_t asInt64(facebook::jsi::Runtime &rt, const facebook::jsi::Value &value) {{
+              if (value.isBigInt()) {{
+                auto bigint = value.getBigInt(rt);
+
+                if (!bigint.isInt64(rt)) {{
+                  throw outOfRangeError(rt, "Int64");
+                }}
+
+                return bigint.asInt64(rt);
+              }}
+
+              // Integers beyond `Number.MAX_SAFE_INTEGER` may have already lost precision
+              constexpr double maxSafeInteger = 9007199254740991.0;
+              auto num = value.asNumber();
+
+              if (std::trunc(num) != num || std::fabs(num) > maxSafeInteger) {{
+                throw outOfRangeError(rt, "Int64");
+              }}
+
+              return static_cast<int64_t>(num);
+            }}
+
+            inline float asFloat(facebook::jsi::Runtime &rt, const facebook::jsi::Value &value) {{
+              auto num = value.asNumber();
+
+              if (std::isfinite(num) && std::fabs(num) > std::numeric_limits<float>::max()) {{
+                throw outOfRangeError(rt, "Float");
+              }}
+
+              return static_cast<float>(num);
+            }}{extra_utils}
+
+            }} // namespace utils
+            }} // namespace {flat_name}
+            }} // namespace craby"#,
+        })
+    }
+
+    /// Generates the signal manager header file for event emission.
+    ///
+    /// # Generated Code
+    ///
+    /// ```cpp
+    /// #pragma once
+    ///
+    /// #include "rust/cxx.h"
+    /// #include <functional>
+    /// #include <memory>
+    /// #include <mutex>
+    /// #include <unordered_map>
+    ///
+    /// namespace craby {
+    /// namespace mymodule {
+    /// namespace signals {
+    ///
+    /// using Delegate = std::function<void(const std::string& signalName, std::shared_ptr<void> payload)>;
+    ///
+    /// class SignalManager {
+    /// public:
+    ///   static SignalManager& getInstance() {
+    ///     static SignalManager instance;
+    ///     return instance;
+    ///   }
+    ///
+    ///   void emit(uintptr_t id, rust::Str name) const {
+    ///     std::lock_guard<std::mutex> lock(mutex_);
+    ///     auto it = delegates_.find(id);
+    ///     if (it != delegates_.end()) {
+    ///       it->second(std::string(name), nullptr);
+    ///     }
+    ///   }
+    ///
+    ///   template <typename T>
+    ///   void emit(uintptr_t id, rust::Str name, const T& payload) const {
+    ///     std::lock_guard<std::mutex> lock(mutex_);
+    ///     auto it = delegates_.find(id);
+    ///     if (it != delegates_.end()) {
+    ///       it->second(std::string(name), std::make_shared<T>(payload));
+    ///     }
+    ///   }
+    ///
+    ///   void registerDelegate(uintptr_t id, Delegate delegate) const {
+    ///     std::lock_guard<std::mutex> lock(mutex_);
+    ///     delegates_.insert_or_assign(id, delegate);
+    ///   }
+    ///
+    ///   void unregisterDelegate(uintptr_t id) const {
+    ///     std::lock_guard<std::mutex> lock(mutex_);
+    ///     delegates_.erase(id);
+    ///   }
+    ///
+    /// private:
+    ///   SignalManager() = default;
+    ///   mutable std::unordered_map<uintptr_t, Delegate> delegates_;
+    ///   mutable std::mutex mutex_;
+    /// };
+    ///
+    /// } // namespace signals
+    /// } // namespace mymodule
+    /// } // namespace craby
+    /// ```
+    fn cxx_signals(&self, project_name: &str) -> Result<String, anyhow::Error> {
+        let flat_name = flat_case(project_name);
+
+        Ok(formatdoc! {
+            r#"
+            #pragma once
+
+            #include "rust/cxx.h"
+            #include <functional>
+            #include <memory>
+            #include <mutex>
+            #include <unordered_map>
+
+            namespace craby {{
+            namespace {flat_name} {{
+            namespace signals {{
+
+            using Delegate = std::function<void(const std::string& signalName, std::shared_ptr<void> payload)>;
+
+            class SignalManager {{
+            public:
+              static SignalManager& getInstance() {{
+                static SignalManager instance;
+                return instance;
+              }}
+
+              void emit(uintptr_t id, rust::Str name) const {{
+                std::lock_guard<std::mutex> lock(mutex_);
+                auto it = delegates_.find(id);
+                if (it != delegates_.end()) {{
+                  it->second(std::string(name), nullptr);
+                }}
+              }}
+
+              template <typename T>
+              void emit(uintptr_t id, rust::Str name, const T& payload) const {{
+                std::lock_guard<std::mutex> lock(mutex_);
+                auto it = delegates_.find(id);
+                if (it != delegates_.end()) {{
+                  // Copy the payload to keep it alive until the listeners are invoked on the JS thread
+                  it->second(std::string(name), std::make_shared<T>(payload));
+                }}
+              }}
+
+              void registerDelegate(uintptr_t id, Delegate delegate) const {{
+                std::lock_guard<std::mutex> lock(mutex_);
+                delegates_.insert_or_assign(id, delegate);
+              }}
+
+              void unregisterDelegate(uintptr_t id) const {{
+                std::lock_guard<std::mutex> lock(mutex_);
+                delegates_.erase(id);
+              }}
+
+            private:
+              SignalManager() = default;
+              mutable std::unordered_map<uintptr_t, Delegate> delegates_;
+              mutable std::mutex mutex_;
+            }};
+
+            inline const SignalManager& getSignalManager() {{
+              return SignalManager::getInstance();
+            }}
+
+            }} // namespace signals
+            }} // namespace {flat_name}
+            }} // namespace craby"#,
+        })
+    }
+
+    /// Generates the callback manager header file for JS callback invocation.
+    ///
+    /// # Generated Code
+    ///
+    /// ```cpp
+    /// #pragma once
+    ///
+    /// #include <cstddef>
+    /// #include <cstdint>
+    /// #include <functional>
+    /// #include <memory>
+    /// #include <mutex>
+    /// #include <unordered_map>
+    ///
+    /// namespace craby {
+    /// namespace mymodule {
+    /// namespace callbacks {
+    ///
+    /// using Invoker = std::function<void(std::shared_ptr<void> payload)>;
+    ///
+    /// class CallbackManager {
+    /// public:
+    ///   static CallbackManager& getInstance() {
+    ///     static CallbackManager instance;
+    ///     return instance;
+    ///   }
+    ///
+    ///   void invoke(size_t id) const {
+    ///     dispatch(id, nullptr);
+    ///   }
+    ///
+    ///   template <typename T>
+    ///   void invoke(size_t id, const T& payload) const {
+    ///     dispatch(id, std::make_shared<T>(payload));
+    ///   }
+    ///
+    ///   void release(size_t id) const;
+    ///   size_t registerCallback(uintptr_t owner, Invoker invoker) const;
+    ///   void unregisterCallbacks(uintptr_t owner) const;
+    ///
+    /// private:
+    ///   // ...
+    /// };
+    ///
+    /// } // namespace callbacks
+    /// } // namespace mymodule
+    /// } // namespace craby
+    /// ```
+    fn cxx_callbacks(&self, project_name: &str) -> Result<String, anyhow::Error> {
+        let flat_name = flat_case(project_name);
+
+        Ok(formatdoc! {
+            r#"
+            #pragma once
+
+            #include <cstddef>
+            #include <cstdint>
+            #include <functional>
+            #include <memory>
+            #include <mutex>
+            #include <unordered_map>
+            #include <vector>
+
+            namespace craby {{
+            namespace {flat_name} {{
+            namespace callbacks {{
+
+            using Invoker = std::function<void(std::shared_ptr<void> payload)>;
+
+            class CallbackManager {{
+            public:
+              static CallbackManager& getInstance() {{
+                static CallbackManager instance;
+                return instance;
+              }}
+
+              void invoke(size_t id) const {{
+                dispatch(id, nullptr);
+              }}
+
+              template <typename T>
+              void invoke(size_t id, const T& payload) const {{
+                // Copy the payload to keep it alive until the callback is invoked on the JS thread
+                dispatch(id, std::make_shared<T>(payload));
+              }}
+
+              void release(size_t id) const {{
+                // Destroy the invoker outside of the lock
+                Invoker invoker;
+                {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  auto it = callbacks_.find(id);
+                  if (it == callbacks_.end()) {{
+                    return;
+                  }}
+                  invoker = std::move(it->second.invoker);
+                  callbacks_.erase(it);
+                }}
+              }}
+
+              size_t registerCallback(uintptr_t owner, Invoker invoker) const {{
+                std::lock_guard<std::mutex> lock(mutex_);
+                auto id = nextId_++;
+                callbacks_.emplace(id, Entry{{owner, std::move(invoker)}});
+                return id;
+              }}
+
+              void unregisterCallbacks(uintptr_t owner) const {{
+                // Destroy the invokers outside of the lock
+                std::vector<Invoker> invokers;
+                {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  for (auto it = callbacks_.begin(); it != callbacks_.end();) {{
+                    if (it->second.owner == owner) {{
+                      invokers.push_back(std::move(it->second.invoker));
+                      it = callbacks_.erase(it);
+                    }} else {{
+                      ++it;
+                    }}
+                  }}
+                }}
+              }}
+
+            private:
+              struct Entry {{
+                uintptr_t owner;
+                Invoker invoker;
+              }};
+
+              CallbackManager() = default;
+
+              void dispatch(size_t id, std::shared_ptr<void> payload) const {{
+                Invoker invoker;
+                {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  auto it = callbacks_.find(id);
+                  if (it == callbacks_.end()) {{
+                    return;
+                  }}
+                  invoker = it->second.invoker;
+                }}
+                invoker(std::move(payload));
+              }}
+
+              mutable size_t nextId_ = 1;
+              mutable std::unordered_map<size_t, Entry> callbacks_;
+              mutable std::mutex mutex_;
+            }};
+
+            inline const CallbackManager& getCallbackManager() {{
+              return CallbackManager::getInstance();
+            }}
+
+            }} // namespace callbacks
+            }} // namespace {flat_name}
+            }} // namespace craby"#,
+        })
+    }
+
+    /// Generates the cancellation manager header file for abortable promise methods.
+    ///
+    /// # Generated Code
+    ///
+    /// ```cpp
+    /// #pragma once
+    ///
+    /// #include <cstddef>
+    /// #include <cstdint>
+    /// #include <functional>
+    /// #include <mutex>
+    /// #include <unordered_map>
+    ///
+    /// namespace craby {
+    /// namespace mymodule {
+    /// namespace cancellation {
+    ///
+    /// using Canceler = std::function<void()>;
+    ///
+    /// class CancellationManager {
+    /// public:
+    ///   static CancellationManager& getInstance() {
+    ///     static CancellationManager instance;
+    ///     return instance;
+    ///   }
+    ///
+    ///   bool isCancelled(size_t id) const;
+    ///   void cancel(size_t id) const;
+    ///   void release(size_t id) const;
+    ///   size_t registerToken(uintptr_t owner, Canceler onCancel) const;
+    ///   void unregisterTokens(uintptr_t owner) const;
+    ///
+    /// private:
+    ///   // ...
+    /// };
+    ///
+    /// } // namespace cancellation
+    /// } // namespace mymodule
+    /// } // namespace craby
+    /// ```
+    fn cxx_cancellation(&self, project_name: &str) -> Result<String, anyhow::Error> {
+        let flat_name = flat_case(project_name);
+
+        Ok(formatdoc! {
+            r#"
+            #pragma once
+
+            #include <cstddef>
+            #include <cstdint>
+            #include <functional>
+            #include <mutex>
+            #include <unordered_map>
+            #include <vector>
+
+            namespace craby {{
+            namespace {flat_name} {{
+            namespace cancellation {{
+
+            using Canceler = std::function<void()>;
+
+            class CancellationManager {{
+            public:
+              static CancellationManager& getInstance() {{
+                static CancellationManager instance;
+                return instance;
+              }}
+
+              bool isCancelled(size_t id) const {{
+                std::lock_guard<std::mutex> lock(mutex_);
+                auto it = tokens_.find(id);
+                // Tokens are removed only by `release` (handle dropped) or `unregisterTokens` (module invalidated)
+                return it == tokens_.end() || it->second.cancelled;
+              }}
+
+              void cancel(size_t id) const {{
+                Canceler onCancel;
+                {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  auto it = tokens_.find(id);
+                  if (it == tokens_.end() || it->second.cancelled) {{
+                    return;
+                  }}
+                  it->second.cancelled = true;
+                  onCancel = std::move(it->second.onCancel);
+                }}
+                onCancel();
+              }}
+
+              void release(size_t id) const {{
+                // Destroy the canceler outside of the lock
+                Canceler onCancel;
+                {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  auto it = tokens_.find(id);
+                  if (it == tokens_.end()) {{
+                    return;
+                  }}
+                  onCancel = std::move(it->second.onCancel);
+                  tokens_.erase(it);
+                }}
+              }}
+
+              size_t registerToken(uintptr_t owner, Canceler onCancel) const {{
+                std::lock_guard<std::mutex> lock(mutex_);
+                auto id = nextId_++;
+                tokens_.emplace(id, Entry{{owner, false, std::move(onCancel)}});
+                return id;
+              }}
+
+              void unregisterTokens(uintptr_t owner) const {{
+                std::vector<Canceler> cancelers;
+                {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  for (auto it = tokens_.begin(); it != tokens_.end();) {{
+                    if (it->second.owner == owner) {{
+                      if (!it->second.cancelled) {{
+                        cancelers.push_back(std::move(it->second.onCancel));
+                      }}
+                      it = tokens_.erase(it);
+                    }} else {{
+                      ++it;
+                    }}
+                  }}
+                }}
+
+                for (auto &onCancel : cancelers) {{
+                  onCancel();
+                }}
+              }}
+
+            private:
+              struct Entry {{
+                uintptr_t owner;
+                bool cancelled;
+                Canceler onCancel;
+              }};
+
+              CancellationManager() = default;
 
-                      {{
-                        std::unique_lock<std::mutex> lock(this->mutex);
-                        this->condition.wait(
-                            lock, [this] {{ return this->stop || !this->tasks.empty(); }});
+              mutable size_t nextId_ = 1;
+              mutable std::unordered_map<size_t, Entry> tokens_;
+              mutable std::mutex mutex_;
+            }};
 
-                        if (this->stop && this->tasks.empty()) {{
-                          return;
-                        }}
+            inline const CancellationManager& getCancellationManager() {{
+              return CancellationManager::getInstance();
+            }}
 
-                        task = std::move(this->tasks.front());
-                        this->tasks.pop();
-                      }}
+            }} // namespace cancellation
+            }} // namespace {flat_name}
+            }} // namespace craby"#,
+        })
+    }
 
-                      task();
-                    }}
+    /// Generates the stream manager header file for async iterable producers.
+    ///
+    /// # Generated Code
+    ///
+    /// ```cpp
+    /// #pragma once
+    ///
+    /// #include "rust/cxx.h"
+    /// #include <condition_variable>
+    /// #include <deque>
+    /// #include <memory>
+    /// #include <mutex>
+    /// #include <unordered_map>
+    ///
+    /// namespace craby {
+    /// namespace mymodule {
+    /// namespace streams {
+    ///
+    /// struct Chunk {
+    ///   std::shared_ptr<void> item;
+    ///   bool done;
+    ///   std::optional<std::string> error;
+    /// };
+    ///
+    /// class Stream {
+    /// public:
+    ///   bool push(std::shared_ptr<void> item); // Blocks while the buffer is full
+    ///   void pull(Puller puller);
+    ///   void finish();
+    ///   void fail(std::string message);
+    ///   void cancel();
+    /// };
+    ///
+    /// class StreamManager {
+    /// public:
+    ///   template <typename T>
+    ///   bool push(size_t id, const T& item) const;
+    ///
+    ///   void finish(size_t id) const;
+    ///   void fail(size_t id, rust::Str message) const;
+    ///   void release(size_t id) const;
+    ///   size_t registerStream(uintptr_t owner, std::shared_ptr<Stream> stream) const;
+    ///   void unregisterStreams(uintptr_t owner) const;
+    /// };
+    ///
+    /// } // namespace streams
+    /// } // namespace mymodule
+    /// } // namespace craby
+    /// ```
+    fn cxx_streams(&self, project_name: &str) -> Result<String, anyhow::Error> {
+        let flat_name = flat_case(project_name);
+
+        Ok(formatdoc! {
+            r#"
+            #pragma once
+
+            #include "rust/cxx.h"
+            #include <atomic>
+            #include <condition_variable>
+            #include <cstddef>
+            #include <cstdint>
+            #include <deque>
+            #include <functional>
+            #include <memory>
+            #include <mutex>
+            #include <optional>
+            #include <string>
+            #include <unordered_map>
+            #include <utility>
+            #include <vector>
+
+            namespace craby {{
+            namespace {flat_name} {{
+            namespace streams {{
+
+            // An item, the end of the stream or an error
+            struct Chunk {{
+              std::shared_ptr<void> item;
+              bool done;
+              std::optional<std::string> error;
+            }};
+
+            using Puller = std::function<void(Chunk chunk)>;
+
+            class Stream {{
+            public:
+              explicit Stream(size_t capacity = 16) : capacity_(capacity) {{}}
+
+              // Blocks the producer while the buffer is full (backpressure).
+              // Returns `false` if the stream is closed or the consumer stopped iterating.
+              bool push(std::shared_ptr<void> item) {{
+                Puller puller;
+                {{
+                  std::unique_lock<std::mutex> lock(mutex_);
+                  condition_.wait(lock, [this] {{
+                    return done_ || cancelled_ || !pullers_.empty() || items_.size() < capacity_;
                   }});
+
+                  if (done_ || cancelled_) {{
+                    return false;
+                  }}
+
+                  if (pullers_.empty()) {{
+                    items_.push_back(std::move(item));
+                    return true;
+                  }}
+
+                  puller = std::move(pullers_.front());
+                  pullers_.pop_front();
                 }}
+
+                puller(Chunk{{std::move(item), false, std::nullopt}});
+                return true;
               }}
 
-              template <class F> void enqueue(F &&f) {{
+              // Delivers the next chunk to the puller (immediately if available, otherwise when produced)
+              void pull(Puller puller) {{
+                Chunk chunk;
                 {{
-                  std::unique_lock<std::mutex> lock(mutex);
-                  if (stop) {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  if (!items_.empty()) {{
+                    chunk = Chunk{{std::move(items_.front()), false, std::nullopt}};
+                    items_.pop_front();
+                  }} else if (done_ || cancelled_) {{
+                    chunk = Chunk{{nullptr, true, std::exchange(error_, std::nullopt)}};
+                  }} else {{
+                    pullers_.push_back(std::move(puller));
                     return;
                   }}
-                  tasks.emplace(std::forward<F>(f));
                 }}
-                condition.notify_one();
+
+                // Wake up the producer waiting for the buffer space
+                condition_.notify_all();
+                puller(std::move(chunk));
               }}
 
-              void shutdown() {{
+              void finish() {{
+                close(std::nullopt);
+              }}
+
+              void fail(std::string message) {{
+                close(std::move(message));
+              }}
+
+              // Called when the consumer stops iterating (the buffered items are dropped)
+              void cancel() {{
+                std::deque<Puller> pullers;
                 {{
-                  std::unique_lock<std::mutex> lock(mutex);
-                  stop = true;
-                  std::queue<std::function<void()>> empty;
-                  std::swap(tasks, empty);
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  cancelled_ = true;
+                  items_.clear();
+                  std::swap(pullers, pullers_);
                 }}
 
-                condition.notify_all();
+                condition_.notify_all();
+                for (auto &puller : pullers) {{
+                  puller(Chunk{{nullptr, true, std::nullopt}});
+                }}
+              }}
 
-                for (std::thread &worker : workers) {{
-                  if (worker.joinable()) {{
-                    worker.join();
+              // The producer references are held by the running task and the Rust handle.
+              // The stream is finished when both are released.
+              void retain() {{
+                refs_.fetch_add(1);
+              }}
+
+              void release() {{
+                if (refs_.fetch_sub(1) == 1) {{
+                  finish();
+                }}
+              }}
+
+            private:
+              void close(std::optional<std::string> error) {{
+                std::vector<std::pair<Puller, Chunk>> pending;
+                {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  if (done_) {{
+                    return;
+                  }}
+
+                  done_ = true;
+                  error_ = std::move(error);
+
+                  // Pending pullers exist only when the buffer is empty
+                  while (!pullers_.empty()) {{
+                    pending.emplace_back(std::move(pullers_.front()),
+                                         Chunk{{nullptr, true, std::exchange(error_, std::nullopt)}});
+                    pullers_.pop_front();
+                  }}
+                }}
+
+                condition_.notify_all();
+                for (auto &[puller, chunk] : pending) {{
+                  puller(std::move(chunk));
+                }}
+              }}
+
+              size_t capacity_;
+              std::atomic<int> refs_{{1}};
+              bool done_ = false;
+              bool cancelled_ = false;
+              std::optional<std::string> error_;
+              std::deque<std::shared_ptr<void>> items_;
+              std::deque<Puller> pullers_;
+              std::mutex mutex_;
+              std::condition_variable condition_;
+            }};
+
+            class StreamManager {{
+            public:
+              static StreamManager& getInstance() {{
+                static StreamManager instance;
+                return instance;
+              }}
+
+              template <typename T>
+              bool push(size_t id, const T& item) const {{
+                auto stream = find(id);
+                // Copy the item to keep it alive until it is pulled on the JS thread
+                return stream ? stream->push(std::make_shared<T>(item)) : false;
+              }}
+
+              void finish(size_t id) const {{
+                if (auto stream = find(id)) {{
+                  stream->finish();
+                }}
+              }}
+
+              void fail(size_t id, rust::Str message) const {{
+                if (auto stream = find(id)) {{
+                  stream->fail(std::string(message));
+                }}
+              }}
+
+              // Called when the Rust handle is dropped
+              void release(size_t id) const {{
+                std::shared_ptr<Stream> stream;
+                {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  auto it = streams_.find(id);
+                  if (it == streams_.end()) {{
+                    return;
                   }}
+                  stream = std::move(it->second.stream);
+                  streams_.erase(it);
                 }}
+                stream->release();
               }}
 
-              ~ThreadPool() {{
-                shutdown();
+              size_t registerStream(uintptr_t owner, std::shared_ptr<Stream> stream) const {{
+                // Retained for the Rust handle (released by `release`)
+                stream->retain();
+
+                std::lock_guard<std::mutex> lock(mutex_);
+                auto id = nextId_++;
+                streams_.emplace(id, Entry{{owner, std::move(stream)}});
+                return id;
+              }}
+
+              void unregisterStreams(uintptr_t owner) const {{
+                std::vector<std::shared_ptr<Stream>> streams;
+                {{
+                  std::lock_guard<std::mutex> lock(mutex_);
+                  for (auto it = streams_.begin(); it != streams_.end();) {{
+                    if (it->second.owner == owner) {{
+                      streams.push_back(std::move(it->second.stream));
+                      it = streams_.erase(it);
+                    }} else {{
+                      ++it;
+                    }}
+                  }}
+                }}
+
+                for (auto &stream : streams) {{
+                  stream->cancel();
+                }}
               }}
+
+            private:
+              struct Entry {{
+                uintptr_t owner;
+                std::shared_ptr<Stream> stream;
+              }};
+
+              StreamManager() = default;
+
+              std::shared_ptr<Stream> find(size_t id) const {{
+                std::lock_guard<std::mutex> lock(mutex_);
+                auto it = streams_.find(id);
+                return it == streams_.end() ? nullptr : it->second.stream;
+              }}
+
+              mutable size_t nextId_ = 1;
+              mutable std::unordered_map<size_t, Entry> streams_;
+              mutable std::mutex mutex_;
             }};
 
-            inline std::string errorMessage(const std::exception &err) {{
-              const auto* rs_err = dynamic_cast<const rust::Error*>(&err);
-              return std::string(rs_err ? rs_err->what() : err.what());
+            inline const StreamManager& getStreamManager() {{
+              return StreamManager::getInstance();
             }}
 
-            }} // namespace utils
+            }} // namespace streams
             }} // namespace {flat_name}
             }} // namespace craby"#,
         })
     }
 
-    /// Generates the signal manager header file for event emission.
+    /// Generates the promise manager header file for the `async fn` methods (`@async` tag).
     ///
     /// # Generated Code
     ///
@@ -757,46 +2526,27 @@ impl CxxTemplate {
     ///
     /// namespace craby {
     /// namespace mymodule {
-    /// namespace signals {
+    /// namespace promises {
     ///
-    /// using Delegate = std::function<void(const std::string& signalName)>;
+    /// using Resolver = std::function<void(std::shared_ptr<void> value)>;
+    /// using Rejecter = std::function<void(std::string message)>;
     ///
-    /// class SignalManager {
+    /// class PromiseManager {
     /// public:
-    ///   static SignalManager& getInstance() {
-    ///     static SignalManager instance;
-    ///     return instance;
-    ///   }
-    ///
-    ///   void emit(uintptr_t id, rust::Str name) const {
-    ///     std::lock_guard<std::mutex> lock(mutex_);
-    ///     auto it = delegates_.find(id);
-    ///     if (it != delegates_.end()) {
-    ///       it->second(std::string(name));
-    ///     }
-    ///   }
-    ///
-    ///   void registerDelegate(uintptr_t id, Delegate delegate) const {
-    ///     std::lock_guard<std::mutex> lock(mutex_);
-    ///     delegates_.insert_or_assign(id, delegate);
-    ///   }
-    ///
-    ///   void unregisterDelegate(uintptr_t id) const {
-    ///     std::lock_guard<std::mutex> lock(mutex_);
-    ///     delegates_.erase(id);
-    ///   }
+    ///   template <typename T>
+    ///   void resolve(size_t id, const T& value) const;
     ///
-    /// private:
-    ///   SignalManager() = default;
-    ///   mutable std::unordered_map<uintptr_t, Delegate> delegates_;
-    ///   mutable std::mutex mutex_;
+    ///   void resolve(size_t id) const;
+    ///   void reject(size_t id, rust::Str message) const;
+    ///   void release(size_t id) const;
+    ///   size_t registerPromise(Resolver onResolve, Rejecter onReject) const;
     /// };
     ///
-    /// } // namespace signals
+    /// } // namespace promises
     /// } // namespace mymodule
     /// } // namespace craby
     /// ```
-    fn cxx_signals(&self, project_name: &str) -> Result<String, anyhow::Error> {
+    fn cxx_promises(&self, project_name: &str) -> Result<String, anyhow::Error> {
         let flat_name = flat_case(project_name);
 
         Ok(formatdoc! {
@@ -804,53 +2554,91 @@ impl CxxTemplate {
             #pragma once
 
             #include "rust/cxx.h"
+            #include <cstddef>
             #include <functional>
             #include <memory>
             #include <mutex>
+            #include <optional>
+            #include <string>
             #include <unordered_map>
 
             namespace craby {{
             namespace {flat_name} {{
-            namespace signals {{
+            namespace promises {{
 
-            using Delegate = std::function<void(const std::string& signalName)>;
+            using Resolver = std::function<void(std::shared_ptr<void> value)>;
+            using Rejecter = std::function<void(std::string message)>;
 
-            class SignalManager {{
+            class PromiseManager {{
             public:
-              static SignalManager& getInstance() {{
-                static SignalManager instance;
+              static PromiseManager& getInstance() {{
+                static PromiseManager instance;
                 return instance;
               }}
 
-              void emit(uintptr_t id, rust::Str name) const {{
-                std::lock_guard<std::mutex> lock(mutex_);
-                auto it = delegates_.find(id);
-                if (it != delegates_.end()) {{
-                  it->second(std::string(name));
+              template <typename T>
+              void resolve(size_t id, const T& value) const {{
+                if (auto entry = take(id)) {{
+                  // Copy the value to keep it alive until the promise is resolved on the JS thread
+                  entry->onResolve(std::make_shared<T>(value));
                 }}
               }}
 
-              void registerDelegate(uintptr_t id, Delegate delegate) const {{
-                std::lock_guard<std::mutex> lock(mutex_);
-                delegates_.insert_or_assign(id, delegate);
+              void resolve(size_t id) const {{
+                if (auto entry = take(id)) {{
+                  entry->onResolve(nullptr);
+                }}
               }}
 
-              void unregisterDelegate(uintptr_t id) const {{
+              void reject(size_t id, rust::Str message) const {{
+                if (auto entry = take(id)) {{
+                  entry->onReject(std::string(message));
+                }}
+              }}
+
+              // Called when the Rust handle is dropped (rejects the promise if it is not settled yet)
+              void release(size_t id) const {{
+                if (auto entry = take(id)) {{
+                  entry->onReject("The promise was dropped before completion");
+                }}
+              }}
+
+              size_t registerPromise(Resolver onResolve, Rejecter onReject) const {{
                 std::lock_guard<std::mutex> lock(mutex_);
-                delegates_.erase(id);
+                auto id = nextId_++;
+                promises_.emplace(id, Entry{{std::move(onResolve), std::move(onReject)}});
+                return id;
               }}
 
             private:
-              SignalManager() = default;
-              mutable std::unordered_map<uintptr_t, Delegate> delegates_;
+              struct Entry {{
+                Resolver onResolve;
+                Rejecter onReject;
+              }};
+
+              PromiseManager() = default;
+
+              std::optional<Entry> take(size_t id) const {{
+                std::lock_guard<std::mutex> lock(mutex_);
+                auto it = promises_.find(id);
+                if (it == promises_.end()) {{
+                  return std::nullopt;
+                }}
+                auto entry = std::move(it->second);
+                promises_.erase(it);
+                return entry;
+              }}
+
+              mutable size_t nextId_ = 1;
+              mutable std::unordered_map<size_t, Entry> promises_;
               mutable std::mutex mutex_;
             }};
 
-            inline const SignalManager& getSignalManager() {{
-              return SignalManager::getInstance();
+            inline const PromiseManager& getPromiseManager() {{
+              return PromiseManager::getInstance();
             }}
 
-            }} // namespace signals
+            }} // namespace promises
             }} // namespace {flat_name}
             }} // namespace craby"#,
         })
@@ -870,7 +2658,11 @@ impl Template for CxxTemplate {
                 .schemas
                 .iter()
                 .map(|schema| -> Result<Vec<(PathBuf, String)>, anyhow::Error> {
-                    let (cpp, hpp) = self.cxx_mod(schema, &ctx.project_name)?;
+                    let (cpp, hpp) = self.cxx_mod(
+                        schema,
+                        &ctx.project_name,
+                        &ctx.worker_pool.resolve(&schema.module_name),
+                    )?;
                     let cxx_mod = CxxModuleName::from(&schema.module_name);
                     let cxx_base_path = cxx_dir(&ctx.root);
                     let files = vec![
@@ -888,7 +2680,11 @@ impl Template for CxxTemplate {
             CxxFileType::UtilsHpp => {
                 vec![(
                     cxx_dir(&ctx.root).join("CrabyUtils.hpp"),
-                    self.cxx_utils(&ctx.project_name)?,
+                    self.cxx_utils(
+                        &ctx.project_name,
+                        ctx.schemas.iter().any(|schema| schema.has_abort_signals()),
+                        ctx.schemas.iter().any(|schema| schema.has_streams()),
+                    )?,
                 )]
             }
             CxxFileType::SignalsH => {
@@ -903,6 +2699,54 @@ impl Template for CxxTemplate {
                     vec![]
                 }
             }
+            CxxFileType::CallbacksH => {
+                let has_callbacks = ctx.schemas.iter().any(|schema| schema.has_callbacks());
+
+                if has_callbacks {
+                    vec![(
+                        cxx_bridge_include_dir(&ctx.root).join("CrabyCallbacks.h"),
+                        self.cxx_callbacks(&ctx.project_name)?,
+                    )]
+                } else {
+                    vec![]
+                }
+            }
+            CxxFileType::CancellationH => {
+                let has_abort_signals = ctx.schemas.iter().any(|schema| schema.has_abort_signals());
+
+                if has_abort_signals {
+                    vec![(
+                        cxx_bridge_include_dir(&ctx.root).join("CrabyCancellation.h"),
+                        self.cxx_cancellation(&ctx.project_name)?,
+                    )]
+                } else {
+                    vec![]
+                }
+            }
+            CxxFileType::StreamsH => {
+                let has_streams = ctx.schemas.iter().any(|schema| schema.has_streams());
+
+                if has_streams {
+                    vec![(
+                        cxx_bridge_include_dir(&ctx.root).join("CrabyStreams.h"),
+                        self.cxx_streams(&ctx.project_name)?,
+                    )]
+                } else {
+                    vec![]
+                }
+            }
+            CxxFileType::PromisesH => {
+                let has_async_fns = ctx.schemas.iter().any(|schema| schema.has_async_fns());
+
+                if has_async_fns {
+                    vec![(
+                        cxx_bridge_include_dir(&ctx.root).join("CrabyPromises.h"),
+                        self.cxx_promises(&ctx.project_name)?,
+                    )]
+                } else {
+                    vec![]
+                }
+            }
         };
 
         Ok(res)
@@ -950,6 +2794,10 @@ impl Generator<CxxTemplate> for CxxGenerator {
             template.render(ctx, &CxxFileType::BridgingHpp)?,
             template.render(ctx, &CxxFileType::UtilsHpp)?,
             template.render(ctx, &CxxFileType::SignalsH)?,
+            template.render(ctx, &CxxFileType::CallbacksH)?,
+            template.render(ctx, &CxxFileType::CancellationH)?,
+            template.render(ctx, &CxxFileType::StreamsH)?,
+            template.render(ctx, &CxxFileType::PromisesH)?,
         ]
         .into_iter()
         .flatten()
@@ -976,9 +2824,10 @@ impl GeneratorInvoker for CxxGenerator {
 
 #[cfg(test)]
 mod tests {
+    use craby_common::config::ModuleWorkerPoolConfig;
     use insta::assert_snapshot;
 
-    use crate::tests::get_codegen_context;
+    use crate::tests::{get_codegen_context, get_shared_types_codegen_context};
 
     use super::*;
 
@@ -995,4 +2844,110 @@ mod tests {
 
         assert_snapshot!(result);
     }
+
+    #[test]
+    fn test_shared_types() {
+        let ctx = get_shared_types_codegen_context();
+        let generator = CxxGenerator::new();
+        let results = generator.generate(&ctx).unwrap();
+        let result = results
+            .iter()
+            .map(|res| res.content.clone())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert!(
+            result
+                .matches("struct Bridging<craby::testmodule::bridging::User>")
+                .count()
+                == 1
+        );
+    }
+
+    #[test]
+    fn test_async_fn_reject() {
+        let ctx = get_codegen_context();
+        let generator = CxxGenerator::new();
+        let cpp = generator
+            .generate(&ctx)
+            .unwrap()
+            .into_iter()
+            .find(|res| res.path.ends_with("CxxCrabyTestModule.cpp"))
+            .unwrap()
+            .content;
+        let start = cpp.find("CxxCrabyTestModule::fetchData(").unwrap();
+        let fetch_data = &cpp[start..start + cpp[start..].find("\n}\n").unwrap()];
+
+        // Failed calls release the registered promise
+        assert!(!fetch_data.contains("promise->reject(err.getMessage());"));
+    }
+
+    #[test]
+    fn test_lifecycle_hooks() {
+        let ctx = get_codegen_context();
+        let generator = CxxGenerator::new();
+        let cpp = generator
+            .generate(&ctx)
+            .unwrap()
+            .into_iter()
+            .find(|res| res.path.ends_with("CxxCrabyTestModule.cpp"))
+            .unwrap()
+            .content;
+        let start = cpp.find("void CxxCrabyTestModule::teardown(").unwrap();
+        let teardown = &cpp[start..start + cpp[start..].find("\n}\n").unwrap()];
+
+        // The outgoing instance is notified without blocking the JS thread
+        let hooks = &teardown[teardown.find("std::thread(").unwrap()..];
+        assert!(hooks.contains("bridging::onReload(*module_);"));
+        assert!(hooks.contains("bridging::onInvalidate(*module_);"));
+        assert!(hooks.contains("threadPool_->shutdown();"));
+        // The reload state is not shared with the next instance
+        assert!(!cpp.contains("reloadPending_"));
+    }
+
+    #[test]
+    fn test_worker_pool() {
+        let mut ctx = get_codegen_context();
+        ctx.worker_pool.size = 4;
+        ctx.worker_pool.shared = true;
+        ctx.worker_pool.idle_timeout_ms = 30000;
+
+        let generator = CxxGenerator::new();
+        let cpp = |ctx: &CodegenContext| {
+            generator
+                .generate(ctx)
+                .unwrap()
+                .into_iter()
+                .find(|res| res.path.ends_with("CxxCrabyTestModule.cpp"))
+                .unwrap()
+                .content
+        };
+
+        // Shared worker pool is released by the module (shut down by the last module)
+        let shared = cpp(&ctx);
+        assert!(shared.contains(
+            "threadPool_ = craby::testmodule::utils::ThreadPool::shared(4, \"\", std::chrono::milliseconds(30000), 0);"
+        ));
+        assert!(
+            shared.contains("craby::testmodule::utils::ThreadPool::releaseShared(threadPool_);")
+        );
+        assert!(!shared.contains("threadPool_->shutdown();"));
+        // Queued tasks of the invalidated module are dropped from the shared pool
+        assert!(shared.contains("threadPool_->cancel(reinterpret_cast<uintptr_t>(this));"));
+
+        // Module specific worker pool overrides the project options
+        ctx.worker_pool.modules.insert(
+            "CrabyTest".to_string(),
+            ModuleWorkerPoolConfig {
+                size: Some(2),
+                max_queue_size: Some(64),
+                ..Default::default()
+            },
+        );
+        let owned = cpp(&ctx);
+        assert!(owned.contains(
+            "threadPool_ = std::make_shared<craby::testmodule::utils::ThreadPool>(2, \"\", std::chrono::milliseconds(30000), 64);"
+        ));
+        assert!(owned.contains("threadPool_->shutdown();"));
+    }
 }
@@ -1,15 +1,17 @@
 use std::{collections::BTreeMap, path::PathBuf};
 
 use craby_common::{
+    config::ConcurrencyMode,
     constants::{crate_dir, impl_mod_name, HASH_COMMENT_PREFIX},
     utils::string::pascal_case,
 };
 use indoc::formatdoc;
 
 use crate::{
-    platform::rust::RsCxxBridge,
+    common::IntoCode,
+    platform::rust::{as_rs_self_param, RsCxxBridge},
     types::{CodegenContext, CxxNamespace, Schema},
-    utils::indent_str,
+    utils::{indent_str, with_doc_comment, with_rs_method_doc_comment},
 };
 
 use super::types::{GenerateResult, Generator, GeneratorInvoker, Template};
@@ -42,10 +44,13 @@ impl RsTemplate {
             .collect::<Vec<String>>()
     }
 
-    fn rs_cxx_bridges(&self, schemas: &[Schema]) -> Result<Vec<RsCxxBridge>, anyhow::Error> {
-        let res = schemas
+    fn rs_cxx_bridges(&self, ctx: &CodegenContext) -> Result<Vec<RsCxxBridge>, anyhow::Error> {
+        let res = ctx
+            .schemas
             .iter()
-            .map(|schema| schema.as_rs_cxx_bridge())
+            .map(|schema| {
+                schema.as_rs_cxx_bridge(ctx.concurrency.resolve(&schema.module_name), &ctx.panic)
+            })
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(res)
@@ -69,13 +74,13 @@ impl RsTemplate {
     ///     }
     ///
     ///     extern "Rust" {
-    ///         type MyModule;
+    ///         type MyModuleInstance;
     ///
     ///         #[cxx_name = "createMyModule"]
-    ///         fn create_my_module(id: usize, data_path: &str) -> Box<MyModule>;
+    ///         fn create_my_module(id: usize, data_path: &str) -> Result<Box<MyModuleInstance>>;
     ///
     ///         #[cxx_name = "multiply"]
-    ///         fn my_module_multiply(it_: &mut MyModule, a: f64, b: f64) -> Result<f64>;
+    ///         fn my_module_multiply(it_: &MyModuleInstance, a: f64, b: f64) -> Result<f64>;
     ///     }
     /// }
     /// ```
@@ -83,20 +88,83 @@ impl RsTemplate {
         &self,
         cxx_ns: &CxxNamespace,
         rs_cxx_bridges: &[RsCxxBridge],
-        has_signals: bool,
+        schemas: &[Schema],
     ) -> String {
-        let (impl_types, cxx_externs, struct_defs, enum_defs) = rs_cxx_bridges.iter().fold(
-            (vec![], vec![], vec![], vec![]),
-            |(mut impl_types, mut externs, mut structs, mut enums), bridge| {
+        let has_signals = schemas.iter().any(|schema| !schema.signals.is_empty());
+        let has_callbacks = schemas.iter().any(|schema| schema.has_callbacks());
+        let has_abort_signals = schemas.iter().any(|schema| schema.has_abort_signals());
+        let has_streams = schemas.iter().any(|schema| schema.has_streams());
+        let has_async_fns = schemas.iter().any(|schema| schema.has_async_fns());
+        let (
+            impl_types,
+            cxx_externs,
+            struct_defs,
+            enum_defs,
+            signal_externs,
+            callback_externs,
+            stream_externs,
+            promise_externs,
+            handle_externs,
+        ) = rs_cxx_bridges.iter().fold(
+            (
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+            ),
+            |(
+                mut impl_types,
+                mut externs,
+                mut structs,
+                mut enums,
+                mut signals,
+                mut callbacks,
+                mut streams,
+                mut promises,
+                mut handles,
+            ),
+             bridge| {
                 impl_types.push(bridge.impl_type.clone());
                 externs.extend(bridge.func_extern_sigs.clone());
-                structs.extend(bridge.struct_defs.clone());
-                enums.extend(bridge.enum_defs.clone());
-                (impl_types, externs, structs, enums)
+                // Types shared across the modules (eg. imported types) are defined once
+                for struct_def in &bridge.struct_defs {
+                    if !structs.contains(struct_def) {
+                        structs.push(struct_def.clone());
+                    }
+                }
+                for enum_def in &bridge.enum_defs {
+                    if !enums.contains(enum_def) {
+                        enums.push(enum_def.clone());
+                    }
+                }
+                signals.extend(bridge.signal_extern_sigs.clone());
+                callbacks.extend(bridge.callback_extern_sigs.clone());
+                streams.extend(bridge.stream_extern_sigs.clone());
+                promises.extend(bridge.promise_extern_sigs.clone());
+                // Host objects returned by the multiple modules are declared once
+                for handle_extern in &bridge.handle_externs {
+                    if !handles.contains(handle_extern) {
+                        handles.push(handle_extern.clone());
+                    }
+                }
+                (
+                    impl_types, externs, structs, enums, signals, callbacks, streams, promises,
+                    handles,
+                )
             },
         );
 
-        let cxx_extern_stmts = indent_str(&[impl_types, cxx_externs].concat().join("\n\n"), 4);
+        let cxx_extern_stmts = indent_str(
+            &[impl_types, cxx_externs, handle_externs]
+                .concat()
+                .join("\n\n"),
+            4,
+        );
         let cxx_extern = formatdoc! {
             r#"
             extern "Rust" {{
@@ -105,6 +173,12 @@ impl RsTemplate {
         };
 
         let cxx_signal_manager = if has_signals {
+            let signal_extern_stmts = if signal_externs.is_empty() {
+                String::new()
+            } else {
+                format!("\n{}", indent_str(&signal_externs.join("\n"), 4))
+            };
+
             formatdoc! {
                 r#"
                 #[namespace = "{cxx_ns}::signals"]
@@ -113,7 +187,7 @@ impl RsTemplate {
 
                     type SignalManager;
 
-                    fn emit(self: &SignalManager, id: usize, name: &str);
+                    fn emit(self: &SignalManager, id: usize, name: &str);{signal_extern_stmts}
                     #[rust_name = "get_signal_manager"]
                     fn getSignalManager() -> &'static SignalManager;
                 }}"#,
@@ -122,13 +196,117 @@ impl RsTemplate {
             String::new()
         };
 
+        let cxx_callback_manager = if has_callbacks {
+            let callback_extern_stmts = if callback_externs.is_empty() {
+                String::new()
+            } else {
+                format!("\n{}", indent_str(&callback_externs.join("\n"), 4))
+            };
+
+            formatdoc! {
+                r#"
+                #[namespace = "{cxx_ns}::callbacks"]
+                unsafe extern "C++" {{
+                    include!("CrabyCallbacks.h");
+
+                    type CallbackManager;
+
+                    fn invoke(self: &CallbackManager, id: usize);{callback_extern_stmts}
+                    fn release(self: &CallbackManager, id: usize);
+                    #[rust_name = "get_callback_manager"]
+                    fn getCallbackManager() -> &'static CallbackManager;
+                }}"#,
+            }
+        } else {
+            String::new()
+        };
+
+        let cxx_cancellation_manager = if has_abort_signals {
+            formatdoc! {
+                r#"
+                #[namespace = "{cxx_ns}::cancellation"]
+                unsafe extern "C++" {{
+                    include!("CrabyCancellation.h");
+
+                    type CancellationManager;
+
+                    #[rust_name = "is_cancelled"]
+                    fn isCancelled(self: &CancellationManager, id: usize) -> bool;
+                    fn release(self: &CancellationManager, id: usize);
+                    #[rust_name = "get_cancellation_manager"]
+                    fn getCancellationManager() -> &'static CancellationManager;
+                }}"#,
+            }
+        } else {
+            String::new()
+        };
+
+        let cxx_stream_manager = if has_streams {
+            let stream_extern_stmts = if stream_externs.is_empty() {
+                String::new()
+            } else {
+                format!("\n{}", indent_str(&stream_externs.join("\n"), 4))
+            };
+
+            formatdoc! {
+                r#"
+                #[namespace = "{cxx_ns}::streams"]
+                unsafe extern "C++" {{
+                    include!("CrabyStreams.h");
+
+                    type StreamManager;
+                {stream_extern_stmts}
+                    fn finish(self: &StreamManager, id: usize);
+                    fn fail(self: &StreamManager, id: usize, message: &str);
+                    fn release(self: &StreamManager, id: usize);
+                    #[rust_name = "get_stream_manager"]
+                    fn getStreamManager() -> &'static StreamManager;
+                }}"#,
+            }
+        } else {
+            String::new()
+        };
+
+        let cxx_promise_manager = if has_async_fns {
+            let promise_extern_stmts = if promise_externs.is_empty() {
+                String::new()
+            } else {
+                format!("\n{}", indent_str(&promise_externs.join("\n"), 4))
+            };
+
+            formatdoc! {
+                r#"
+                #[namespace = "{cxx_ns}::promises"]
+                unsafe extern "C++" {{
+                    include!("CrabyPromises.h");
+
+                    type PromiseManager;
+                {promise_extern_stmts}
+                    fn resolve(self: &PromiseManager, id: usize);
+                    fn reject(self: &PromiseManager, id: usize, message: &str);
+                    fn release(self: &PromiseManager, id: usize);
+                    #[rust_name = "get_promise_manager"]
+                    fn getPromiseManager() -> &'static PromiseManager;
+                }}"#,
+            }
+        } else {
+            String::new()
+        };
+
         let code = indent_str(
             &[
                 struct_defs.join("\n\n"),
                 enum_defs.join("\n\n"),
                 cxx_extern,
                 cxx_signal_manager,
+                cxx_callback_manager,
+                cxx_cancellation_manager,
+                cxx_stream_manager,
+                cxx_promise_manager,
             ]
+            .into_iter()
+            .filter(|code| !code.is_empty())
+            .collect::<Vec<_>>()
             .join("\n\n"),
             4,
         );
@@ -147,58 +325,128 @@ impl RsTemplate {
     /// # Generated Code
     ///
     /// ```rust,ignore
-    /// fn create_my_module(id: usize, data_path: &str) -> Box<MyModule> {
-    ///     let ctx = Context::new(id, data_path);
-    ///     Box::new(MyModule::new(ctx))
-    /// }
+    /// pub struct MyModuleInstance(std::sync::Arc<craby::sync::ModuleLock<MyModule>>);
     ///
-    /// fn my_module_multiply(it_: &mut MyModule, a: f64, b: f64) -> Result<f64> {
+    /// fn create_my_module(id: usize, data_path: &str) -> Result<Box<MyModuleInstance>> {
     ///     craby::catch_panic!({
+    ///         let ctx = Context::new(id, data_path);
+    ///         let module = craby::sync::ModuleLock::new(MyModule::new(ctx));
+    ///         Box::new(MyModuleInstance(std::sync::Arc::new(module)))
+    ///     })
+    ///     .map_err(craby::errors::into_ffi_error)
+    /// }
+    ///
+    /// fn my_module_multiply(it_: &MyModuleInstance, a: f64, b: f64) -> Result<f64> {
+    ///     craby::catch_panic!(it_.0, {
+    ///         let mut it_ = it_.0.lock();
     ///         let ret = it_.multiply(a, b);
     ///         ret
     ///     })
+    ///     .map_err(craby::errors::into_ffi_error)
     /// }
     /// ```
     fn rs_cxx_impl(&self, rs_cxx_bridges: &[RsCxxBridge]) -> Vec<String> {
+        let mut handle_impls: Vec<String> = vec![];
+        for bridge in rs_cxx_bridges {
+            for handle_impl in &bridge.handle_impls {
+                if !handle_impls.contains(handle_impl) {
+                    handle_impls.push(handle_impl.clone());
+                }
+            }
+        }
+
         rs_cxx_bridges
             .iter()
             .map(|bridge| bridge.func_impls.join("\n\n"))
+            .chain((!handle_impls.is_empty()).then(|| handle_impls.join("\n\n")))
             .collect::<Vec<_>>()
     }
 
     /// Generate the traits code for the given schema.
     ///
+    /// Methods of the modules in the `shared` concurrency mode take `&self` instead of `&mut self`,
+    /// and stream and `async fn` methods of the modules in the `lock` mode take `this: ModuleRef<Self>`.
+    ///
     /// ```rust,ignore
+    /// /// Spec of the `MyModule` module.
+    /// ///
+    /// /// Lifecycle: `new` (created) -> `on_reload` (reloaded) -> `on_invalidate` (torn down) -> `Drop` (destroyed).
+    /// /// ...
     /// pub trait MyModuleSpec {
+    ///     fn on_invalidate(&mut self) {}
+    ///     fn on_reload(&mut self) {}
+    ///     fn emit(&self, signal_name: MyModuleSignal) {
+    ///         let manager = crate::ffi::bridging::get_signal_manager();
+    ///         match signal_name {
+    ///             MyModuleSignal::OnReady => manager.emit(self.id(), "onReady"),
+    ///             MyModuleSignal::OnProgress(payload) => manager.emit_my_module_on_progress(self.id(), "onProgress", &payload),
+    ///         }
+    ///     }
     ///     fn multiply(&mut self, a: f64, b: f64) -> f64;
     /// }
+    ///
+    /// pub enum MyModuleSignal {
+    ///     OnReady,
+    ///     OnProgress(Progress),
+    /// }
     /// ```
-    fn rs_spec(&self, schema: &Schema) -> Result<String, anyhow::Error> {
+    fn rs_spec(
+        &self,
+        schema: &Schema,
+        concurrency: ConcurrencyMode,
+    ) -> Result<String, anyhow::Error> {
         let trait_name = pascal_case(&format!("{}Spec", schema.module_name));
+        let self_param = as_rs_self_param(concurrency);
         let mut methods = schema
             .methods
             .iter()
             .map(|spec| -> Result<String, anyhow::Error> {
-                let sig = spec.try_into_impl_sig()?;
-                Ok(format!("{sig};"))
+                let sig = spec.try_into_impl_sig(spec.as_rs_self_param(concurrency))?;
+                Ok(with_rs_method_doc_comment(
+                    spec.doc_with_params().as_deref(),
+                    format!("{sig};"),
+                ))
             })
             .collect::<Result<Vec<_>, _>>()?;
 
+        if let Some(sig) = schema.try_into_constants_sig()? {
+            methods.insert(0, format!("{sig};"));
+        }
+
         let signal_enum = if !schema.signals.is_empty() {
             let signal_enum_name = format!("{}Signal", schema.module_name);
             let (signal_members, pattern_matches): (Vec<String>, Vec<String>) = schema
                 .signals
                 .iter()
-                .map(|signal| {
+                .map(|signal| -> Result<(String, String), anyhow::Error> {
                     let member_name = pascal_case(&signal.name);
-                    let enum_member = format!("{member_name},");
-                    let enum_pattern_match = formatdoc! {
-                        r#"{signal_enum_name}::{member_name} => manager.emit(self.id(), "{raw}"),"#,
-                        raw = signal.name,
+                    let raw = &signal.name;
+
+                    let res = match &signal.payload_type {
+                        Some(payload_type) => {
+                            let payload_impl_type = payload_type.as_rs_impl_type()?.into_code();
+                            let payload = format!("&{}", payload_type.as_rs_into_expr("payload"));
+                            let emit_fn_name = signal.as_rs_emit_fn_name(&schema.module_name);
+
+                            (
+                                format!("{member_name}({payload_impl_type}),"),
+                                formatdoc! {
+                                    r#"{signal_enum_name}::{member_name}(payload) => manager.{emit_fn_name}(self.id(), "{raw}", {payload}),"#,
+                                },
+                            )
+                        }
+                        None => (
+                            format!("{member_name},"),
+                            formatdoc! {
+                                r#"{signal_enum_name}::{member_name} => manager.emit(self.id(), "{raw}"),"#,
+                            },
+                        ),
                     };
 
-                    (enum_member, enum_pattern_match)
+                    Ok(res)
                 })
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
                 .unzip();
 
             let signal_members_exprs = indent_str(&signal_members.join("\n"), 4);
@@ -227,14 +475,32 @@ impl RsTemplate {
             None
         };
 
+        // Lifecycle hooks are optional (no-op by default)
+        let hooks = Schema::lifecycle_hooks()
+            .iter()
+            .map(|hook| -> Result<String, anyhow::Error> {
+                let sig = hook.try_into_impl_sig(self_param)?;
+                Ok(with_doc_comment(hook.doc.as_deref(), format!("{sig} {{}}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let hook_defs = indent_str(&hooks.join("\n"), 4);
         let method_defs = indent_str(&methods.join("\n"), 4);
         let spec_trait = formatdoc! {
             r#"
+            /// Spec of the `{module_name}` module.
+            ///
+            /// Lifecycle: `new` (created) -> `on_reload` (reloaded) -> `on_invalidate` (torn down) -> `Drop` (destroyed).
+            /// The hooks are called on a background thread, so they may run concurrently with the pending calls.
+            /// Implement `Drop` as the destroy hook, it runs when the last reference to the module is released
+            /// (after `on_invalidate`, once the pending calls are completed).
             pub trait {trait_name} {{
                 fn new(ctx: Context) -> Self;
                 fn id(&self) -> usize;
+            {hook_defs}
             {method_defs}
-            }}"#
+            }}"#,
+            module_name = schema.module_name,
         };
 
         let content = [Some(spec_trait), signal_enum]
@@ -272,28 +538,87 @@ impl RsTemplate {
     ///     fn multiply(&mut self, a: Number, b: Number) -> Number {
     ///         unimplemented!();
     ///     }
+    ///
+    ///     // `@async` methods
+    ///     async fn fetch(&self, url: String) -> Promise<String> {
+    ///         unimplemented!();
+    ///     }
+    /// }
+    ///
+    /// // Host objects (`interface Connection extends HostObject`)
+    /// pub struct Connection {}
+    ///
+    /// impl ConnectionSpec for Connection {
+    ///     fn close(&mut self) {
+    ///         unimplemented!();
+    ///     }
     /// }
     /// ```
-    fn rs_impl(&self, schema: &Schema) -> Result<String, anyhow::Error> {
+    fn rs_impl(
+        &self,
+        schema: &Schema,
+        concurrency: ConcurrencyMode,
+    ) -> Result<String, anyhow::Error> {
         let struct_name = pascal_case(&schema.module_name);
         let trait_name = pascal_case(&format!("{}Spec", schema.module_name));
-        let methods = schema
-            .methods
-            .iter()
-            .map(|spec| -> Result<String, anyhow::Error> {
-                let func_sig = spec.try_into_impl_sig()?;
-                let code = formatdoc! {
+        let func_sigs = schema
+            .try_into_constants_sig()?
+            .into_iter()
+            .map(Ok)
+            .chain(
+                schema
+                    .methods
+                    .iter()
+                    .map(|spec| spec.try_into_impl_fn_sig(spec.as_rs_self_param(concurrency))),
+            )
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+        let methods = func_sigs
+            .into_iter()
+            .map(|func_sig| {
+                formatdoc! {
                   r#"
                   {func_sig} {{
                       unimplemented!();
                   }}"#,
-                };
-
-                Ok(code)
+                }
             })
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Vec<_>>();
 
         let method_impls = indent_str(&methods.join("\n\n"), 4);
+        let handle_impls = schema
+            .handles
+            .iter()
+            .map(|handle| -> Result<String, anyhow::Error> {
+                let name = pascal_case(&handle.name);
+                let trait_name = handle.as_handle_type().as_rs_spec_trait();
+                let methods = handle
+                    .methods
+                    .iter()
+                    .map(|spec| -> Result<String, anyhow::Error> {
+                        let func_sig = spec.try_into_impl_sig("&mut self")?;
+                        Ok(formatdoc! {
+                            r#"
+                            {func_sig} {{
+                                unimplemented!();
+                            }}"#,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let method_impls = indent_str(&methods.join("\n\n"), 4);
+
+                Ok(formatdoc! {
+                    r#"
+
+
+                    pub struct {name} {{}}
+
+                    impl {trait_name} for {name} {{
+                    {method_impls}
+                    }}"#,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .join("");
         let content = formatdoc! {
             r#"
             use craby::{{prelude::*, throw}};
@@ -308,7 +633,7 @@ impl RsTemplate {
             #[craby_module]
             impl {trait_name} for {struct_name} {{
             {method_impls}
-            }}"#,
+            }}{handle_impls}"#,
         };
 
         Ok(content)
@@ -372,10 +697,9 @@ impl RsTemplate {
             .map(|impl_mod| format!("use crate::{impl_mod}::*;"))
             .collect::<Vec<String>>();
 
-        let has_signals = ctx.schemas.iter().any(|schema| !schema.signals.is_empty());
-        let rs_cxx_bridges = self.rs_cxx_bridges(&ctx.schemas)?;
+        let rs_cxx_bridges = self.rs_cxx_bridges(ctx)?;
         let cxx_impls = self.rs_cxx_impl(&rs_cxx_bridges);
-        let cxx_externs = self.rs_cxx_extern(&cxx_ns, &rs_cxx_bridges, has_signals);
+        let cxx_externs = self.rs_cxx_extern(&cxx_ns, &rs_cxx_bridges, &ctx.schemas);
         let impl_mods = impl_mods.join("\n");
         let cxx_impls = cxx_impls.join("\n\n");
         let content = formatdoc! {
@@ -407,17 +731,17 @@ impl RsTemplate {
     ///     fn multiply(&mut self, a: f64, b: f64) -> f64;
     /// }
     /// ```
-    pub fn generated_rs(&self, schemas: &[Schema]) -> Result<String, anyhow::Error> {
-        let mut spec_codes = Vec::with_capacity(schemas.len());
+    pub fn generated_rs(&self, ctx: &CodegenContext) -> Result<String, anyhow::Error> {
+        let mut spec_codes = Vec::with_capacity(ctx.schemas.len());
         let mut type_aliases = BTreeMap::new();
 
-        for schema in schemas {
+        for schema in &ctx.schemas {
             // Collect the type implementations
             schema.try_collect_type_impls(&mut type_aliases)?;
-            spec_codes.push(self.rs_spec(schema)?);
+            spec_codes.push(self.rs_spec(schema, ctx.concurrency.resolve(&schema.module_name))?);
         }
 
-        let hash = Schema::to_hash(schemas);
+        let hash = Schema::to_hash(&ctx.schemas);
         let hash_comment = format!("{HASH_COMMENT_PREFIX} {hash}");
         let type_impls = type_aliases.into_values().collect::<Vec<_>>();
 
@@ -452,7 +776,7 @@ impl Template for RsTemplate {
         let content = match file_type {
             RsFileType::CrateEntry => self.lib_rs(&ctx.schemas),
             RsFileType::FFIEntry => self.ffi_rs(ctx),
-            RsFileType::Generated => self.generated_rs(&ctx.schemas),
+            RsFileType::Generated => self.generated_rs(ctx),
         }?;
 
         Ok(vec![(path, content)])
@@ -497,7 +821,8 @@ impl Generator<RsTemplate> for RsGenerator {
             ctx.schemas
                 .iter()
                 .map(|schema| -> Result<GenerateResult, anyhow::Error> {
-                    let impl_code = template.rs_impl(schema)?;
+                    let impl_code =
+                        template.rs_impl(schema, ctx.concurrency.resolve(&schema.module_name))?;
 
                     Ok(GenerateResult {
                         path: base_path.join(format!("{}.rs", impl_mod_name(&schema.module_name))),
@@ -526,7 +851,7 @@ impl GeneratorInvoker for RsGenerator {
 mod tests {
     use insta::assert_snapshot;
 
-    use crate::tests::get_codegen_context;
+    use crate::tests::{get_codegen_context, get_shared_types_codegen_context};
 
     use super::*;
 
@@ -543,4 +868,78 @@ mod tests {
 
         assert_snapshot!(result);
     }
+
+    #[test]
+    fn test_shared_types() {
+        let ctx = get_shared_types_codegen_context();
+        let generator = RsGenerator::new();
+        let results = generator.generate(&ctx).unwrap();
+        let result = results
+            .iter()
+            .map(|res| res.content.clone())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert!(result.matches("struct User {").count() == 1);
+    }
+
+    #[test]
+    fn test_host_object_send() {
+        let ctx = get_codegen_context();
+        let generator = RsGenerator::new();
+        let results = generator.generate(&ctx).unwrap();
+        let generated = &results[2].content;
+
+        assert!(generated.contains("pub trait ConnectionSpec: Send {"));
+    }
+
+    #[test]
+    fn test_lock_concurrency() {
+        let ctx = get_shared_types_codegen_context();
+        let generator = RsGenerator::new();
+        let results = generator.generate(&ctx).unwrap();
+        let result = results
+            .iter()
+            .map(|res| format!("{}\n{}", res.path.display(), res.content))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        assert_snapshot!(result);
+    }
+
+    #[test]
+    fn test_panic_options() {
+        let mut ctx = get_shared_types_codegen_context();
+        ctx.panic.backtrace = true;
+        ctx.panic.poison = true;
+        let generator = RsGenerator::new();
+        let results = generator.generate(&ctx).unwrap();
+        let ffi = &results[1].content;
+
+        assert_eq!(
+            ffi.matches("craby::errors::set_panic_backtrace(true);")
+                .count(),
+            2
+        );
+        assert!(ffi.contains(
+            "let module = craby::sync::ModuleLock::new(UserModule::new(ctx)).poison_on_panic();"
+        ));
+    }
+
+    #[test]
+    fn test_shared_concurrency() {
+        let mut ctx = get_codegen_context();
+        ctx.concurrency
+            .modules
+            .insert("CrabyTest".to_string(), ConcurrencyMode::Shared);
+        let generator = RsGenerator::new();
+        let results = generator.generate(&ctx).unwrap();
+        let result = results
+            .iter()
+            .map(|res| format!("{}\n{}", res.path.display(), res.content))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        assert_snapshot!(result);
+    }
 }
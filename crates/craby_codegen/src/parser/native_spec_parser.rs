@@ -1,11 +1,13 @@
+use craby_common::utils::string::{pascal_case, snake_case};
 use log::debug;
 use oxc::{
     allocator::Allocator,
     ast::ast::*,
-    ast_visit::Visit,
+    ast_visit::{walk, Visit},
     diagnostics::OxcDiagnostic,
     parser::Parser,
-    semantic::{Scoping, SemanticBuilder, SymbolId},
+    semantic::{ReferenceId, Scoping, SemanticBuilder, SymbolId},
+    span::{GetSpan, Span},
 };
 use rustc_hash::{FxHashMap, FxHashSet};
 
@@ -20,23 +22,97 @@ const INVALID_TYPE_REFERENCE: &str = "Invalid type reference";
 const INVALID_COMPUTED_SIG: &str = "Computed signature is not supported";
 const INVALID_OPTIONAL_SIG: &str = "Optional signature is not supported";
 const INVALID_NO_SPEC_GENERIC: &str = "NativeModule specification generic argument is required";
-const INVALID_FUNC_PARAM: &str = "Function parameter is not supported";
+const INVALID_FUNC_PARAM: &str =
+    "Function type is only supported as a method parameter type (eg. `(arg: T) => void`)";
 const INVALID_TYPE_LITERAL: &str =
-    "Type literal is not supported. Use defined type reference instead";
+    "Type literal is only supported in method and signal signatures. Use defined type reference instead";
 const INVALID_UNION_TYPE: &str = "Union types only allow nullable type (eg. `T | null`)";
+const INVALID_LITERAL_UNION_TYPE: &str =
+    "Literal union type is only supported in type aliases and method and signal signatures (eg. `type Mode = 'fast' | 'safe'`)";
+const INVALID_LITERAL_UNION_MEMBER: &str =
+    "Literal union type only allows string or numeric literals";
+const INVALID_TAGGED_UNION_TYPE: &str = "Discriminated union type must be declared as a type alias (eg. `type Outcome = { kind: 'ok' } | { kind: 'err' }`)";
+const INVALID_TAGGED_UNION: &str =
+    "Discriminated union members must be object type literals with a literal discriminant property (eg. `{ kind: 'ok', value: T }`)";
+const INVALID_ERROR_CODE: &str =
+    "Error code enum must be a string enum (eg. `enum MyError { NotFound = 'NOT_FOUND' }`)";
 const INVALID_MIXED_ENUM_MEMBER: &str =
     "Enum member type must be single type (eg. only `number` or `string`)";
+const INVALID_ENUM_MEMBER: &str =
+    "Enum member initializer must be a string or numeric literal (eg. `'foo'`, `1`, `-1`, `0.5`)";
+const INVALID_ENUM_REPR: &str =
+    "Enum repr must be one of `i8`, `i16`, `i32`, `i64`, `u8`, `u16`, `u32` and `u64`";
+const INVALID_ENUM_REPR_MEMBER: &str = "Enum repr is only supported for integer enum members";
 const INVALID_REGISTRY_METHOD: &str = "Invalid NativeModuleRegistry method";
 const INVALID_RESERVED_ARG_NAME_ID: &str = "Reserved argument name `it_` is not allowed";
 const INVALID_RESERVED_METHOD_NAME_ID: &str = "Reserved method name `emit` is not allowed";
+const INVALID_RESERVED_METHOD_NAME_LIFECYCLE: &str =
+    "Reserved method names `onInvalidate` and `onReload` are not allowed (lifecycle hooks)";
+const INVALID_UINT8_ARRAY: &str =
+    "Uint8Array is only supported as a method parameter type. Use `ArrayBuffer` instead";
+const INVALID_MAP_TYPE: &str =
+    "Map key type must be `string` (eg. `Record<string, T>` or `{ [key: string]: T }`)";
+const INVALID_REQUIRED_PARAM: &str = "Required parameter cannot follow an optional parameter";
+const INVALID_CALLBACK: &str =
+    "Callback must have at most one parameter and return `void` (eg. `(arg: T) => void`)";
+const INVALID_OPTIONAL_CALLBACK: &str = "Callback parameter cannot be optional";
+const INVALID_EXTENDS: &str =
+    "Interface can only extend object types without type arguments (eg. `interface Photo extends MediaBase`)";
+const INVALID_EXTENDS_CYCLE: &str = "Circular interface inheritance is not allowed";
+const INVALID_EXTENDS_CONFLICT: &str = "Conflicting property type with the inherited property";
+const INVALID_IMPORTED_TYPE: &str = "Type not found in the imported source";
+const INVALID_IMPORT_SPECIFIER: &str =
+    "Only named type imports are supported for relative imports (eg. `import type { User } from './types'`)";
+const INVALID_SIGNAL_PAYLOAD: &str =
+    "Signal payload must be exactly one non-promise type (eg. `Signal<T>`)";
+const INVALID_STREAM: &str =
+    "`AsyncIterable` is only allowed as a method return type with a non-void item type (eg. `AsyncIterable<T>`)";
+const INVALID_RESERVED_ARG_NAME_STREAM: &str =
+    "Reserved argument name `stream` is not allowed in stream methods";
+const INVALID_ABORT_SIGNAL: &str =
+    "`AbortSignal` is only allowed as a single parameter of promise methods (eg. `run(signal?: AbortSignal): Promise<T>`)";
+const INVALID_HANDLE: &str =
+    "Host object interface can only extend `HostObject` and declare methods (eg. `interface Connection extends HostObject { close(): void }`)";
+const INVALID_HANDLE_METHOD: &str =
+    "Host object methods must be synchronous without callback or `AbortSignal` parameters";
+const INVALID_HANDLE_USAGE: &str =
+    "Host object is only allowed as a method return type (eg. `open(): Connection` or `open(): Promise<Connection>`)";
+const INVALID_HANDLE_NOT_FOUND: &str = "Host object interface must be declared in the spec file";
+const INVALID_THREAD: &str =
+    "Thread affinity must be one of `js`, `pool`, `serial` or a thread name (eg. `@thread io`)";
+const INVALID_THREAD_SYNC: &str =
+    "Thread affinity is only supported for promise and stream methods (sync methods run on the JS thread)";
+const INVALID_THREAD_STREAM: &str = "Stream methods cannot run on the JS thread";
+const INVALID_THREAD_SPEC: &str =
+    "Default thread affinity of the module must be one of `pool`, `serial` or a thread name";
+const INVALID_ASYNC_FN: &str =
+    "`@async` is only supported for promise methods that do not return a host object (eg. `fetch(url: string): Promise<string>`)";
+const INVALID_ASYNC_FN_THREAD: &str =
+    "`@async` methods are driven by the async executor and cannot have thread affinity";
+const INVALID_RESERVED_ARG_NAME_PROMISE: &str =
+    "Reserved argument name `promise` is not allowed in `@async` methods";
+const INVALID_CONSTANTS: &str =
+    "`getConstants` must have no parameters and return an object type (eg. `getConstants(): { version: string }`)";
+
+/// `extends` clause of the interface (flattened after all declarations are collected)
+struct InterfaceExtends {
+    /// Base type references and their spans
+    bases: Vec<(ReferenceId, Span)>,
+    /// Spans of the own properties (key: property name)
+    prop_spans: FxHashMap<String, Span>,
+}
 
 pub struct NativeModuleAnalyzer<'a> {
     pub diagnostics: Vec<OxcDiagnostic>,
     scoping: &'a Scoping,
     /// Symbol ID of `NativeModule` identifier's reference
     mod_type_sym_id: Option<SymbolId>,
+    /// Symbol ID of `HostObject` identifier's reference
+    mod_host_object_sym_id: Option<SymbolId>,
     /// Symbol ID of `Signal` identifier's reference
     mod_signal_sym_id: Option<SymbolId>,
+    /// Symbol IDs of the numeric type (eg. `Int32`) identifiers' references
+    mod_numeric_sym_ids: FxHashMap<SymbolId, TypeAnnotation>,
     /// Symbol ID of `NativeModuleRegistry` identifier's reference
     mod_reg_sym_id: Option<SymbolId>,
     /// Symbol ID of `react-native` namespace's reference
@@ -45,22 +121,52 @@ pub struct NativeModuleAnalyzer<'a> {
     mods: FxHashMap<SymbolId, String>,
     /// Declarations collected from the source code
     decls: FxHashMap<SymbolId, TypeAnnotation>,
+    /// Types of the relative imports (key: import source, eg. `./types`)
+    imported_types: FxHashMap<String, ImportedTypes>,
+    /// Symbol IDs of the declarations imported from the other source files
+    imported_sym_ids: FxHashSet<SymbolId>,
+    /// JSDoc comments (key: start of the token the comment is attached to)
+    jsdocs: FxHashMap<u32, String>,
+    /// Start of the `export` keyword (key: start of the exported declaration)
+    export_starts: FxHashMap<u32, u32>,
+    /// Interfaces with `extends` clause (eg. `interface Photo extends MediaBase`)
+    extends: FxHashMap<SymbolId, InterfaceExtends>,
     /// NativeModule specs collected from the source code
     specs: FxHashMap<SymbolId, Spec>,
+    /// Host object interfaces collected from the source code (key: interface name)
+    handles: FxHashMap<String, Handle>,
+    /// Name for the type literal in the current signature (eg. `MyMethodArgOptions`)
+    type_lit_name: Option<String>,
+    /// Names of the type literals and literal unions collected from the signatures (key: `TypeAnnotation::to_id` of the unnamed type)
+    type_lits: FxHashMap<u64, String>,
 }
 
 impl<'a> NativeModuleAnalyzer<'a> {
-    fn new(scoping: &'a Scoping) -> Self {
+    fn new(
+        scoping: &'a Scoping,
+        imported_types: FxHashMap<String, ImportedTypes>,
+        jsdocs: FxHashMap<u32, String>,
+    ) -> Self {
         Self {
             scoping,
             diagnostics: vec![],
             mod_type_sym_id: None,
+            mod_host_object_sym_id: None,
             mod_signal_sym_id: None,
+            mod_numeric_sym_ids: FxHashMap::default(),
             mod_reg_sym_id: None,
             mod_ns_sym_id: None,
             specs: FxHashMap::default(),
+            handles: FxHashMap::default(),
             mods: FxHashMap::default(),
             decls: FxHashMap::default(),
+            imported_types,
+            imported_sym_ids: FxHashSet::default(),
+            jsdocs,
+            export_starts: FxHashMap::default(),
+            extends: FxHashMap::default(),
+            type_lit_name: None,
+            type_lits: FxHashMap::default(),
         }
     }
 
@@ -82,12 +188,36 @@ impl<'a> NativeModuleAnalyzer<'a> {
     fn collect_spec(&mut self, it: &TSInterfaceDeclaration<'a>) {
         let mut methods = vec![];
         let mut signals = vec![];
+        let mut constants = None;
+
+        // Default thread affinity of the async methods (eg. `@thread serial` on the spec interface)
+        let (_, thread) = split_doc_tag(self.doc_at(it.span.start), "@thread");
+        let default_thread = match thread.as_deref().map(parse_thread_affinity) {
+            Some(Some(ThreadAffinity::Js)) => {
+                return self.collect_error(INVALID_THREAD_SPEC, it.span)
+            }
+            Some(None) => return self.collect_error(INVALID_THREAD, it.span),
+            Some(thread) => thread,
+            None => None,
+        };
 
         for sig in &it.body.body {
             match sig {
+                TSSignature::TSMethodSignature(method_sig) if self.is_constants_sig(method_sig) => {
+                    match self.try_into_constants(method_sig) {
+                        Ok(type_annotation) => constants = Some(type_annotation),
+                        Err(e) => return self.diagnostics.push(e),
+                    }
+                }
                 TSSignature::TSMethodSignature(method_sig) => {
                     match self.try_into_method(method_sig) {
-                        Ok(method) => methods.push(method),
+                        Ok(mut method) => {
+                            // `async fn` methods are driven by the async executor instead of the threads
+                            if method.is_async() && !method.async_fn && method.thread.is_none() {
+                                method.thread = default_thread.clone();
+                            }
+                            methods.push(method);
+                        }
                         Err(e) => return self.diagnostics.push(e),
                     }
                 }
@@ -108,40 +238,263 @@ impl<'a> NativeModuleAnalyzer<'a> {
                 name,
                 methods,
                 signals,
+                constants,
             },
         );
     }
 
-    fn collect_interface_type(&mut self, it: &TSInterfaceDeclaration<'a>) {
+    /// Collect the host object interface (eg. `interface Connection extends HostObject { ... }`)
+    fn collect_handle(&mut self, it: &TSInterfaceDeclaration<'a>) {
         if let Err(e) = self.try_assert_reserved_type(&it.id.name) {
             return self.collect_error(&e.to_string(), it.span);
         };
 
-        if !it.extends.is_empty() {
-            return self.collect_error(INVALID_SPEC, it.span);
+        if it.extends.len() != 1 {
+            return self.collect_error(INVALID_HANDLE, it.span);
+        }
+
+        let mut methods = vec![];
+        for sig in &it.body.body {
+            let method_sig = match sig {
+                TSSignature::TSMethodSignature(method_sig) => method_sig,
+                _ => return self.collect_error(INVALID_HANDLE, it.span),
+            };
+
+            let method = match self.try_into_method(method_sig) {
+                Ok(method) => method,
+                Err(e) => return self.diagnostics.push(e),
+            };
+
+            // Host object methods are invoked on the JS thread only
+            let has_callbacks = method.params.iter().any(|param| {
+                param.type_annotation.is_callback() || param.type_annotation.is_abort_signal()
+            });
+            if method.is_async() || has_callbacks {
+                return self.collect_error(INVALID_HANDLE_METHOD, method_sig.span);
+            }
+
+            methods.push(method);
+        }
+
+        let name = it.id.name.to_string();
+        self.decls.insert(
+            it.id.symbol_id(),
+            TypeAnnotation::Handle(HandleTypeAnnotation { name: name.clone() }),
+        );
+        self.handles.insert(
+            name.clone(),
+            Handle {
+                name,
+                methods,
+                doc: self.doc_at(it.span.start),
+            },
+        );
+    }
+
+    /// Collect the types imported from the other source file (eg. `import type { User } from './types'`)
+    fn collect_imported_types(&mut self, it: &ImportDeclaration<'a>) {
+        let specifiers = match &it.specifiers {
+            Some(specifiers) => specifiers,
+            None => return,
+        };
+
+        for specifier in specifiers {
+            match specifier {
+                ImportDeclarationSpecifier::ImportSpecifier(spec) => {
+                    let name = spec.imported.name();
+                    let type_annotation = self
+                        .imported_types
+                        .get(it.source.value.as_str())
+                        .and_then(|types| types.get(name.as_str()))
+                        .cloned();
+
+                    match type_annotation {
+                        Some(type_annotation) => {
+                            let id = spec.local.symbol_id();
+                            self.decls.insert(id, type_annotation);
+                            self.imported_sym_ids.insert(id);
+                        }
+                        None => self
+                            .collect_error(&format!("{INVALID_IMPORTED_TYPE}: {name}"), spec.span),
+                    }
+                }
+                _ => self.collect_error(INVALID_IMPORT_SPECIFIER, it.span),
+            }
         }
+    }
+
+    fn collect_interface_type(&mut self, it: &TSInterfaceDeclaration<'a>) {
+        if let Err(e) = self.try_assert_reserved_type(&it.id.name) {
+            return self.collect_error(&e.to_string(), it.span);
+        };
 
         let id = it.id.symbol_id();
         let name = it.id.name.to_string();
 
+        // Index signature only interface (eg. `interface Headers { [key: string]: string }`)
+        if let Some(index_sig) = self.as_index_signature(&it.body.body) {
+            if !it.extends.is_empty() {
+                return self.collect_error(INVALID_SPEC, it.span);
+            }
+
+            match self.try_into_map(index_sig) {
+                Ok(type_annotation) => drop(self.decls.insert(id, type_annotation)),
+                Err(e) => self.diagnostics.push(error(&e.to_string(), it.span)),
+            }
+            return;
+        }
+
         // Collect type alias
         let mut props = vec![];
+        let mut prop_spans = FxHashMap::default();
         for sig in &it.body.body {
             match sig {
                 TSSignature::TSPropertySignature(prop_sig) => match self.try_into_prop(prop_sig) {
-                    Ok(prop) => props.push(prop),
+                    Ok(prop) => {
+                        prop_spans.insert(prop.name.clone(), prop_sig.span);
+                        props.push(prop);
+                    }
                     Err(e) => return self.diagnostics.push(e),
                 },
                 _ => return self.collect_error(INVALID_SPEC, it.span),
             }
         }
 
+        // Inherited properties are flattened in `flatten_extends`
+        if !it.extends.is_empty() {
+            let mut bases = vec![];
+            for heritage in &it.extends {
+                match heritage.expression.get_identifier_reference() {
+                    Some(ident_ref) if heritage.type_arguments.is_none() => {
+                        bases.push((ident_ref.reference_id(), heritage.span))
+                    }
+                    _ => return self.collect_error(INVALID_EXTENDS, heritage.span),
+                }
+            }
+            self.extends
+                .insert(id, InterfaceExtends { bases, prop_spans });
+        }
+
+        let doc = self.doc_at(it.span.start);
         self.decls.insert(
             id,
-            TypeAnnotation::Object(ObjectTypeAnnotation { name, props }),
+            TypeAnnotation::Object(ObjectTypeAnnotation { name, props, doc }),
         );
     }
 
+    /// Flattens the inherited properties into the interfaces with `extends` clause.
+    ///
+    /// Properties of the base types come first, in the order of the `extends` clause.
+    fn flatten_extends(&mut self) {
+        let mut ids = self.extends.keys().copied().collect::<Vec<_>>();
+        ids.sort();
+
+        let mut done = FxHashSet::default();
+        for id in ids {
+            let mut visiting = vec![];
+            if let Err(e) = self.try_flatten_extends(id, &mut visiting, &mut done) {
+                // Report the error once for the interfaces in the inheritance chain
+                done.extend(visiting);
+                self.diagnostics.push(e);
+            }
+        }
+    }
+
+    fn try_flatten_extends(
+        &mut self,
+        id: SymbolId,
+        visiting: &mut Vec<SymbolId>,
+        done: &mut FxHashSet<SymbolId>,
+    ) -> Result<(), OxcDiagnostic> {
+        if done.contains(&id) {
+            return Ok(());
+        }
+
+        let interface_extends = match self.extends.remove(&id) {
+            Some(interface_extends) => interface_extends,
+            None => return Ok(()),
+        };
+
+        visiting.push(id);
+
+        let mut props: Vec<Prop> = vec![];
+        for (ref_id, span) in &interface_extends.bases {
+            let base_id = self
+                .scoping
+                .get_reference(*ref_id)
+                .symbol_id()
+                .ok_or_else(|| error(INVALID_TYPE_REFERENCE, *span))?;
+
+            if visiting.contains(&base_id) {
+                return Err(error(INVALID_EXTENDS_CYCLE, *span));
+            }
+
+            self.try_flatten_extends(base_id, visiting, done)?;
+
+            let base_props = match self.decls.get(&base_id) {
+                Some(TypeAnnotation::Object(base)) => base.props.clone(),
+                _ => return Err(error(INVALID_EXTENDS, *span)),
+            };
+
+            for base_prop in base_props {
+                match props.iter().find(|prop| prop.name == base_prop.name) {
+                    Some(prop) if !self.is_same_prop(prop, &base_prop) => {
+                        return Err(error(
+                            &format!("{INVALID_EXTENDS_CONFLICT}: {}", base_prop.name),
+                            *span,
+                        ));
+                    }
+                    Some(_) => {}
+                    None => props.push(base_prop),
+                }
+            }
+        }
+
+        let own_props = match self.decls.get(&id) {
+            Some(TypeAnnotation::Object(obj)) => obj.props.clone(),
+            _ => unreachable!("Interface declaration not found (sym: {:?})", id),
+        };
+
+        for own_prop in own_props {
+            match props.iter().position(|prop| prop.name == own_prop.name) {
+                Some(index) if !self.is_same_prop(&props[index], &own_prop) => {
+                    let span = interface_extends.prop_spans[&own_prop.name];
+                    return Err(error(
+                        &format!("{INVALID_EXTENDS_CONFLICT}: {}", own_prop.name),
+                        span,
+                    ));
+                }
+                Some(_) => {}
+                None => props.push(own_prop),
+            }
+        }
+        if let Some(TypeAnnotation::Object(obj)) = self.decls.get_mut(&id) {
+            obj.props = props;
+        }
+
+        visiting.pop();
+        done.insert(id);
+
+        Ok(())
+    }
+
+    /// Compares the properties with the resolved types (type references have different IDs).
+    fn is_same_prop(&self, a: &Prop, b: &Prop) -> bool {
+        let resolve = |prop: &Prop| {
+            let mut prop = prop.clone();
+            // Documentation does not affect the property type
+            prop.doc = None;
+            NativeModuleAnalyzer::resolve_refs(
+                &mut prop.type_annotation,
+                self.scoping,
+                &self.decls,
+            );
+            prop
+        };
+
+        a.name == b.name && resolve(a) == resolve(b)
+    }
+
     fn collect_alias_type(&mut self, it: &TSTypeAliasDeclaration<'a>) {
         if let Err(e) = self.try_assert_reserved_type(&it.id.name) {
             return self.collect_error(&e.to_string(), it.span);
@@ -157,6 +510,14 @@ impl<'a> NativeModuleAnalyzer<'a> {
         let name = it.id.name.to_string();
 
         match &it.type_annotation {
+            TSType::TSTypeLiteral(type_lit)
+                if self.as_index_signature(&type_lit.members).is_some() =>
+            {
+                match self.try_into_type_annotation(&it.type_annotation) {
+                    Ok(type_annotation) => drop(self.decls.insert(id, type_annotation)),
+                    Err(e) => self.diagnostics.push(error(&e.to_string(), it.span)),
+                }
+            }
             TSType::TSTypeLiteral(type_lit) => {
                 let props = type_lit
                     .members
@@ -169,94 +530,136 @@ impl<'a> NativeModuleAnalyzer<'a> {
 
                 match props {
                     Ok(props) => {
+                        let doc = self.doc_at(it.span.start);
                         self.decls.insert(
                             id,
-                            TypeAnnotation::Object(ObjectTypeAnnotation { name, props }),
+                            TypeAnnotation::Object(ObjectTypeAnnotation { name, props, doc }),
                         );
                     }
                     Err(e) => self.diagnostics.push(e),
                 }
             }
+            // `type Mode = 'fast' | 'safe'`
+            TSType::TSUnionType(union_type) if self.is_literal_union(union_type) => {
+                let doc = self.doc_at(it.span.start);
+                match self.try_into_literal_enum(name, doc, union_type) {
+                    Ok(type_annotation) => drop(self.decls.insert(id, type_annotation)),
+                    Err(e) => self.diagnostics.push(error(&e.to_string(), it.span)),
+                }
+            }
+            // `type Outcome = { kind: 'ok', value: number } | { kind: 'err', reason: string }`
+            TSType::TSUnionType(union_type) if self.is_tagged_union(union_type) => {
+                match self.try_into_tagged_union(name, union_type) {
+                    Ok(type_annotation) => drop(self.decls.insert(id, type_annotation)),
+                    Err(e) => self.diagnostics.push(error(&e.to_string(), it.span)),
+                }
+            }
             TSType::TSUnionType(union_type) => match self.try_into_nullable(union_type) {
                 Ok(type_annotation) => drop(self.decls.insert(id, type_annotation)),
                 Err(e) => self.diagnostics.push(error(&e.to_string(), it.span)),
             },
+            // `type Headers = Record<string, string>`
+            TSType::TSTypeReference(..) => match self.try_into_type_annotation(&it.type_annotation)
+            {
+                Ok(type_annotation @ TypeAnnotation::Map(..)) => {
+                    drop(self.decls.insert(id, type_annotation))
+                }
+                Ok(..) => self.collect_error(INVALID_SPEC, it.span),
+                Err(e) => self.diagnostics.push(error(&e.to_string(), it.span)),
+            },
             _ => self.collect_error(INVALID_SPEC, it.span),
         }
     }
 
     fn collect_enum_type(&mut self, it: &TSEnumDeclaration<'a>) {
-        let mut members = vec![];
-        let mut prev_num_raw_val = 0;
-        let mut member_type = None;
-
-        for (idx, member) in it.body.members.iter().enumerate() {
-            match &member.initializer {
-                Some(expr) => match expr {
-                    Expression::NumericLiteral(num_lit) => {
-                        if let Some(type_annotation) = &member_type {
-                            if !matches!(type_annotation, TypeAnnotation::Number) {
-                                return self.collect_error(INVALID_MIXED_ENUM_MEMBER, it.span);
-                            }
-                        } else {
-                            member_type = Some(TypeAnnotation::Number);
-                        }
+        let mut members: Vec<EnumMember> = vec![];
+        // Members without initializer are auto-incremented from the previous numeric member
+        let mut next_num_val = 0.0;
+
+        for member in &it.body.members {
+            let value = match &member.initializer {
+                Some(expr) => match self.try_into_enum_member_value(expr) {
+                    Ok(value) => value,
+                    Err(e) => return self.collect_error(&e.to_string(), member.span),
+                },
+                None => match EnumMemberValue::from_f64(next_num_val) {
+                    Ok(value) => value,
+                    Err(e) => return self.collect_error(&e.to_string(), member.span),
+                },
+            };
 
-                        let raw = num_lit.value as usize;
-                        prev_num_raw_val = raw;
-                        let is_float = num_lit.raw_str().contains(".");
+            next_num_val = match &value {
+                EnumMemberValue::Number(val) => *val as f64 + 1.0,
+                EnumMemberValue::Float(val) => val.parse::<f64>().unwrap_or_default() + 1.0,
+                EnumMemberValue::String(..) => next_num_val,
+            };
 
-                        if is_float {
-                            self.collect_error("Float number is not supported in enum", it.span);
-                        } else {
-                            members.push(EnumMember {
-                                name: member.id.static_name().to_string(),
-                                value: EnumMemberValue::Number(raw),
-                            });
-                        }
-                    }
-                    Expression::StringLiteral(str_lit) => {
-                        if let Some(type_annotation) = &member_type {
-                            if !matches!(type_annotation, TypeAnnotation::String) {
-                                return self.collect_error(INVALID_MIXED_ENUM_MEMBER, it.span);
-                            }
-                        } else {
-                            member_type = Some(TypeAnnotation::String);
-                        }
+            if let Some(first) = members.first() {
+                if first.value.is_string() != value.is_string() {
+                    return self.collect_error(INVALID_MIXED_ENUM_MEMBER, it.span);
+                }
+            }
 
-                        members.push(EnumMember {
-                            name: member.id.static_name().to_string(),
-                            value: EnumMemberValue::String(str_lit.value.into_string()),
-                        });
-                    }
-                    _ => self.collect_error(INVALID_SPEC, it.span),
-                },
-                None => {
-                    if let Some(type_annotation) = &member_type {
-                        if !matches!(type_annotation, TypeAnnotation::Number) {
-                            return self.collect_error(INVALID_MIXED_ENUM_MEMBER, it.span);
-                        }
-                    } else {
-                        member_type = Some(TypeAnnotation::Number);
-                    }
+            // Values are used as the discriminants of the generated enum
+            if members.iter().any(|m| m.value == value) {
+                return self.collect_error(
+                    &format!("Duplicate enum member value: {value}"),
+                    member.span,
+                );
+            }
 
-                    members.push(EnumMember {
-                        name: member.id.static_name().to_string(),
-                        value: EnumMemberValue::Number(prev_num_raw_val + idx),
-                    });
-                }
-            };
+            members.push(EnumMember {
+                name: member.id.static_name().to_string(),
+                value,
+                doc: self.doc_at(member.span.start),
+            });
         }
 
+        let (doc, repr) = split_doc_tag(self.doc_at(it.span.start), "@repr");
+        let repr = match try_into_enum_repr(repr, &members) {
+            Ok(repr) => repr,
+            Err(e) => return self.collect_error(&e.to_string(), it.span),
+        };
+
         self.decls.insert(
             it.id.symbol_id(),
             TypeAnnotation::Enum(EnumTypeAnnotation {
                 name: it.id.name.to_string(),
                 members,
+                repr,
+                error_code: false,
+                doc,
             }),
         );
     }
 
+    /// Converts the enum member initializer (eg. `'foo'`, `1`, `-1`, `0.5`) into the member value.
+    fn try_into_enum_member_value(
+        &self,
+        expr: &Expression<'a>,
+    ) -> Result<EnumMemberValue, anyhow::Error> {
+        match expr {
+            Expression::StringLiteral(str_lit) => {
+                Ok(EnumMemberValue::String(str_lit.value.to_string()))
+            }
+            Expression::NumericLiteral(num_lit) => EnumMemberValue::from_f64(num_lit.value),
+            Expression::UnaryExpression(unary_expr)
+                if unary_expr.operator == UnaryOperator::UnaryNegation =>
+            {
+                match &unary_expr.argument {
+                    Expression::NumericLiteral(num_lit) => {
+                        EnumMemberValue::from_f64(-num_lit.value)
+                    }
+                    _ => anyhow::bail!(INVALID_ENUM_MEMBER),
+                }
+            }
+            Expression::ParenthesizedExpression(paren_expr) => {
+                self.try_into_enum_member_value(&paren_expr.expression)
+            }
+            _ => anyhow::bail!(INVALID_ENUM_MEMBER),
+        }
+    }
+
     fn as_spec_id(&mut self, it: &CallExpression<'a>) -> Option<SymbolId> {
         let spec_generic = match &it.type_arguments {
             Some(type_arguments) => match type_arguments.params.first() {
@@ -353,13 +756,66 @@ impl<'a> NativeModuleAnalyzer<'a> {
 
                 Ok(Prop {
                     name: prop_name,
-                    type_annotation,
+                    type_annotation: if prop_sig.optional {
+                        type_annotation.into_nullable()
+                    } else {
+                        type_annotation
+                    },
+                    optional: prop_sig.optional,
+                    doc: self.doc_at(prop_sig.span.start),
                 })
             }
             _ => Err(error(INVALID_SPEC, prop_sig.span)),
         }
     }
 
+    fn is_constants_sig(&self, sig: &TSMethodSignature<'a>) -> bool {
+        matches!(&sig.key, PropertyKey::StaticIdentifier(ident) if ident.name == CONSTANTS_METHOD_NAME)
+    }
+
+    /// Returns the object type of the module constants (eg. `getConstants(): { version: string }`).
+    ///
+    /// Type literals are named after the module in `try_into_schema` (eg. `MyModuleConstants`).
+    fn try_into_constants(
+        &mut self,
+        sig: &TSMethodSignature<'a>,
+    ) -> Result<TypeAnnotation, OxcDiagnostic> {
+        if sig.optional || !sig.params.items.is_empty() {
+            return Err(error(INVALID_CONSTANTS, sig.span));
+        }
+
+        let ret_type = sig
+            .return_type
+            .as_ref()
+            .ok_or_else(|| error(INVALID_CONSTANTS, sig.span))?;
+
+        match &ret_type.type_annotation {
+            TSType::TSTypeLiteral(type_lit)
+                if self.as_index_signature(&type_lit.members).is_none() =>
+            {
+                let props = type_lit
+                    .members
+                    .iter()
+                    .map(|member| match member {
+                        TSSignature::TSPropertySignature(prop_sig) => self.try_into_prop(prop_sig),
+                        _ => Err(error(INVALID_CONSTANTS, type_lit.span)),
+                    })
+                    .collect::<Result<Vec<Prop>, OxcDiagnostic>>()?;
+
+                Ok(TypeAnnotation::Object(ObjectTypeAnnotation {
+                    name: String::new(),
+                    props,
+                    doc: None,
+                }))
+            }
+            // Resolved and validated in `try_into_schema`
+            TSType::TSTypeReference(..) => self
+                .try_into_type_annotation(&ret_type.type_annotation)
+                .map_err(|e| error(&e.to_string(), sig.span)),
+            _ => Err(error(INVALID_CONSTANTS, sig.span)),
+        }
+    }
+
     fn try_into_method(&mut self, sig: &TSMethodSignature<'a>) -> Result<Method, OxcDiagnostic> {
         if sig.computed {
             return Err(error(INVALID_COMPUTED_SIG, sig.span));
@@ -378,6 +834,20 @@ impl<'a> NativeModuleAnalyzer<'a> {
             return Err(error(INVALID_RESERVED_METHOD_NAME_ID, sig.span));
         }
 
+        if RESERVED_METHOD_NAMES_LIFECYCLE.contains(&snake_case(&method_name).as_str()) {
+            return Err(error(INVALID_RESERVED_METHOD_NAME_LIFECYCLE, sig.span));
+        }
+
+        let (doc, mut param_docs) = split_param_docs(self.doc_at(sig.span.start));
+        let (doc, thread) = split_doc_tag(doc, "@thread");
+        let thread = thread
+            .map(|value| {
+                parse_thread_affinity(&value).ok_or_else(|| error(INVALID_THREAD, sig.span))
+            })
+            .transpose()?;
+        let (doc, async_fn) = split_doc_tag(doc, "@async");
+        let async_fn = async_fn.is_some();
+        let mut has_optional = false;
         let params = sig
             .params
             .items
@@ -403,10 +873,55 @@ impl<'a> NativeModuleAnalyzer<'a> {
                     .as_ref()
                     .ok_or_else(|| error(INVALID_SPEC, param.span))?;
 
-                match self.try_into_type_annotation(&param_type_annotation.type_annotation) {
+                // `Uint8Array` is accepted as an input only (values returned to JS are `ArrayBuffer`)
+                let type_lit_name = format!(
+                    "{}{}",
+                    pascal_case(&method_name),
+                    pascal_case(param_name.as_str())
+                );
+                // `options` parameters are not suffixed twice (eg. `RequestOptions`, not `RequestOptionsOptions`)
+                // and the literal unions are not suffixed (eg. `SetModeMode`)
+                let is_literal_union = matches!(
+                    &param_type_annotation.type_annotation,
+                    TSType::TSUnionType(union_type) if self.is_literal_union(union_type)
+                );
+                let type_lit_name = if type_lit_name.ends_with("Options") || is_literal_union {
+                    type_lit_name
+                } else {
+                    format!("{type_lit_name}Options")
+                };
+                let param_type_annotation = self.with_type_lit_name(type_lit_name, |this| {
+                    match &param_type_annotation.type_annotation {
+                        ts_type if this.is_uint8_array(ts_type) => Ok(TypeAnnotation::ArrayBuffer),
+                        ts_type if this.is_abort_signal(ts_type) => Ok(TypeAnnotation::AbortSignal),
+                        TSType::TSFunctionType(func_type) => this.try_into_callback(func_type),
+                        ts_type => this.try_into_type_annotation(ts_type),
+                    }
+                });
+
+                // Optional parameters are allowed only at the end of the parameter list
+                let optional = param.pattern.optional;
+                if optional && matches!(param_type_annotation, Ok(TypeAnnotation::Callback(..))) {
+                    return Err(error(INVALID_OPTIONAL_CALLBACK, param.span));
+                }
+                if has_optional && !optional {
+                    return Err(error(INVALID_REQUIRED_PARAM, param.span));
+                }
+                has_optional |= optional;
+
+                match param_type_annotation {
                     Ok(type_annotation) => Ok(Param {
                         name: param_name.to_string(),
-                        type_annotation,
+                        // The omitted `AbortSignal` is never aborted (the token is still cancelled by invalidation)
+                        type_annotation: if optional && !type_annotation.is_abort_signal() {
+                            type_annotation.into_nullable()
+                        } else {
+                            type_annotation
+                        },
+                        optional,
+                        doc: self
+                            .doc_at(param.span.start)
+                            .or_else(|| param_docs.remove(param_name.as_str())),
                     }),
                     Err(e) => Err(error(&e.to_string(), param.span)),
                 }
@@ -418,14 +933,84 @@ impl<'a> NativeModuleAnalyzer<'a> {
             .as_ref()
             .ok_or_else(|| error(INVALID_SPEC, sig.span))?;
 
-        match self.try_into_type_annotation(&ret_type.type_annotation) {
-            Ok(type_annotation) => Ok(Method {
-                name: method_name,
-                params,
-                ret_type: type_annotation,
-            }),
-            Err(e) => Err(error(&e.to_string(), sig.span)),
+        // `AsyncIterable<T>` is accepted as a return type only
+        let ret_type = match self.as_async_iterable(&ret_type.type_annotation) {
+            Some(item_type) => {
+                if params.iter().any(|p| p.name == RESERVED_ARG_NAME_STREAM) {
+                    return Err(error(INVALID_RESERVED_ARG_NAME_STREAM, sig.span));
+                }
+
+                let type_lit_name = format!("{}Item", pascal_case(&method_name));
+                self.with_type_lit_name(type_lit_name, |this| {
+                    match this.try_into_type_annotation(item_type)? {
+                        TypeAnnotation::Void | TypeAnnotation::Promise(..) => {
+                            anyhow::bail!(INVALID_STREAM)
+                        }
+                        item_type => Ok(TypeAnnotation::Stream(Box::new(item_type))),
+                    }
+                })
+            }
+            None => {
+                let type_lit_name = format!("{}Result", pascal_case(&method_name));
+                self.with_type_lit_name(type_lit_name, |this| {
+                    this.try_into_type_annotation(&ret_type.type_annotation)
+                })
+            }
+        };
+
+        // Promise methods only can be cancelled by the `AbortSignal`
+        let abort_signals = params
+            .iter()
+            .filter(|param| param.type_annotation.is_abort_signal())
+            .count();
+        if abort_signals > 1
+            || (abort_signals == 1 && !matches!(ret_type, Ok(TypeAnnotation::Promise(..))))
+        {
+            return Err(error(INVALID_ABORT_SIGNAL, sig.span));
+        }
+
+        let ret_type = ret_type.map_err(|e| error(&e.to_string(), sig.span))?;
+        let is_async = matches!(
+            ret_type,
+            TypeAnnotation::Promise(..) | TypeAnnotation::Stream(..)
+        );
+
+        // Sync methods always run on the JS thread, and streams cannot be driven by the JS thread
+        match (&thread, &ret_type) {
+            (Some(..), _) if !is_async => return Err(error(INVALID_THREAD_SYNC, sig.span)),
+            (Some(ThreadAffinity::Js), TypeAnnotation::Stream(..)) => {
+                return Err(error(INVALID_THREAD_STREAM, sig.span))
+            }
+            _ => {}
+        }
+
+        // `async fn` methods resolve the promise when the future is completed
+        if async_fn {
+            match &ret_type {
+                // Host objects are checked after the references are resolved
+                TypeAnnotation::Promise(..) => {}
+                _ => return Err(error(INVALID_ASYNC_FN, sig.span)),
+            }
+
+            if thread.is_some() {
+                return Err(error(INVALID_ASYNC_FN_THREAD, sig.span));
+            }
+
+            if params.iter().any(|p| p.name == RESERVED_ARG_NAME_PROMISE) {
+                return Err(error(INVALID_RESERVED_ARG_NAME_PROMISE, sig.span));
+            }
         }
+
+        Ok(Method {
+            name: method_name,
+            params,
+            // Async methods are always fallible (rejected promise or failed stream)
+            throws: has_throws_tag(&doc) && !is_async,
+            thread,
+            async_fn,
+            ret_type,
+            doc,
+        })
     }
 
     fn try_into_signal(&mut self, sig: &TSPropertySignature<'a>) -> Result<Signal, OxcDiagnostic> {
@@ -446,11 +1031,34 @@ impl<'a> NativeModuleAnalyzer<'a> {
                         .get_reference(ident_ref.reference_id())
                         .symbol_id();
 
-                    if sym_id == self.mod_signal_sym_id {
-                        Ok(Signal { name: event_name })
-                    } else {
-                        Err(error(INVALID_SPEC, sig.span))
+                    if sym_id != self.mod_signal_sym_id {
+                        return Err(error(INVALID_SPEC, sig.span));
                     }
+
+                    // `Signal` or `Signal<T>`
+                    let payload_type = match &type_ref.type_arguments {
+                        Some(type_args) if type_args.params.len() == 1 => {
+                            let payload_type = type_args.params.first().unwrap();
+                            let type_lit_name = format!("{}Payload", pascal_case(&event_name));
+                            match self.with_type_lit_name(type_lit_name, |this| {
+                                this.try_into_type_annotation(payload_type)
+                            }) {
+                                Ok(TypeAnnotation::Void) => None,
+                                Ok(TypeAnnotation::Promise(..)) => {
+                                    return Err(error(INVALID_SIGNAL_PAYLOAD, sig.span))
+                                }
+                                Ok(payload_type) => Some(payload_type),
+                                Err(e) => return Err(error(&e.to_string(), sig.span)),
+                            }
+                        }
+                        Some(_) => return Err(error(INVALID_SIGNAL_PAYLOAD, sig.span)),
+                        None => None,
+                    };
+
+                    Ok(Signal {
+                        name: event_name,
+                        payload_type,
+                    })
                 }
                 _ => Err(error(INVALID_SPEC, sig.span)),
             },
@@ -474,21 +1082,50 @@ impl<'a> NativeModuleAnalyzer<'a> {
             TSType::TSBooleanKeyword(..) => Ok(TypeAnnotation::Boolean),
             TSType::TSNumberKeyword(..) => Ok(TypeAnnotation::Number),
             TSType::TSStringKeyword(..) => Ok(TypeAnnotation::String),
+            TSType::TSBigIntKeyword(..) => Ok(TypeAnnotation::Int64),
             TSType::TSArrayType(arr_type) => {
                 let type_annotation = self.try_into_type_annotation(&arr_type.element_type)?;
                 Ok(TypeAnnotation::Array(Box::new(type_annotation)))
             }
             TSType::TSTypeReference(type_ref) => match &type_ref.type_name {
                 TSTypeName::IdentifierReference(ident_ref) => {
-                    if matches!(ident_ref.name.as_str(), RESERVED_TYPE_PROMISE) {
-                        match &type_ref.type_arguments {
+                    match ident_ref.name.as_str() {
+                        RESERVED_TYPE_PROMISE => match &type_ref.type_arguments {
                             Some(type_args) if type_args.params.len() == 1 => {
                                 let resolved_type = type_args.params.first().unwrap();
                                 let resolved_type = self.try_into_type_annotation(resolved_type)?;
                                 return Ok(TypeAnnotation::Promise(Box::new(resolved_type)));
                             }
                             _ => anyhow::bail!("Invalid promise type"),
-                        }
+                        },
+                        RESERVED_TYPE_ASYNC_ITERABLE => anyhow::bail!(INVALID_STREAM),
+                        RESERVED_TYPE_ABORT_SIGNAL => anyhow::bail!(INVALID_ABORT_SIGNAL),
+                        RESERVED_TYPE_RECORD => match &type_ref.type_arguments {
+                            Some(type_args) if type_args.params.len() == 2 => {
+                                if !matches!(type_args.params[0], TSType::TSStringKeyword(..)) {
+                                    anyhow::bail!(INVALID_MAP_TYPE);
+                                }
+
+                                let value_type =
+                                    self.try_into_type_annotation(&type_args.params[1])?;
+                                return Ok(TypeAnnotation::Map(Box::new(value_type)));
+                            }
+                            _ => anyhow::bail!("Invalid record type"),
+                        },
+                        RESERVED_TYPE_ARRAY_BUFFER => return Ok(TypeAnnotation::ArrayBuffer),
+                        RESERVED_TYPE_UINT8_ARRAY => anyhow::bail!(INVALID_UINT8_ARRAY),
+                        _ => {}
+                    }
+
+                    // Numeric types of `craby-modules` (eg. `Int32`)
+                    let sym_id = self
+                        .scoping
+                        .get_reference(ident_ref.reference_id())
+                        .symbol_id();
+                    if let Some(numeric_type) =
+                        sym_id.and_then(|sym_id| self.mod_numeric_sym_ids.get(&sym_id))
+                    {
+                        return Ok(numeric_type.clone());
                     }
 
                     Ok(TypeAnnotation::Ref(RefTypeAnnotation {
@@ -498,8 +1135,20 @@ impl<'a> NativeModuleAnalyzer<'a> {
                 }
                 _ => anyhow::bail!(INVALID_TYPE_REFERENCE),
             },
+            TSType::TSUnionType(union_type) if self.is_literal_union(union_type) => {
+                match self.type_lit_name.clone() {
+                    Some(name) => self.try_into_literal_union_enum(name, union_type),
+                    None => anyhow::bail!(INVALID_LITERAL_UNION_TYPE),
+                }
+            }
             TSType::TSUnionType(union_type) => self.try_into_nullable(union_type),
-            TSType::TSTypeLiteral { .. } => anyhow::bail!(INVALID_TYPE_LITERAL),
+            TSType::TSTypeLiteral(type_lit) => match self.as_index_signature(&type_lit.members) {
+                Some(index_sig) => self.try_into_map(index_sig),
+                None => match self.type_lit_name.clone() {
+                    Some(name) => self.try_into_type_lit_object(name, type_lit),
+                    None => anyhow::bail!(INVALID_TYPE_LITERAL),
+                },
+            },
             TSType::TSFunctionType { .. } => anyhow::bail!(INVALID_FUNC_PARAM),
             _ => anyhow::bail!(INVALID_SPEC),
         }
@@ -509,6 +1158,20 @@ impl<'a> NativeModuleAnalyzer<'a> {
         &mut self,
         union_type: &TSUnionType<'a>,
     ) -> Result<TypeAnnotation, anyhow::Error> {
+        if self.is_literal_union(union_type) {
+            anyhow::bail!(INVALID_LITERAL_UNION_TYPE);
+        }
+
+        // `{ ... } | null` is a nullable type literal
+        let is_nullable_type_lit = union_type.types.len() == 2
+            && union_type
+                .types
+                .iter()
+                .any(|ts_type| matches!(ts_type, TSType::TSNullKeyword(..)));
+        if self.is_tagged_union(union_type) && !is_nullable_type_lit {
+            anyhow::bail!(INVALID_TAGGED_UNION_TYPE);
+        }
+
         if union_type.types.len() != 2 {
             anyhow::bail!(INVALID_UNION_TYPE);
         }
@@ -527,33 +1190,489 @@ impl<'a> NativeModuleAnalyzer<'a> {
         Ok(TypeAnnotation::Nullable(Box::new(base)))
     }
 
-    /// Check the specification interface extends `NativeModule` interface of 'craby-modules' package.
-    fn is_spec(&self, it: &TSInterfaceDeclaration<'a>) -> bool {
-        it.extends.iter().any(|ex| {
-            if let Some(ref_id) = ex.expression.get_identifier_reference() {
-                // Check if the expression is `NativeModule` of 'craby-modules' package
-                // eg. `import type { NativeModule } from 'craby-modules';`
-                let sym_id = self
-                    .scoping
-                    .get_reference(ref_id.reference_id())
-                    .symbol_id();
-                sym_id == self.mod_type_sym_id
-            } else if let Some(member_expr) = ex.expression.get_member_expr() {
-                // Check if the expression is `Namespace.NativeModule` of 'craby-modules' package
-                // eg. `import * as Namespace from 'craby-modules'`
-                if let Expression::Identifier(ident) = member_expr.object() {
-                    let sym_id = self.scoping.get_reference(ident.reference_id()).symbol_id();
-                    member_expr.static_property_name() == Some(NATIVE_MODULE_INTERFACE)
-                        && self
-                            .mod_ns_sym_id
-                            .zip(sym_id)
-                            .is_some_and(|(id, s)| id == s)
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
+    /// Returns the JSDoc comment attached to the token at `start` (or its `export` keyword).
+    fn doc_at(&self, start: u32) -> Option<String> {
+        self.jsdocs.get(&start).cloned().or_else(|| {
+            self.export_starts
+                .get(&start)
+                .and_then(|export_start| self.jsdocs.get(export_start).cloned())
+        })
+    }
+
+    /// Runs `f` with the name for the type literals in the signature (eg. `MyMethodArgOptions`).
+    fn with_type_lit_name<T>(&mut self, name: String, f: impl FnOnce(&mut Self) -> T) -> T {
+        let prev = self.type_lit_name.replace(name);
+        let res = f(self);
+        self.type_lit_name = prev;
+        res
+    }
+
+    /// Converts the type literal in the signature into the object type.
+    ///
+    /// The nested type literals are named after the property (eg. `MyMethodArgOptionsRetry`)
+    /// and the type literals with the same shape share the first collected name.
+    fn try_into_type_lit_object(
+        &mut self,
+        name: String,
+        type_lit: &TSTypeLiteral<'a>,
+    ) -> Result<TypeAnnotation, anyhow::Error> {
+        let mut props = vec![];
+        for member in &type_lit.members {
+            match member {
+                TSSignature::TSPropertySignature(prop_sig) => {
+                    let prop_name = self.try_into_prop_name(&prop_sig.key)?;
+                    let type_lit_name = format!("{name}{}", pascal_case(&prop_name));
+                    let prop = self
+                        .with_type_lit_name(type_lit_name, |this| this.try_into_prop(prop_sig))
+                        .map_err(|e| anyhow::anyhow!(e.message.to_string()))?;
+                    props.push(prop);
+                }
+                _ => anyhow::bail!(INVALID_SPEC),
+            }
+        }
+
+        let shape_id = TypeAnnotation::Object(ObjectTypeAnnotation {
+            name: String::new(),
+            props: props.clone(),
+            doc: None,
+        })
+        .to_id();
+
+        Ok(TypeAnnotation::Object(ObjectTypeAnnotation {
+            name: self.as_type_lit_name(name, shape_id),
+            props,
+            doc: None,
+        }))
+    }
+
+    /// Converts the literal union type in the signature (eg. `mode: 'fast' | 'safe'`) into the enum type.
+    ///
+    /// The literal unions are named and shared like the type literals (eg. `SetModeMode`).
+    fn try_into_literal_union_enum(
+        &mut self,
+        name: String,
+        union_type: &TSUnionType<'a>,
+    ) -> Result<TypeAnnotation, anyhow::Error> {
+        let rename = |this: &mut Self, mut enum_type: EnumTypeAnnotation| {
+            let name = std::mem::take(&mut enum_type.name);
+            let shape_id = TypeAnnotation::Enum(enum_type.clone()).to_id();
+            enum_type.name = this.as_type_lit_name(name, shape_id);
+            TypeAnnotation::Enum(enum_type)
+        };
+
+        Ok(match self.try_into_literal_enum(name, None, union_type)? {
+            TypeAnnotation::Enum(enum_type) => rename(self, enum_type),
+            TypeAnnotation::Nullable(base) => match *base {
+                TypeAnnotation::Enum(enum_type) => rename(self, enum_type).into_nullable(),
+                base => base.into_nullable(),
+            },
+            type_annotation => type_annotation,
+        })
+    }
+
+    /// Returns the name of the type literal shape, numbering the different shapes with the same name (eg. `FooBarOptions2`).
+    fn as_type_lit_name(&mut self, name: String, shape_id: u64) -> String {
+        match self.type_lits.get(&shape_id) {
+            Some(name) => name.clone(),
+            None => {
+                let name = (1..)
+                    .map(|n| match n {
+                        1 => name.clone(),
+                        n => format!("{name}{n}"),
+                    })
+                    .find(|name| !self.type_lits.values().any(|n| n == name))
+                    .unwrap();
+                self.type_lits.insert(shape_id, name.clone());
+                name
+            }
+        }
+    }
+
+    /// Converts the literal union type (eg. `'fast' | 'safe'`) into the enum type.
+    ///
+    /// If the union contains `null`, the enum type is wrapped with `Nullable`.
+    fn try_into_literal_enum(
+        &mut self,
+        name: String,
+        doc: Option<String>,
+        union_type: &TSUnionType<'a>,
+    ) -> Result<TypeAnnotation, anyhow::Error> {
+        let mut members: Vec<EnumMember> = vec![];
+        let mut nullable = false;
+
+        for ts_type in &union_type.types {
+            let member = match ts_type {
+                TSType::TSNullKeyword(..) => {
+                    nullable = true;
+                    continue;
+                }
+                TSType::TSLiteralType(lit_type) => {
+                    self.try_into_literal_member(&lit_type.literal)?
+                }
+                _ => anyhow::bail!(INVALID_UNION_TYPE),
+            };
+
+            if let Some(first) = members.first() {
+                if first.value.is_string() != member.value.is_string() {
+                    anyhow::bail!(INVALID_MIXED_ENUM_MEMBER);
+                }
+            }
+
+            if members.iter().any(|m| m.value == member.value) {
+                continue;
+            }
+
+            if members.iter().any(|m| m.name == member.name) {
+                anyhow::bail!("Duplicate enum member name: {}", member.name);
+            }
+
+            members.push(member);
+        }
+
+        if members.is_empty() {
+            anyhow::bail!(INVALID_UNION_TYPE);
+        }
+
+        let (doc, repr) = split_doc_tag(doc, "@repr");
+        let repr = try_into_enum_repr(repr, &members)?;
+        let enum_type = TypeAnnotation::Enum(EnumTypeAnnotation {
+            name,
+            members,
+            repr,
+            error_code: false,
+            doc,
+        });
+
+        Ok(if nullable {
+            enum_type.into_nullable()
+        } else {
+            enum_type
+        })
+    }
+
+    /// Converts the discriminated union type (eg. `{ kind: 'ok', value: T } | { kind: 'err' }`) into the union type.
+    ///
+    /// Each member must be an object type literal with the same literal discriminant property.
+    /// Other properties are collected into the variant data struct (eg. `OutcomeOk`).
+    fn try_into_tagged_union(
+        &mut self,
+        name: String,
+        union_type: &TSUnionType<'a>,
+    ) -> Result<TypeAnnotation, anyhow::Error> {
+        let mut tag: Option<String> = None;
+        let mut variants: Vec<UnionVariant> = vec![];
+        let mut nullable = false;
+
+        for ts_type in &union_type.types {
+            let type_lit = match ts_type {
+                TSType::TSNullKeyword(..) => {
+                    nullable = true;
+                    continue;
+                }
+                TSType::TSTypeLiteral(type_lit) => type_lit,
+                _ => anyhow::bail!(INVALID_TAGGED_UNION),
+            };
+
+            let mut variant_tag = None;
+            let mut props = vec![];
+
+            for member in &type_lit.members {
+                let prop_sig = match member {
+                    TSSignature::TSPropertySignature(prop_sig) => prop_sig,
+                    _ => anyhow::bail!(INVALID_TAGGED_UNION),
+                };
+
+                let literal = match &prop_sig.type_annotation {
+                    Some(type_annotation) => match &type_annotation.type_annotation {
+                        TSType::TSLiteralType(lit_type) => Some(&lit_type.literal),
+                        _ => None,
+                    },
+                    None => None,
+                };
+
+                match literal {
+                    Some(literal) if variant_tag.is_none() && !prop_sig.optional => {
+                        let prop_name = self.try_into_prop_name(&prop_sig.key)?;
+                        variant_tag = Some((prop_name, self.try_into_literal_member(literal)?));
+                    }
+                    _ => {
+                        let prop = self
+                            .try_into_prop(prop_sig)
+                            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+                        props.push(prop);
+                    }
+                }
+            }
+
+            let (prop_name, member) = variant_tag.ok_or(anyhow::anyhow!(INVALID_TAGGED_UNION))?;
+
+            match &tag {
+                Some(tag) if *tag != prop_name => {
+                    anyhow::bail!("Discriminant property must be the same in all union members (`{tag}`, `{prop_name}`)")
+                }
+                Some(..) => {}
+                None => tag = Some(prop_name),
+            }
+
+            if let Some(first) = variants.first() {
+                if std::mem::discriminant(&first.value) != std::mem::discriminant(&member.value) {
+                    anyhow::bail!(INVALID_MIXED_ENUM_MEMBER);
+                }
+            }
+
+            if variants
+                .iter()
+                .any(|v| v.value == member.value || v.name == member.name)
+            {
+                anyhow::bail!("Duplicate discriminant value: {:?}", member.value);
+            }
+
+            let data = if props.is_empty() {
+                None
+            } else {
+                Some(ObjectTypeAnnotation {
+                    name: format!("{name}{}", member.name),
+                    props,
+                    doc: None,
+                })
+            };
+
+            variants.push(UnionVariant {
+                name: member.name,
+                value: member.value,
+                data,
+            });
+        }
+
+        let tag = tag.ok_or(anyhow::anyhow!(INVALID_TAGGED_UNION))?;
+        let union_type = TypeAnnotation::Union(UnionTypeAnnotation {
+            name,
+            tag,
+            variants,
+        });
+
+        Ok(if nullable {
+            union_type.into_nullable()
+        } else {
+            union_type
+        })
+    }
+
+    /// Converts the literal type (eg. `'read-only'`, `1`) into the enum member.
+    ///
+    /// Member names are derived from the literal values (eg. `'read-only'` → `ReadOnly`, `1` → `Value1`, `-1` → `ValueNeg1`, `0.5` → `Value0_5`).
+    fn try_into_literal_member(
+        &self,
+        literal: &TSLiteral<'a>,
+    ) -> Result<EnumMember, anyhow::Error> {
+        let value = match literal {
+            TSLiteral::StringLiteral(str_lit) => EnumMemberValue::String(str_lit.value.to_string()),
+            TSLiteral::NumericLiteral(num_lit) => EnumMemberValue::from_f64(num_lit.value)?,
+            TSLiteral::UnaryExpression(unary_expr)
+                if unary_expr.operator == UnaryOperator::UnaryNegation =>
+            {
+                match &unary_expr.argument {
+                    Expression::NumericLiteral(num_lit) => {
+                        EnumMemberValue::from_f64(-num_lit.value)?
+                    }
+                    _ => anyhow::bail!(INVALID_LITERAL_UNION_MEMBER),
+                }
+            }
+            _ => anyhow::bail!(INVALID_LITERAL_UNION_MEMBER),
+        };
+
+        let name = match &value {
+            EnumMemberValue::String(val) => pascal_case(val),
+            num_val => format!(
+                "Value{}",
+                num_val.to_string().replace('-', "Neg").replace('.', "_")
+            ),
+        };
+
+        if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            anyhow::bail!(
+                "Cannot derive enum member name from the literal: {:?}",
+                value
+            );
+        }
+
+        Ok(EnumMember {
+            name,
+            value,
+            doc: None,
+        })
+    }
+
+    /// Converts the index signature (eg. `{ [key: string]: T }`) into the map type.
+    fn try_into_map(
+        &mut self,
+        index_sig: &TSIndexSignature<'a>,
+    ) -> Result<TypeAnnotation, anyhow::Error> {
+        match index_sig.parameters.as_slice() {
+            [key]
+                if matches!(
+                    key.type_annotation.type_annotation,
+                    TSType::TSStringKeyword(..)
+                ) =>
+            {
+                let value_type =
+                    self.try_into_type_annotation(&index_sig.type_annotation.type_annotation)?;
+                Ok(TypeAnnotation::Map(Box::new(value_type)))
+            }
+            _ => anyhow::bail!(INVALID_MAP_TYPE),
+        }
+    }
+
+    /// Converts the function type (eg. `(arg: T) => void`) into the callback type.
+    fn try_into_callback(
+        &mut self,
+        func_type: &TSFunctionType<'a>,
+    ) -> Result<TypeAnnotation, anyhow::Error> {
+        if func_type.type_parameters.is_some()
+            || func_type.this_param.is_some()
+            || func_type.params.rest.is_some()
+            || !matches!(
+                func_type.return_type.type_annotation,
+                TSType::TSVoidKeyword(..)
+            )
+        {
+            anyhow::bail!(INVALID_CALLBACK);
+        }
+
+        let arg_type = match func_type.params.items.as_slice() {
+            [] => TypeAnnotation::Void,
+            [param] => {
+                let type_annotation = param
+                    .pattern
+                    .type_annotation
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!(INVALID_CALLBACK))?;
+
+                match self.try_into_type_annotation(&type_annotation.type_annotation)? {
+                    TypeAnnotation::Void | TypeAnnotation::Promise(..) => {
+                        anyhow::bail!(INVALID_CALLBACK)
+                    }
+                    arg_type if param.pattern.optional => arg_type.into_nullable(),
+                    arg_type => arg_type,
+                }
+            }
+            _ => anyhow::bail!(INVALID_CALLBACK),
+        };
+
+        Ok(TypeAnnotation::Callback(Box::new(arg_type)))
+    }
+
+    /// Returns the index signature if the members contain only one index signature.
+    fn as_index_signature<'b>(
+        &self,
+        members: &'b [TSSignature<'a>],
+    ) -> Option<&'b TSIndexSignature<'a>> {
+        match members {
+            [TSSignature::TSIndexSignature(index_sig)] => Some(index_sig),
+            _ => None,
+        }
+    }
+
+    /// Check the union type contains any literal type (eg. `'fast' | 'safe'`).
+    fn is_literal_union(&self, union_type: &TSUnionType<'a>) -> bool {
+        union_type
+            .types
+            .iter()
+            .any(|ts_type| matches!(ts_type, TSType::TSLiteralType(..)))
+    }
+
+    /// Check the union type contains any object type literal (eg. `{ kind: 'ok' } | { kind: 'err' }`).
+    fn is_tagged_union(&self, union_type: &TSUnionType<'a>) -> bool {
+        union_type.types.iter().any(|ts_type| match ts_type {
+            TSType::TSTypeLiteral(type_lit) => self.as_index_signature(&type_lit.members).is_none(),
+            _ => false,
+        })
+    }
+
+    /// Check the type is `Uint8Array` type reference.
+    fn is_uint8_array(&self, ts_type: &TSType<'a>) -> bool {
+        match ts_type {
+            TSType::TSTypeReference(type_ref) => match &type_ref.type_name {
+                TSTypeName::IdentifierReference(ident_ref) => {
+                    ident_ref.name.as_str() == RESERVED_TYPE_UINT8_ARRAY
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn is_abort_signal(&self, ts_type: &TSType<'a>) -> bool {
+        match ts_type {
+            TSType::TSTypeReference(type_ref) => match &type_ref.type_name {
+                TSTypeName::IdentifierReference(ident_ref) => {
+                    ident_ref.name.as_str() == RESERVED_TYPE_ABORT_SIGNAL
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns the item type of `AsyncIterable<T>`.
+    fn as_async_iterable<'b>(&self, ts_type: &'b TSType<'a>) -> Option<&'b TSType<'a>> {
+        match ts_type {
+            TSType::TSTypeReference(type_ref) => match &type_ref.type_name {
+                TSTypeName::IdentifierReference(ident_ref)
+                    if ident_ref.name.as_str() == RESERVED_TYPE_ASYNC_ITERABLE =>
+                {
+                    match &type_ref.type_arguments {
+                        Some(type_args) if type_args.params.len() == 1 => type_args.params.first(),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Check the specification interface extends `NativeModule` interface of 'craby-modules' package.
+    fn is_spec(&self, it: &TSInterfaceDeclaration<'a>) -> bool {
+        self.extends_mod_interface(it, self.mod_type_sym_id, NATIVE_MODULE_INTERFACE)
+    }
+
+    /// Check the interface extends `HostObject` interface of 'craby-modules' package.
+    fn is_host_object(&self, it: &TSInterfaceDeclaration<'a>) -> bool {
+        self.extends_mod_interface(it, self.mod_host_object_sym_id, HOST_OBJECT_INTERFACE)
+    }
+
+    fn extends_mod_interface(
+        &self,
+        it: &TSInterfaceDeclaration<'a>,
+        mod_sym_id: Option<SymbolId>,
+        name: &str,
+    ) -> bool {
+        it.extends.iter().any(|ex| {
+            if let Some(ref_id) = ex.expression.get_identifier_reference() {
+                // Check if the expression is `NativeModule` of 'craby-modules' package
+                // eg. `import type { NativeModule } from 'craby-modules';`
+                let sym_id = self
+                    .scoping
+                    .get_reference(ref_id.reference_id())
+                    .symbol_id();
+                sym_id.is_some() && sym_id == mod_sym_id
+            } else if let Some(member_expr) = ex.expression.get_member_expr() {
+                // Check if the expression is `Namespace.NativeModule` of 'craby-modules' package
+                // eg. `import * as Namespace from 'craby-modules'`
+                if let Expression::Identifier(ident) = member_expr.object() {
+                    let sym_id = self.scoping.get_reference(ident.reference_id()).symbol_id();
+                    member_expr.static_property_name() == Some(name)
+                        && self
+                            .mod_ns_sym_id
+                            .zip(sym_id)
+                            .is_some_and(|(id, s)| id == s)
+                } else {
+                    false
+                }
+            } else {
+                false
+            }
         })
     }
 
@@ -631,12 +1750,32 @@ impl<'a> NativeModuleAnalyzer<'a> {
             enum_type @ TypeAnnotation::Enum(..) => {
                 enums.insert(enum_type.clone());
             }
+            // Methods of the host object are collected in `try_into_schema`
+            handle_type @ TypeAnnotation::Handle(..) => {
+                types.insert(handle_type.clone());
+            }
+            TypeAnnotation::Union(union_type) => {
+                for data in union_type.variants.iter().filter_map(|v| v.data.as_ref()) {
+                    NativeModuleAnalyzer::collect_types(
+                        &TypeAnnotation::Object(data.clone()),
+                        _scoping,
+                        _decls,
+                        types,
+                        enums,
+                    );
+                }
+            }
             TypeAnnotation::Nullable(base_type) => {
                 NativeModuleAnalyzer::collect_types(base_type, _scoping, _decls, types, enums);
             }
-            TypeAnnotation::Promise(resolved_type) => {
+            TypeAnnotation::Promise(resolved_type) | TypeAnnotation::Stream(resolved_type) => {
                 NativeModuleAnalyzer::collect_types(resolved_type, _scoping, _decls, types, enums);
             }
+            TypeAnnotation::Array(element_type)
+            | TypeAnnotation::Map(element_type)
+            | TypeAnnotation::Callback(element_type) => {
+                NativeModuleAnalyzer::collect_types(element_type, _scoping, _decls, types, enums);
+            }
             _ => {}
         }
     }
@@ -670,10 +1809,28 @@ impl<'a> NativeModuleAnalyzer<'a> {
                     NativeModuleAnalyzer::resolve_refs(&mut prop.type_annotation, scoping, decls);
                 }
             }
+            TypeAnnotation::Union(union_type) => {
+                for data in union_type
+                    .variants
+                    .iter_mut()
+                    .filter_map(|v| v.data.as_mut())
+                {
+                    for prop in &mut data.props {
+                        NativeModuleAnalyzer::resolve_refs(
+                            &mut prop.type_annotation,
+                            scoping,
+                            decls,
+                        );
+                    }
+                }
+            }
             TypeAnnotation::Nullable(base_type) => {
                 NativeModuleAnalyzer::resolve_refs(base_type, scoping, decls);
             }
-            TypeAnnotation::Promise(t) => {
+            TypeAnnotation::Promise(t) | TypeAnnotation::Stream(t) => {
+                NativeModuleAnalyzer::resolve_refs(&mut *t, scoping, decls);
+            }
+            TypeAnnotation::Array(t) | TypeAnnotation::Map(t) | TypeAnnotation::Callback(t) => {
                 NativeModuleAnalyzer::resolve_refs(&mut *t, scoping, decls);
             }
             _ => {}
@@ -681,7 +1838,15 @@ impl<'a> NativeModuleAnalyzer<'a> {
     }
 
     fn try_assert_reserved_type(&self, name: &Atom<'a>) -> Result<(), anyhow::Error> {
-        if matches!(name.as_str(), RESERVED_TYPE_PROMISE) {
+        if matches!(
+            name.as_str(),
+            RESERVED_TYPE_PROMISE
+                | RESERVED_TYPE_ASYNC_ITERABLE
+                | RESERVED_TYPE_ABORT_SIGNAL
+                | RESERVED_TYPE_ARRAY_BUFFER
+                | RESERVED_TYPE_UINT8_ARRAY
+                | RESERVED_TYPE_RECORD
+        ) {
             anyhow::bail!("Cannot use reserved type: {}", name.as_str());
         }
 
@@ -692,9 +1857,52 @@ impl<'a> NativeModuleAnalyzer<'a> {
         Ok(())
     }
 
-    fn try_into_schema(self) -> Result<Vec<Schema>, anyhow::Error> {
+    /// Returns the types declared in the source code.
+    ///
+    /// The references are resolved in advance because the types are used by the other source files.
+    fn try_into_types(self) -> Result<ImportedTypes, anyhow::Error> {
+        let mut types = ImportedTypes::default();
+
+        for (sym_id, type_annotation) in &self.decls {
+            if self.imported_sym_ids.contains(sym_id) {
+                continue;
+            }
+
+            let mut type_annotation = type_annotation.clone();
+            NativeModuleAnalyzer::resolve_refs(&mut type_annotation, self.scoping, &self.decls);
+            types.insert(
+                self.scoping.symbol_name(*sym_id).to_string(),
+                type_annotation,
+            );
+        }
+
+        Ok(types)
+    }
+
+    fn try_into_schema(mut self) -> Result<Vec<Schema>, anyhow::Error> {
         let mut schemas = Vec::with_capacity(self.specs.len());
 
+        // String enums referenced by the `@throws {MyError}` tags are the error codes
+        let error_code_sym_ids = self
+            .specs
+            .values()
+            .flat_map(|spec| &spec.methods)
+            .flat_map(|method| throws_types(&method.doc))
+            .filter_map(|name| self.scoping.get_root_binding(name))
+            .collect::<FxHashSet<_>>();
+        for sym_id in &error_code_sym_ids {
+            match self.decls.get_mut(sym_id) {
+                Some(TypeAnnotation::Enum(enum_type))
+                    if enum_type.members.iter().all(|m| m.value.is_string()) =>
+                {
+                    enum_type.error_code = true;
+                }
+                Some(TypeAnnotation::Enum(..)) => anyhow::bail!(INVALID_ERROR_CODE),
+                // Other types (eg. `@throws {Error}`) are only documented
+                _ => {}
+            }
+        }
+
         for (id, mut spec) in self.specs {
             let mut types = FxHashSet::default();
             let mut enums = FxHashSet::default();
@@ -738,34 +1946,191 @@ impl<'a> NativeModuleAnalyzer<'a> {
                         &mut enums,
                     );
 
+                    // Error codes are generated even if they are not used in the signatures
+                    for sym_id in throws_types(&method.doc)
+                        .into_iter()
+                        .filter_map(|name| self.scoping.get_root_binding(name))
+                        .filter(|sym_id| error_code_sym_ids.contains(sym_id))
+                    {
+                        enums.insert(self.decls[&sym_id].clone());
+                    }
+
                     method
                 })
                 .collect::<Vec<Method>>();
 
-            let mut aliases = types.into_iter().collect::<Vec<_>>();
-            let mut enums = enums.into_iter().collect::<Vec<_>>();
-
-            // Sort collected metadata to ensure deterministic output (for hash)
-            aliases.sort_by_key(|v| v.as_object().unwrap().name.to_lowercase());
-            enums.sort_by_key(|v| v.as_enum().unwrap().name.to_lowercase());
-            methods.sort_by_key(|v| v.name.to_lowercase());
-            spec.signals.sort_by_key(|v| v.name.to_lowercase());
+            for signal in &mut spec.signals {
+                if let Some(payload_type) = &mut signal.payload_type {
+                    NativeModuleAnalyzer::resolve_refs(payload_type, self.scoping, &self.decls);
+                    NativeModuleAnalyzer::collect_types(
+                        payload_type,
+                        self.scoping,
+                        &self.decls,
+                        &mut types,
+                        &mut enums,
+                    );
+                }
+            }
 
-            schemas.push(Schema {
-                module_name: module_name.to_owned(),
-                aliases,
-                enums,
-                methods,
-                signals: spec.signals,
-            });
-        }
+            let constants = match spec.constants {
+                Some(mut constants) => {
+                    NativeModuleAnalyzer::resolve_refs(&mut constants, self.scoping, &self.decls);
 
-        Ok(schemas)
-    }
-}
+                    match &mut constants {
+                        TypeAnnotation::Object(obj) if obj.name.is_empty() => {
+                            obj.name = format!("{}Constants", pascal_case(module_name));
+                        }
+                        TypeAnnotation::Object(..) => {}
+                        _ => anyhow::bail!(INVALID_CONSTANTS),
+                    }
+
+                    NativeModuleAnalyzer::collect_types(
+                        &constants,
+                        self.scoping,
+                        &self.decls,
+                        &mut types,
+                        &mut enums,
+                    );
+
+                    Some(constants)
+                }
+                None => None,
+            };
+
+            // Host object handles are allowed only as the method return type
+            let payload_types = spec
+                .signals
+                .iter()
+                .filter_map(|signal| signal.payload_type.as_ref())
+                .chain(constants.as_ref());
+            if methods.iter().any(|method| !is_valid_handle_usage(method))
+                || payload_types
+                    .into_iter()
+                    .any(TypeAnnotation::contains_handle)
+            {
+                anyhow::bail!(INVALID_HANDLE_USAGE);
+            }
+
+            // `async fn` methods cannot create the host objects (requires the `CallInvoker`)
+            if methods
+                .iter()
+                .any(|method| method.async_fn && method.ret_type.contains_handle())
+            {
+                anyhow::bail!(INVALID_ASYNC_FN);
+            }
+
+            // Collect the host objects returned by the methods (including the ones returned by the other host objects)
+            let mut handles: Vec<Handle> = vec![];
+            loop {
+                let pending = types
+                    .iter()
+                    .filter_map(|type_annotation| match type_annotation {
+                        TypeAnnotation::Handle(handle_type)
+                            if !handles.iter().any(|h| h.name == handle_type.name) =>
+                        {
+                            Some(handle_type.name.clone())
+                        }
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+
+                if pending.is_empty() {
+                    break;
+                }
+
+                for name in pending {
+                    let mut handle = self
+                        .handles
+                        .get(&name)
+                        .cloned()
+                        .ok_or_else(|| anyhow::anyhow!("{INVALID_HANDLE_NOT_FOUND}: {name}"))?;
+
+                    for method in &mut handle.methods {
+                        for param in &mut method.params {
+                            NativeModuleAnalyzer::resolve_refs(
+                                &mut param.type_annotation,
+                                self.scoping,
+                                &self.decls,
+                            );
+                        }
+                        NativeModuleAnalyzer::resolve_refs(
+                            &mut method.ret_type,
+                            self.scoping,
+                            &self.decls,
+                        );
+
+                        if !is_valid_handle_usage(method) {
+                            anyhow::bail!(INVALID_HANDLE_USAGE);
+                        }
+
+                        for type_annotation in method
+                            .params
+                            .iter()
+                            .map(|param| &param.type_annotation)
+                            .chain(std::iter::once(&method.ret_type))
+                        {
+                            NativeModuleAnalyzer::collect_types(
+                                type_annotation,
+                                self.scoping,
+                                &self.decls,
+                                &mut types,
+                                &mut enums,
+                            );
+                        }
+                    }
+
+                    handle.methods.sort_by_key(|v| v.name.to_lowercase());
+                    handles.push(handle);
+                }
+            }
+
+            let mut aliases = types
+                .into_iter()
+                .filter(|type_annotation| type_annotation.as_object().is_some())
+                .collect::<Vec<_>>();
+            let mut enums = enums.into_iter().collect::<Vec<_>>();
+
+            // Type literals are named automatically, so they may conflict with the declared types
+            let mut type_names = FxHashSet::default();
+            for name in aliases
+                .iter()
+                .map(|v| &v.as_object().unwrap().name)
+                .chain(enums.iter().map(|v| &v.as_enum().unwrap().name))
+                .chain(handles.iter().map(|v| &v.name))
+            {
+                if !type_names.insert(name) {
+                    anyhow::bail!("Duplicate type name: {name}");
+                }
+            }
+
+            // Sort collected metadata to ensure deterministic output (for hash)
+            aliases.sort_by_key(|v| v.as_object().unwrap().name.to_lowercase());
+            enums.sort_by_key(|v| v.as_enum().unwrap().name.to_lowercase());
+            methods.sort_by_key(|v| v.name.to_lowercase());
+            spec.signals.sort_by_key(|v| v.name.to_lowercase());
+            handles.sort_by_key(|v| v.name.to_lowercase());
+
+            schemas.push(Schema {
+                module_name: module_name.to_owned(),
+                aliases,
+                enums,
+                methods,
+                signals: spec.signals,
+                handles,
+                constants,
+            });
+        }
+
+        Ok(schemas)
+    }
+}
+
+impl<'a> Visit<'a> for NativeModuleAnalyzer<'a> {
+    fn visit_import_declaration(&mut self, it: &ImportDeclaration<'a>) {
+        if is_relative_import(it.source.value.as_str()) {
+            return self.collect_imported_types(it);
+        }
 
-impl<'a> Visit<'a> for NativeModuleAnalyzer<'a> {
-    fn visit_import_declaration(&mut self, it: &ImportDeclaration<'a>) {
         if it.source.value.as_str() != NATIVE_MODULE_PKG {
             return;
         }
@@ -789,8 +2154,18 @@ impl<'a> Visit<'a> for NativeModuleAnalyzer<'a> {
 
                     match imported_name.as_str() {
                         NATIVE_MODULE_INTERFACE => self.mod_type_sym_id = Some(symbol_id),
+                        HOST_OBJECT_INTERFACE => self.mod_host_object_sym_id = Some(symbol_id),
                         NATIVE_MODULE_REGISTRY => self.mod_reg_sym_id = Some(symbol_id),
                         SIGNAL_TYPE => self.mod_signal_sym_id = Some(symbol_id),
+                        INT32_TYPE | INT64_TYPE | UINT8_TYPE | FLOAT_TYPE => {
+                            let numeric_type = match imported_name.as_str() {
+                                INT32_TYPE => TypeAnnotation::Int32,
+                                INT64_TYPE => TypeAnnotation::Int64,
+                                UINT8_TYPE => TypeAnnotation::UInt8,
+                                _ => TypeAnnotation::Float,
+                            };
+                            self.mod_numeric_sym_ids.insert(symbol_id, numeric_type);
+                        }
                         _ => {}
                     };
                 }
@@ -802,6 +2177,15 @@ impl<'a> Visit<'a> for NativeModuleAnalyzer<'a> {
         }
     }
 
+    fn visit_export_named_declaration(&mut self, it: &ExportNamedDeclaration<'a>) {
+        // JSDoc comments of the exported declarations are attached to the `export` keyword
+        if let Some(decl) = &it.declaration {
+            self.export_starts.insert(decl.span().start, it.span.start);
+        }
+
+        walk::walk_export_named_declaration(self, it);
+    }
+
     fn visit_ts_interface_declaration(&mut self, it: &TSInterfaceDeclaration<'a>) {
         if it.declare {
             return;
@@ -810,6 +2194,9 @@ impl<'a> Visit<'a> for NativeModuleAnalyzer<'a> {
         if self.is_spec(it) {
             // Collect module spec
             self.collect_spec(it);
+        } else if self.is_host_object(it) {
+            // Collect host object interface
+            self.collect_handle(it);
         } else {
             // Collect user defined type (interface)
             self.collect_interface_type(it);
@@ -840,7 +2227,191 @@ impl<'a> Visit<'a> for NativeModuleAnalyzer<'a> {
     }
 }
 
-pub fn try_parse_schema(src: &str) -> Result<Vec<Schema>, ParseError> {
+/// Resolves the types of the relative import source (eg. `./types`).
+pub type ImportResolver<'r> = dyn FnMut(&str) -> Result<ImportedTypes, anyhow::Error> + 'r;
+
+/// Strips the leading `*` of the JSDoc comment lines.
+fn clean_jsdoc(content: &str) -> String {
+    let lines = content
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            let line = line.strip_prefix('*').unwrap_or(line);
+            let line = line.strip_prefix(' ').unwrap_or(line);
+            line.trim_end()
+        })
+        .collect::<Vec<_>>();
+
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+/// Splits the tag (eg. `@repr i32`, `@thread serial`) from the JSDoc comment.
+fn split_doc_tag(doc: Option<String>, tag_name: &str) -> (Option<String>, Option<String>) {
+    let doc = match doc {
+        Some(doc) => doc,
+        None => return (None, None),
+    };
+
+    let mut value = None;
+    let mut lines = vec![];
+
+    for line in doc.lines() {
+        match line.strip_prefix(tag_name) {
+            Some(tag) if tag.is_empty() || tag.starts_with(char::is_whitespace) => {
+                value = Some(tag.trim().to_string());
+            }
+            _ => lines.push(line),
+        }
+    }
+
+    let doc = lines.join("\n").trim_matches('\n').to_string();
+    ((!doc.is_empty()).then_some(doc), value)
+}
+
+/// Parses the `@thread` tag value (`js`, `pool`, `serial` or a thread name like `io`).
+fn parse_thread_affinity(value: &str) -> Option<ThreadAffinity> {
+    match value {
+        "js" => Some(ThreadAffinity::Js),
+        "pool" => Some(ThreadAffinity::Pool),
+        "serial" => Some(ThreadAffinity::Serial),
+        name if name.starts_with(|c: char| c.is_ascii_lowercase())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+        {
+            Some(ThreadAffinity::Named(name.to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Validates the enum repr with the member values.
+fn try_into_enum_repr(
+    repr: Option<String>,
+    members: &[EnumMember],
+) -> Result<Option<String>, anyhow::Error> {
+    let repr = match repr {
+        Some(repr) => repr,
+        None => return Ok(None),
+    };
+
+    let (min, max) = match repr.as_str() {
+        "i8" => (i8::MIN as i128, i8::MAX as i128),
+        "i16" => (i16::MIN as i128, i16::MAX as i128),
+        "i32" => (i32::MIN as i128, i32::MAX as i128),
+        "i64" => (i64::MIN as i128, i64::MAX as i128),
+        "u8" => (0, u8::MAX as i128),
+        "u16" => (0, u16::MAX as i128),
+        "u32" => (0, u32::MAX as i128),
+        "u64" => (0, u64::MAX as i128),
+        _ => anyhow::bail!(INVALID_ENUM_REPR),
+    };
+
+    for member in members {
+        match member.value {
+            EnumMemberValue::Number(val) if (min..=max).contains(&(val as i128)) => {}
+            EnumMemberValue::Number(val) => anyhow::bail!(
+                "Enum member value is out of the repr range ({repr}): {} = {val}",
+                member.name
+            ),
+            _ => anyhow::bail!(INVALID_ENUM_REPR_MEMBER),
+        }
+    }
+
+    Ok(Some(repr))
+}
+
+/// Splits the `@param` tags from the method JSDoc comment.
+///
+/// Supports `@param name desc`, `@param name - desc` and `@param {type} name desc`.
+fn split_param_docs(doc: Option<String>) -> (Option<String>, FxHashMap<String, String>) {
+    let doc = match doc {
+        Some(doc) => doc,
+        None => return (None, FxHashMap::default()),
+    };
+
+    let mut param_docs = FxHashMap::default();
+    let mut lines = vec![];
+
+    for line in doc.lines() {
+        let tag = match line.strip_prefix("@param") {
+            Some(tag) if tag.is_empty() || tag.starts_with(char::is_whitespace) => tag.trim(),
+            _ => {
+                lines.push(line);
+                continue;
+            }
+        };
+
+        // Skip the type expression (eg. `{number}`)
+        let tag = match tag.strip_prefix('{') {
+            Some(tag) => tag
+                .split_once('}')
+                .map_or("", |(_, rest)| rest.trim_start()),
+            None => tag,
+        };
+
+        let (name, desc) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        let name = name.trim_matches(|c| c == '[' || c == ']');
+        let desc = desc.trim_start();
+        let desc = desc.strip_prefix("- ").unwrap_or(desc).trim();
+
+        if !name.is_empty() && !desc.is_empty() {
+            param_docs.insert(name.to_string(), desc.to_string());
+        }
+    }
+
+    let doc = lines.join("\n").trim_matches('\n').to_string();
+    ((!doc.is_empty()).then_some(doc), param_docs)
+}
+
+/// Whether the method JSDoc comment has the `@throws` tag (eg. `@throws {Error} If ...`).
+fn has_throws_tag(doc: &Option<String>) -> bool {
+    doc.as_ref().is_some_and(|doc| {
+        doc.lines().any(|line| match line.strip_prefix("@throws") {
+            Some(tag) => tag.is_empty() || tag.starts_with(char::is_whitespace),
+            None => false,
+        })
+    })
+}
+
+/// Types of the `@throws` tags in the method JSDoc comment (eg. `MyError` of `@throws {MyError} If ...`).
+fn throws_types(doc: &Option<String>) -> Vec<&str> {
+    doc.as_deref()
+        .map(|doc| {
+            doc.lines()
+                .filter_map(|line| {
+                    let tag = line.strip_prefix("@throws")?.trim_start();
+                    tag.strip_prefix('{')?.split_once('}')
+                })
+                .map(|(name, _)| name.trim())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether the host object handles are used as the method return type only (eg. `Connection` or `Promise<Connection>`).
+fn is_valid_handle_usage(method: &Method) -> bool {
+    let ret_type = match &method.ret_type {
+        TypeAnnotation::Promise(resolved_type) => resolved_type,
+        ret_type => ret_type,
+    };
+
+    let is_valid_ret =
+        matches!(ret_type, TypeAnnotation::Handle(..)) || !ret_type.contains_handle();
+    is_valid_ret
+        && !method
+            .params
+            .iter()
+            .any(|param| param.type_annotation.contains_handle())
+}
+
+fn is_relative_import(source: &str) -> bool {
+    source.starts_with("./") || source.starts_with("../")
+}
+
+fn try_analyze<T>(
+    src: &str,
+    resolve: &mut ImportResolver,
+    f: impl FnOnce(NativeModuleAnalyzer) -> Result<T, anyhow::Error>,
+) -> Result<T, ParseError> {
     let allocator = Allocator::default();
     let source_type = SourceType::tsx();
     let ret = Parser::new(&allocator, src, source_type).parse();
@@ -860,10 +2431,33 @@ pub fn try_parse_schema(src: &str) -> Result<Vec<Schema>, ParseError> {
         });
     }
 
+    // Resolve the relative imports before the analysis
+    let mut imported_types = FxHashMap::default();
+    for stmt in &program.body {
+        if let Statement::ImportDeclaration(import_decl) = stmt {
+            let source = import_decl.source.value.as_str();
+            if is_relative_import(source) && !imported_types.contains_key(source) {
+                imported_types.insert(source.to_string(), resolve(source)?);
+            }
+        }
+    }
+
+    let jsdocs = program
+        .comments
+        .iter()
+        .filter(|comment| comment.is_jsdoc())
+        .map(|comment| {
+            let content = comment.content_span().source_text(src);
+            (comment.attached_to, clean_jsdoc(content))
+        })
+        .filter(|(_, doc)| !doc.is_empty())
+        .collect();
+
     let scoping = ret.semantic.into_scoping();
-    let mut analyzer = NativeModuleAnalyzer::new(&scoping);
+    let mut analyzer = NativeModuleAnalyzer::new(&scoping, imported_types, jsdocs);
 
     analyzer.visit_program(&program);
+    analyzer.flatten_extends();
 
     if !analyzer.diagnostics.is_empty() {
         return Err(ParseError::Oxc {
@@ -873,16 +2467,42 @@ pub fn try_parse_schema(src: &str) -> Result<Vec<Schema>, ParseError> {
 
     debug!("Collected decls: {:?}", analyzer.decls);
 
-    let schemas = analyzer.try_into_schema()?;
+    Ok(f(analyzer)?)
+}
+
+pub fn try_parse_schema(src: &str) -> Result<Vec<Schema>, ParseError> {
+    try_parse_schema_with_imports(src, &mut |source| {
+        anyhow::bail!("Cannot resolve the type import: {source}")
+    })
+}
 
-    Ok(schemas)
+/// Parses the schema and resolves the relative type imports with `resolve`.
+pub fn try_parse_schema_with_imports(
+    src: &str,
+    resolve: &mut ImportResolver,
+) -> Result<Vec<Schema>, ParseError> {
+    try_analyze(src, resolve, |analyzer| analyzer.try_into_schema())
+}
+
+/// Parses the types declared in the source file (eg. shared model files).
+pub fn try_parse_types(
+    src: &str,
+    resolve: &mut ImportResolver,
+) -> Result<ImportedTypes, ParseError> {
+    try_analyze(src, resolve, |analyzer| analyzer.try_into_types())
 }
 
 #[cfg(test)]
 mod tests {
     use insta::{assert_debug_snapshot, assert_snapshot};
 
-    use crate::{parser::native_spec_parser::try_parse_schema, types::Schema};
+    use crate::{
+        parser::native_spec_parser::{
+            try_parse_schema, try_parse_schema_with_imports, try_parse_types,
+        },
+        parser::types::{EnumTypeAnnotation, ObjectTypeAnnotation, ThreadAffinity, TypeAnnotation},
+        types::Schema,
+    };
 
     #[test]
     fn test_common_spec() {
@@ -1027,17 +2647,20 @@ mod tests {
     }
 
     #[test]
-    fn test_ref_type() {
+    fn test_signal_payload() {
         let src = "
         import type { NativeModule, Signal } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
-        interface Foo {
-            bar: string;
+        interface Progress {
+            loaded: number;
+            total: number;
         }
 
         export interface Spec extends NativeModule {
-            getFoo(): Promise<Foo>;
+            onProgress: Signal<Progress>;
+            onMessage: Signal<string | null>;
+            onDone: Signal<void>;
         }
 
         export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
@@ -1045,62 +2668,63 @@ mod tests {
         let schemas = try_parse_schema(src).unwrap();
 
         assert!(schemas.len() == 1);
+        assert!(schemas[0].signals.len() == 3);
         assert!(schemas[0].aliases.len() == 1);
         assert_debug_snapshot!(schemas);
     }
 
     #[test]
-    fn test_multiple_specs() {
+    fn test_invalid_signal_payload() {
         let src = "
         import type { NativeModule, Signal } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
-        type Common = { value: number };
-
-        export interface Spec1 extends NativeModule {
-            foo(arg: Common): void;
-        }
-
-        export interface Spec2 extends NativeModule {
-            bar(arg: Common): void;
+        export interface Spec extends NativeModule {
+            onFoo: Signal<Promise<number>>;
         }
 
-        export const Foo = NativeModuleRegistry.getEnforcing<Spec1>('FooModule');
-        export const Bar = NativeModuleRegistry.getEnforcing<Spec2>('BarModule');
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
         ";
-        let schemas = try_parse_schema(src).unwrap();
+        let result = try_parse_schema(src);
 
-        assert!(schemas.len() == 2);
-        assert_debug_snapshot!(schemas);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_non_spec_1() {
+    fn test_array_buffer() {
         let src = "
-        import type { Unknown } from 'craby-modules';
+        import type { NativeModule } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
-        export interface Spec extends Unknown {
-            myMethod(): void;
+        interface Packet {
+            data: ArrayBuffer;
         }
 
-        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        export interface Spec extends NativeModule {
+            encode(data: Uint8Array): ArrayBuffer;
+            decode(data: ArrayBuffer | null): Packet;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
         ";
-        let result = try_parse_schema(src);
+        let schemas = try_parse_schema(src).unwrap();
 
-        assert!(result.is_err());
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].methods.len() == 2);
+        assert_debug_snapshot!(schemas);
     }
 
     #[test]
-    fn test_non_spec_2() {
+    fn test_invalid_uint8_array() {
         let src = "
-        import { NativeModuleRegistry } from 'react-native';
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
 
-        export interface Spec {
-            myMethod(): void;
+        export interface Spec extends NativeModule {
+            encode(data: ArrayBuffer): Uint8Array;
         }
 
-        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
         ";
         let result = try_parse_schema(src);
 
@@ -1108,107 +2732,194 @@ mod tests {
     }
 
     #[test]
-    fn test_invalid_spec_generic_1() {
+    fn test_numeric_types() {
         let src = "
-        import type { NativeModule, Signal } from 'craby-modules';
+        import type { NativeModule, Int32, Int64, UInt8, Float } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
+        interface Pixel {
+            r: UInt8;
+            g: UInt8;
+            b: UInt8;
+            alpha: Float;
+        }
+
         export interface Spec extends NativeModule {
-            myMethod(): void;
+            seek(offset: Int64, whence: Int32): bigint;
+            blend(pixels: Pixel[], weights: Float[] | null): Pixel;
+            sum(values: Int32[]): Int64 | null;
         }
 
-        export default NativeModuleRegistry.getEnforcing<Unknown>('MyModule');
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
         ";
-        let result = try_parse_schema(src);
+        let schemas = try_parse_schema(src).unwrap();
 
-        assert!(result.is_err());
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].methods.len() == 3);
+        assert_debug_snapshot!(schemas);
     }
 
     #[test]
-    fn test_invalid_spec_generic_2() {
+    fn test_callback() {
         let src = "
-        import type { NativeModule, Signal } from 'craby-modules';
+        import type { NativeModule } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
+        interface Chunk {
+            data: string;
+        }
+
         export interface Spec extends NativeModule {
-            myMethod(): void;
+            subscribe(onData: (chunk: Chunk) => void, onDone: () => void): void;
+            watch(onChange: (value?: number) => void): Promise<void>;
         }
 
-        export default NativeModuleRegistry.getEnforcing<Spec, any>('MyModule');
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
         ";
-        let result = try_parse_schema(src);
+        let schemas = try_parse_schema(src).unwrap();
 
-        assert!(result.is_err());
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].aliases.len() == 1);
+        assert_debug_snapshot!(schemas);
     }
 
     #[test]
-    fn test_non_registry() {
-        let src: &'static str = "
-        import type { NativeModule, Signal } from 'craby-modules';
-        import { Something } from 'craby-modules';
+    fn test_invalid_callback() {
+        let srcs = [
+            // Callback must return `void`
+            "subscribe(onData: (chunk: string) => number): void;",
+            // Callback must have at most one parameter
+            "subscribe(onData: (a: string, b: string) => void): void;",
+            // Callback is only supported as a method parameter
+            "subscribe(): () => void;",
+            // Callback parameter cannot be optional
+            "subscribe(onData?: () => void): void;",
+        ];
+
+        for method in srcs {
+            let src = format!(
+                "
+                import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                export interface Spec extends NativeModule {{
+                    {method}
+                }}
+
+                export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+                "
+            );
+            let result = try_parse_schema(&src);
 
-        export interface Spec extends NativeModule {
-            myMethod(): void;
+            assert!(result.is_err(), "{method}");
         }
-
-        export default Something.getEnforcing<Spec>('MyModule');
-        ";
-        let result = try_parse_schema(src);
-
-        assert!(result.is_err());
     }
 
     #[test]
-    fn test_non_registry_call() {
-        let src: &'static str = "
-        import type { NativeModule, Signal } from 'craby-modules';
+    fn test_abort_signal() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
         export interface Spec extends NativeModule {
-            myMethod(): void;
+            hash(data: ArrayBuffer, signal?: AbortSignal): Promise<string>;
+            sleep(ms: number, signal: AbortSignal): Promise<void>;
         }
 
-        export default NativeModuleRegistry.foo<Spec>('MyModule');
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
         ";
-        let result = try_parse_schema(src);
+        let schemas = try_parse_schema(src).unwrap();
 
-        assert!(result.is_err());
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].has_abort_signals());
+        assert_debug_snapshot!(schemas);
     }
 
     #[test]
-    fn test_duplicate_spec() {
-        let src: &'static str = "
-        import type { NativeModule, Signal } from 'craby-modules';
+    fn test_throws() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
+        enum FileError {
+            NotFound = 'NOT_FOUND',
+            PermissionDenied = 'PERMISSION_DENIED',
+        }
+
+        enum Status {
+            Ok = 'ok',
+        }
+
         export interface Spec extends NativeModule {
-            myMethod(): void;
+            /**
+             * Divides two numbers.
+             *
+             * @throws {Error} If the divisor is zero.
+             */
+            divide(a: number, b: number): number;
+            /** @throws */
+            validate(value: string): Status;
+            /** @throws {FileError} If the file does not exist. */
+            readFile(path: string): Promise<string>;
+            /** @throwsError */
+            noop(): void;
         }
 
-        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('MyModule');
-        export const Bar = NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
         ";
-        let result = try_parse_schema(src);
+        let schemas = try_parse_schema(src).unwrap();
+        let throws = schemas[0]
+            .methods
+            .iter()
+            .map(|method| (method.name.as_str(), method.throws))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            throws,
+            [
+                ("divide", true),
+                ("noop", false),
+                // Async methods are always fallible
+                ("readFile", false),
+                ("validate", true),
+            ]
+        );
 
-        assert!(result.is_err());
+        // Only the enums referenced by the `@throws` tags are the error codes
+        let error_codes = schemas[0]
+            .enums
+            .iter()
+            .map(|enum_type| match enum_type {
+                TypeAnnotation::Enum(EnumTypeAnnotation {
+                    name, error_code, ..
+                }) => (name.as_str(), *error_code),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(error_codes.len(), 2);
+        assert!(error_codes.contains(&("FileError", true)));
+        assert!(error_codes.contains(&("Status", false)));
+        assert_debug_snapshot!(schemas);
     }
 
     #[test]
-    fn test_invalid_enum_1() {
-        let src: &'static str = "
-        import type { NativeModule, Signal } from 'craby-modules';
+    fn test_invalid_error_code() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
-        enum MyEnum {
-            Foo = 'foo',
-            Bar = 1
+        enum FileError {
+            NotFound = 0,
+            PermissionDenied = 1,
         }
 
         export interface Spec extends NativeModule {
-            myMethod(arg: MyEnum): void;
+            /** @throws {FileError} Error codes must be a string enum */
+            readFile(path: string): Promise<string>;
         }
 
-        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
         ";
         let result = try_parse_schema(src);
 
@@ -1216,18 +2927,154 @@ mod tests {
     }
 
     #[test]
-    fn test_invalid_enum_2() {
-        let src: &'static str = "
-        import type { NativeModule, Signal } from 'craby-modules';
-        import { NativeModuleRegistry } from 'craby-modules';
+    fn test_invalid_abort_signal() {
+        let srcs = [
+            // `AbortSignal` is only supported in promise methods
+            "hash(data: string, signal?: AbortSignal): string;",
+            // `AbortSignal` can be declared only once
+            "hash(a: AbortSignal, b: AbortSignal): Promise<string>;",
+            // `AbortSignal` is only supported as a method parameter
+            "hash(options: { signal: AbortSignal }): Promise<string>;",
+            "hash(): Promise<AbortSignal>;",
+            "hash(signal: AbortSignal | null): Promise<string>;",
+        ];
+
+        for method in srcs {
+            let src = format!(
+                "
+                import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                export interface Spec extends NativeModule {{
+                    {method}
+                }}
+
+                export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+                "
+            );
+            let result = try_parse_schema(&src);
 
-        enum MyEnum {
-            Foo = 1,
-            Bar = 3.14
+            assert!(result.is_err(), "{method}");
+        }
+    }
+
+    #[test]
+    fn test_stream() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface Line {
+            text: string;
         }
 
         export interface Spec extends NativeModule {
-            myMethod(arg: MyEnum): void;
+            readLines(path: string): AsyncIterable<Line>;
+            readNumbers(): AsyncIterable<{ value: number }>;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].has_streams());
+        assert!(schemas[0].aliases.len() == 2);
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_stream() {
+        let srcs = [
+            // Stream item type is required
+            "readLines(): AsyncIterable;",
+            // Stream item cannot be `void`
+            "readLines(): AsyncIterable<void>;",
+            // Stream item cannot be a promise
+            "readLines(): AsyncIterable<Promise<string>>;",
+            // Stream is only supported as a method return type
+            "readLines(lines: AsyncIterable<string>): void;",
+            "readLines(): Promise<AsyncIterable<string>>;",
+            "readLines(): AsyncIterable<string> | null;",
+            // `stream` is reserved for the producer handle
+            "readLines(stream: string): AsyncIterable<string>;",
+        ];
+
+        for method in srcs {
+            let src = format!(
+                "
+                import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                export interface Spec extends NativeModule {{
+                    {method}
+                }}
+
+                export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+                "
+            );
+            let result = try_parse_schema(&src);
+
+            assert!(result.is_err(), "{method}");
+        }
+    }
+
+    #[test]
+    fn test_ref_type() {
+        let src = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface Foo {
+            bar: string;
+        }
+
+        export interface Spec extends NativeModule {
+            getFoo(): Promise<Foo>;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].aliases.len() == 1);
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_multiple_specs() {
+        let src = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        type Common = { value: number };
+
+        export interface Spec1 extends NativeModule {
+            foo(arg: Common): void;
+        }
+
+        export interface Spec2 extends NativeModule {
+            bar(arg: Common): void;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec1>('FooModule');
+        export const Bar = NativeModuleRegistry.getEnforcing<Spec2>('BarModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+
+        assert!(schemas.len() == 2);
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_non_spec_1() {
+        let src = "
+        import type { Unknown } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends Unknown {
+            myMethod(): void;
         }
 
         export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
@@ -1238,18 +3085,49 @@ mod tests {
     }
 
     #[test]
-    fn test_reserved_type() {
-        let src: &'static str = "
+    fn test_non_spec_2() {
+        let src = "
+        import { NativeModuleRegistry } from 'react-native';
+
+        export interface Spec {
+            myMethod(): void;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_spec_generic_1() {
+        let src = "
         import type { NativeModule, Signal } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
-        type Promise = number;
+        export interface Spec extends NativeModule {
+            myMethod(): void;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Unknown>('MyModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_spec_generic_2() {
+        let src = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
 
         export interface Spec extends NativeModule {
-            myMethod(arg: Promise): void;
+            myMethod(): void;
         }
 
-        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        export default NativeModuleRegistry.getEnforcing<Spec, any>('MyModule');
         ";
         let result = try_parse_schema(src);
 
@@ -1257,16 +3135,33 @@ mod tests {
     }
 
     #[test]
-    fn test_reserved_arg_name() {
+    fn test_non_registry() {
+        let src: &'static str = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { Something } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            myMethod(): void;
+        }
+
+        export default Something.getEnforcing<Spec>('MyModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_non_registry_call() {
         let src: &'static str = "
         import type { NativeModule, Signal } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
         export interface Spec extends NativeModule {
-            myMethod(it_: number): void;
+            myMethod(): void;
         }
 
-        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        export default NativeModuleRegistry.foo<Spec>('MyModule');
         ";
         let result = try_parse_schema(src);
 
@@ -1274,16 +3169,17 @@ mod tests {
     }
 
     #[test]
-    fn test_reserved_method_name() {
+    fn test_duplicate_spec() {
         let src: &'static str = "
         import type { NativeModule, Signal } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
         export interface Spec extends NativeModule {
-            emit(): void;
+            myMethod(): void;
         }
 
-        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        export const Bar = NativeModuleRegistry.getEnforcing<Spec>('MyModule');
         ";
         let result = try_parse_schema(src);
 
@@ -1291,13 +3187,18 @@ mod tests {
     }
 
     #[test]
-    fn test_optional_method() {
+    fn test_invalid_enum_1() {
         let src: &'static str = "
         import type { NativeModule, Signal } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
+        enum MyEnum {
+            Foo = 'foo',
+            Bar = 1
+        }
+
         export interface Spec extends NativeModule {
-            myMethod?: () => void;
+            myMethod(arg: MyEnum): void;
         }
 
         export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
@@ -1308,13 +3209,18 @@ mod tests {
     }
 
     #[test]
-    fn test_property_method() {
+    fn test_invalid_enum_2() {
         let src: &'static str = "
         import type { NativeModule, Signal } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
+        enum MyEnum {
+            Foo = 1,
+            Bar = 1 + 2
+        }
+
         export interface Spec extends NativeModule {
-            myMethod: () => void;
+            myMethod(arg: MyEnum): void;
         }
 
         export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
@@ -1325,50 +3231,1169 @@ mod tests {
     }
 
     #[test]
-    fn test_hash() {
-        let src_1: &'static str = "
-        import type { NativeModule, Signal } from 'craby-modules';
+    fn test_enum_values() {
+        let src: &'static str = "
+        import type { NativeModule } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
-        interface SomeObject {
-            a: string;
-            b: number;
-            c: boolean;
+        enum Direction {
+            Up,
+            Down,
+            Left = 10,
+            Right,
+        }
+
+        /**
+         * Status codes.
+         *
+         * @repr i16
+         */
+        enum Status {
+            Unknown = -1,
+            Ok,
+            Retry = 300,
         }
 
+        enum Ratio {
+            Half = 0.5,
+            Negative = -1.5,
+            Next,
+        }
+
+        type Level = -1 | 0 | 0.5;
+
         export interface Spec extends NativeModule {
-            foo(arg: SomeObject): SomeObject;
-            bar(): void;
-            baz(): void;
-            onSignal: Signal;
+            myMethod(arg0: Direction, arg1: Status, arg2: Ratio, arg3: Level): void;
         }
 
         export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
         ";
+        let schemas = try_parse_schema(src).unwrap();
 
-        let src_2: &'static str = "
-        import type { NativeModule, Signal } from 'craby-modules';
+        assert!(schemas[0].enums.len() == 4);
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_enum_values() {
+        let srcs = [
+            // Duplicate values
+            "enum MyEnum { Foo = 1, Bar = 0, Baz }",
+            // Implicit value after the string member
+            "enum MyEnum { Foo = 'foo', Bar }",
+            // Unsupported repr
+            "/** @repr f64 */ enum MyEnum { Foo = 1 }",
+            // Out of the repr range
+            "/** @repr u8 */ enum MyEnum { Foo = -1 }",
+            // Repr with non-integer members
+            "/** @repr i32 */ enum MyEnum { Foo = 0.5 }",
+            // Repr with string members
+            "/** @repr i32 */ enum MyEnum { Foo = 'foo' }",
+        ];
+
+        for decls in srcs {
+            let src = format!(
+                "
+                import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                {decls}
+
+                export interface Spec extends NativeModule {{
+                    myMethod(arg: MyEnum): void;
+                }}
+
+                export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+                "
+            );
+
+            assert!(try_parse_schema(&src).is_err(), "{decls}");
+        }
+    }
+
+    #[test]
+    fn test_literal_union() {
+        let src: &'static str = "
+        import type { NativeModule } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
+        type Mode = 'fast' | 'safe' | 'read-only';
+        type Level = 0 | 1 | 2;
+        type MaybeMode = 'on' | 'off' | null;
+
+        interface Options {
+            level: Level;
+        }
+
         export interface Spec extends NativeModule {
-            foo(): void;
-            bar(): void;
-            baz(): void;
+            setMode(mode: Mode, options: Options): MaybeMode;
+            setSpeed(speed: 'fast' | 'slow', level?: 0 | 1): 'ok' | 'failed' | null;
+            setSpeedAgain(speed: 'fast' | 'slow'): void;
+            setSpeedLevel(options: { level: 0 | 1 | 2 }): void;
         }
 
         export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
         ";
-        let schemas = try_parse_schema(src_1).unwrap();
-        let hash_1 = Schema::to_hash(&schemas);
+        let schemas = try_parse_schema(src).unwrap();
+        let mut names = schemas[0]
+            .enums
+            .iter()
+            .map(|enum_type| match enum_type {
+                TypeAnnotation::Enum(EnumTypeAnnotation { name, .. }) => name.as_str(),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        names.sort();
 
-        let schemas = try_parse_schema(src_1).unwrap();
-        let hash_2 = Schema::to_hash(&schemas);
+        assert!(schemas.len() == 1);
+        // Inline literal unions with the same members share the first collected name
+        assert_eq!(
+            names,
+            [
+                "Level",
+                "MaybeMode",
+                "Mode",
+                "SetSpeedLevel",
+                "SetSpeedLevelOptionsLevel",
+                "SetSpeedResult",
+                "SetSpeedSpeed",
+            ]
+        );
+        assert_debug_snapshot!(schemas);
+    }
 
-        let schemas = try_parse_schema(src_2).unwrap();
-        let hash_3 = Schema::to_hash(&schemas);
+    #[test]
+    fn test_invalid_literal_union() {
+        let srcs = [
+            // Inline literal union in the interface property
+            "setMode(options: Options): void;",
+            // Inline literal union with mixed member types
+            "setMode(mode: 'fast' | 1): void;",
+            // Mixed member types
+            "setMode(mode: Mixed): void;",
+            // Negative number
+            "setMode(mode: Negative): void;",
+            // Boolean literal
+            "setMode(mode: Bool): void;",
+            // Duplicate member names
+            "setMode(mode: Duplicate): void;",
+        ];
+
+        for method in srcs {
+            let src = format!(
+                "
+                import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                type Mixed = 'fast' | 1;
+                type Negative = -1 | 1;
+                type Bool = true | false;
+                type Duplicate = 'read-only' | 'read_only';
+
+                interface Options {{
+                    mode: 'fast' | 'safe';
+                }}
+
+                export interface Spec extends NativeModule {{
+                    {method}
+                }}
+
+                export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+                "
+            );
+            let result = try_parse_schema(&src);
 
-        assert_eq!(hash_1, hash_2);
-        assert_ne!(hash_1, hash_3);
-        assert_snapshot!([hash_1, hash_2, hash_3].join("\n"));
+            assert!(result.is_err(), "{method}");
+        }
+    }
+
+    #[test]
+    fn test_tagged_union() {
+        let src: &'static str = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface Reason {
+            code: number;
+            message: string;
+        }
+
+        type Outcome =
+            | { kind: 'ok'; value: number }
+            | { kind: 'err'; reason: Reason }
+            | { kind: 'pending' };
+
+        export interface Spec extends NativeModule {
+            run(prev: Outcome | null): Outcome;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].aliases.len() == 3);
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_tagged_union() {
+        let srcs = [
+            // Tagged union must be declared as a type alias
+            "run(arg: { kind: 'a' } | { kind: 'b' }): void;",
+            // Missing discriminant
+            "run(arg: NoTag): void;",
+            // Different discriminant properties
+            "run(arg: MismatchedTag): void;",
+            // Duplicate discriminant values
+            "run(arg: DuplicateTag): void;",
+        ];
+
+        for method in srcs {
+            let src = format!(
+                "
+                import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                type NoTag = {{ kind: 'a' }} | {{ value: number }};
+                type MismatchedTag = {{ kind: 'a' }} | {{ type: 'b' }};
+                type DuplicateTag = {{ kind: 'a' }} | {{ kind: 'a'; value: number }};
+
+                export interface Spec extends NativeModule {{
+                    {method}
+                }}
+
+                export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+                "
+            );
+            let result = try_parse_schema(&src);
+
+            assert!(result.is_err(), "{method}");
+        }
+    }
+
+    #[test]
+    fn test_reserved_type() {
+        let src: &'static str = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        type Promise = number;
+
+        export interface Spec extends NativeModule {
+            myMethod(arg: Promise): void;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_reserved_arg_name() {
+        let src: &'static str = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            myMethod(it_: number): void;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_reserved_method_name() {
+        let src: &'static str = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            emit(): void;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_reserved_lifecycle_method_name() {
+        for method_name in ["onInvalidate", "onReload", "on_reload"] {
+            let src = format!(
+                "
+                import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                export interface Spec extends NativeModule {{
+                    {method_name}(): void;
+                }}
+
+                export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+                "
+            );
+            let result = try_parse_schema(&src);
+
+            assert!(result.is_err(), "{method_name}");
+        }
+    }
+
+    #[test]
+    fn test_optional_method() {
+        let src: &'static str = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            myMethod?: () => void;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_optional_params() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface Options {
+            name?: string;
+            count?: number | null;
+        }
+
+        export interface Spec extends NativeModule {
+            myMethod(arg: number, options?: Options): void;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].methods[0].params[1].optional);
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_required_param() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            myMethod(arg0?: number, arg1: number): void;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_map_type() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        type Headers = Record<string, string>;
+
+        interface Flags {
+            [name: string]: boolean;
+        }
+
+        interface Request {
+            headers: Headers;
+            query: { [key: string]: string } | null;
+        }
+
+        export interface Spec extends NativeModule {
+            send(request: Request, flags: Flags): Record<string, number>;
+            getHeaders(): Promise<Headers | null>;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].methods.iter().any(|m| m.ret_type.is_map()));
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_map_key() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            myMethod(arg: Record<number, string>): void;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_property_method() {
+        let src: &'static str = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            myMethod: () => void;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_hash() {
+        let src_1: &'static str = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface SomeObject {
+            a: string;
+            b: number;
+            c: boolean;
+        }
+
+        export interface Spec extends NativeModule {
+            foo(arg: SomeObject): SomeObject;
+            bar(): void;
+            baz(): void;
+            onSignal: Signal;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+
+        let src_2: &'static str = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            foo(): void;
+            bar(): void;
+            baz(): void;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let schemas = try_parse_schema(src_1).unwrap();
+        let hash_1 = Schema::to_hash(&schemas);
+
+        let schemas = try_parse_schema(src_1).unwrap();
+        let hash_2 = Schema::to_hash(&schemas);
+
+        let schemas = try_parse_schema(src_2).unwrap();
+        let hash_3 = Schema::to_hash(&schemas);
+
+        assert_eq!(hash_1, hash_2);
+        assert_ne!(hash_1, hash_3);
+        assert_snapshot!([hash_1, hash_2, hash_3].join("\n"));
+    }
+
+    #[test]
+    fn test_constants() {
+        let src: &'static str = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface Features {
+            streaming: boolean;
+        }
+
+        interface Constants {
+            sdkVersion: string;
+            features: Features;
+        }
+
+        export interface Spec extends NativeModule {
+            getConstants(): { version: string; maxItems: number };
+            multiply(a: number, b: number): number;
+        }
+
+        export interface ReferenceSpec extends NativeModule {
+            getConstants(): Constants;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        export const Bar = NativeModuleRegistry.getEnforcing<ReferenceSpec>('ReferenceModule');
+        ";
+        let mut schemas = try_parse_schema(src).unwrap();
+        schemas.sort_by_key(|schema| schema.module_name.clone());
+
+        assert!(schemas.len() == 2);
+        assert!(schemas[0].methods.len() == 1);
+        assert!(schemas[1].methods.is_empty());
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_constants() {
+        let srcs = [
+            // Parameters are not allowed
+            "getConstants(key: string): Constants;",
+            // Non-object type
+            "getConstants(): string;",
+            // Promise is not allowed
+            "getConstants(): Promise<Constants>;",
+            // Map type
+            "getConstants(): Record<string, string>;",
+        ];
+
+        for method in srcs {
+            let src = format!(
+                "
+                import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                interface Constants {{
+                    version: string;
+                }}
+
+                export interface Spec extends NativeModule {{
+                    {method}
+                }}
+
+                export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+                "
+            );
+            let result = try_parse_schema(&src);
+
+            assert!(result.is_err(), "{method}");
+        }
+    }
+
+    #[test]
+    fn test_type_literal() {
+        let src: &'static str = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            request(
+                url: string,
+                options?: { timeout: number; retry: { count: number } },
+            ): Promise<{ status: number; body: string }>;
+            upload(options: { timeout: number; retry: { count: number } }): void;
+            lastResponse(): { status: number; body: string } | null;
+            onProgress: Signal<{ loaded: number }>;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].aliases.len() == 4);
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_type_literal_name_collision() {
+        let src: &'static str = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            fooBar(baz: { a: string }): void;
+            foo(barBaz: { b: string }): void;
+            fooBarBaz(options: { a: string }): void;
+            fooBarBazOptions(): { c: string };
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+        let names = schemas[0]
+            .methods
+            .iter()
+            .map(|method| match method.params.first() {
+                Some(param) => &param.type_annotation,
+                None => &method.ret_type,
+            })
+            .map(|type_annotation| match type_annotation {
+                TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => name.as_str(),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+
+        // Methods are sorted by name (the type literals are named in the declaration order)
+        assert_eq!(
+            names,
+            [
+                // foo(barBaz)
+                "FooBarBazOptions2",
+                // fooBar(baz)
+                "FooBarBazOptions",
+                // fooBarBaz(options): same shape as `fooBar(baz)`
+                "FooBarBazOptions",
+                // fooBarBazOptions()
+                "FooBarBazOptionsResult",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_type_literal() {
+        let srcs = [
+            // Type literal in the interface property
+            (
+                "interface Options { retry: { count: number } }",
+                "foo(arg: Options): void;",
+            ),
+            // Conflicts with the declared type
+            (
+                "interface FooArgOptions { b: number }",
+                "foo(arg: { a: string }): void;\nbar(arg: FooArgOptions): void;",
+            ),
+        ];
+
+        for (decls, methods) in srcs {
+            let src = format!(
+                "
+                import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                {decls}
+
+                export interface Spec extends NativeModule {{
+                    {methods}
+                }}
+
+                export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+                "
+            );
+            let result = try_parse_schema(&src);
+
+            assert!(result.is_err(), "{methods}");
+        }
+
+        // Sanity check: the type literal itself is valid
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            foo(arg: { a: string }): void;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        assert!(try_parse_schema(src).is_ok());
+    }
+
+    #[test]
+    fn test_imported_types() {
+        let types_src = "
+        import type { Int32 } from 'craby-modules';
+        import type { Address } from './address';
+
+        export interface User {
+            name: string;
+            age: Int32;
+            role: Role;
+            address: Address | null;
+        }
+
+        export enum Role {
+            Admin = 'admin',
+            Member = 'member',
+        }
+        ";
+        let address_src = "
+        export type Address = {
+            city: string;
+        };
+        ";
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+        import type { User, Role } from './types';
+
+        export interface Spec extends NativeModule {
+            getUser(role: Role): User;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let schemas = try_parse_schema_with_imports(src, &mut |source| {
+            assert_eq!(source, "./types");
+            let types = try_parse_types(types_src, &mut |source| {
+                assert_eq!(source, "./address");
+                Ok(try_parse_types(address_src, &mut |_| unreachable!()).unwrap())
+            });
+            Ok(types.unwrap())
+        })
+        .unwrap();
+
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].aliases.len() == 2);
+        assert!(schemas[0].enums.len() == 1);
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_imported_types() {
+        let srcs = [
+            // Type not found in the imported source
+            "import type { Unknown } from './types';",
+            // Default import
+            "import User from './types';",
+            // Namespace import
+            "import * as types from './types';",
+        ];
+
+        for import in srcs {
+            let src = format!(
+                "
+                import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+                {import}
+
+                export interface Spec extends NativeModule {{
+                    foo(): void;
+                }}
+
+                export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+                "
+            );
+            let result = try_parse_schema_with_imports(&src, &mut |_| {
+                Ok(try_parse_types(
+                    "export interface User { name: string }",
+                    &mut |_| unreachable!(),
+                )
+                .unwrap())
+            });
+
+            assert!(result.is_err(), "{import}");
+        }
+
+        // Relative imports cannot be resolved without the resolver
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+        import type { User } from './types';
+
+        export interface Spec extends NativeModule {
+            getUser(): User;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        assert!(try_parse_schema(src).is_err());
+    }
+
+    #[test]
+    fn test_interface_extends() {
+        let src: &'static str = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface Video extends Photo, Playable {
+            codec: string;
+        }
+
+        interface Photo extends MediaBase, Timestamped {
+            width: number;
+            owner: User;
+        }
+
+        interface MediaBase {
+            id: string;
+            owner: User;
+        }
+
+        interface Timestamped {
+            updatedAt: number;
+        }
+
+        type Playable = {
+            duration: number;
+        };
+
+        interface User {
+            name: string;
+        }
+
+        export interface Spec extends NativeModule {
+            getPhoto(): Photo;
+            getVideo(): Video;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].aliases.len() == 3);
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_jsdoc() {
+        let src: &'static str = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        /**
+         * Photo metadata.
+         */
+        export interface Photo {
+            /** Unique identifier */
+            id: string;
+            // Line comments are ignored
+            width: number;
+        }
+
+        /** Supported image formats */
+        enum Format {
+            /** Lossy format */
+            Jpeg = 'jpeg',
+            Png = 'png',
+        }
+
+        export interface Spec extends NativeModule {
+            /**
+             * Resizes the photo.
+             *
+             * @param photo - The photo to resize.
+             * @param {number} width The target width.
+             * @returns The resized photo.
+             */
+            resize(photo: Photo, width: number, /** Output format */ format: Format): Photo;
+            undocumented(): void;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+        let method = &schemas[0].methods[0];
+
+        assert_eq!(
+            method.doc.as_deref(),
+            Some("Resizes the photo.\n\n@returns The resized photo.")
+        );
+        assert_eq!(
+            method
+                .params
+                .iter()
+                .map(|param| param.doc.as_deref())
+                .collect::<Vec<_>>(),
+            [
+                Some("The photo to resize."),
+                Some("The target width."),
+                Some("Output format"),
+            ]
+        );
+        assert!(schemas[0].methods[1].doc.is_none());
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_interface_extends() {
+        let srcs = [
+            // Circular inheritance
+            "interface Photo extends MediaBase {} interface MediaBase extends Photo {}",
+            // Conflicts with the own property
+            "interface Photo extends MediaBase { id: number } interface MediaBase { id: string }",
+            // Conflicts between the base types
+            "interface Photo extends MediaBase, Other {} interface MediaBase { id: string } interface Other { id: number }",
+            // Non-object base type
+            "interface Photo extends MediaBase {} type MediaBase = Record<string, string>;",
+            // Type arguments
+            "interface Photo extends MediaBase<string> {} interface MediaBase<T> { id: T }",
+        ];
+
+        for decls in srcs {
+            let src = format!(
+                "
+                import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                {decls}
+
+                export interface Spec extends NativeModule {{
+                    getPhoto(): Photo;
+                }}
+
+                export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+                "
+            );
+            let result = try_parse_schema(&src);
+
+            assert!(result.is_err(), "{decls}");
+        }
+    }
+
+    #[test]
+    fn test_host_object() {
+        let src = "
+        import type { NativeModule, HostObject } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface Row {
+            id: number;
+            name: string;
+        }
+
+        /** Opened database connection */
+        export interface Connection extends HostObject {
+            query(sql: string): Row[];
+            prepare(sql: string): Statement;
+            close(): void;
+        }
+
+        export interface Statement extends HostObject {
+            run(params: string[]): number;
+        }
+
+        export interface Spec extends NativeModule {
+            open(path: string): Connection;
+            openAsync(path: string): Promise<Connection>;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+        let handles = schemas[0]
+            .handles
+            .iter()
+            .map(|handle| handle.name.as_str())
+            .collect::<Vec<_>>();
+
+        // Host objects returned by the other host objects are collected as well
+        assert_eq!(handles, ["Connection", "Statement"]);
+        assert!(schemas[0].aliases.len() == 1);
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_host_object() {
+        let srcs = [
+            // Host object methods must be synchronous
+            (
+                "query(sql: string): Promise<string>;",
+                "open(): Connection;",
+            ),
+            ("rows(): AsyncIterable<string>;", "open(): Connection;"),
+            // Host object methods cannot take callbacks or `AbortSignal`
+            ("query(onRow: () => void): void;", "open(): Connection;"),
+            // Host object members must be methods
+            ("path: string;", "open(): Connection;"),
+            // Host object is only allowed as a method return type
+            ("close(): void;", "close(conn: Connection): void;"),
+            ("close(): void;", "open(): Connection | null;"),
+            ("close(): void;", "open(): Connection[];"),
+            ("close(): void;", "open(): { conn: Connection };"),
+            ("close(): void;", "open(): AsyncIterable<Connection>;"),
+            ("clone(): Connection[];", "open(): Connection;"),
+        ];
+
+        for (member, method) in srcs {
+            let src = format!(
+                "
+                import type {{ NativeModule, HostObject }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                export interface Connection extends HostObject {{
+                    {member}
+                }}
+
+                export interface Spec extends NativeModule {{
+                    {method}
+                }}
+
+                export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+                "
+            );
+            let result = try_parse_schema(&src);
+
+            assert!(result.is_err(), "{member} {method}");
+        }
+    }
+
+    #[test]
+    fn test_thread_affinity() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        /** @thread serial */
+        export interface Spec extends NativeModule {
+            /**
+             * Writes the file.
+             *
+             * @thread io
+             */
+            write(path: string): Promise<void>;
+            read(path: string): Promise<string>;
+            /** @thread pool */
+            hash(data: string): Promise<string>;
+            /** @thread js */
+            peek(key: string): Promise<string>;
+            lines(path: string): AsyncIterable<string>;
+            add(a: number, b: number): number;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+        let threads = schemas[0]
+            .methods
+            .iter()
+            .map(|method| (method.name.as_str(), method.thread.clone()))
+            .collect::<Vec<_>>();
+
+        // Async methods without the tag run on the default thread of the module
+        assert_eq!(
+            threads,
+            [
+                ("add", None),
+                ("hash", Some(ThreadAffinity::Pool)),
+                ("lines", Some(ThreadAffinity::Serial)),
+                ("peek", Some(ThreadAffinity::Js)),
+                ("read", Some(ThreadAffinity::Serial)),
+                ("write", Some(ThreadAffinity::Named("io".to_string()))),
+            ]
+        );
+        assert_eq!(
+            schemas[0].methods[5].doc.as_deref(),
+            Some("Writes the file.")
+        );
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_thread_affinity() {
+        let srcs = [
+            // Sync methods always run on the JS thread
+            (
+                "",
+                "/** @thread serial */ add(a: number, b: number): number;",
+            ),
+            ("", "/** @thread js */ add(a: number, b: number): number;"),
+            // Streams cannot be driven by the JS thread
+            ("", "/** @thread js */ lines(): AsyncIterable<string>;"),
+            // Invalid thread names
+            ("", "/** @thread */ read(): Promise<string>;"),
+            ("", "/** @thread io-worker */ read(): Promise<string>;"),
+            ("", "/** @thread 1st */ read(): Promise<string>;"),
+            // Default thread of the module cannot be the JS thread
+            ("/** @thread js */", "read(): Promise<string>;"),
+            ("/** @thread main thread */", "read(): Promise<string>;"),
+        ];
+
+        for (spec_doc, method) in srcs {
+            let src = format!(
+                "
+                import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                {spec_doc}
+                export interface Spec extends NativeModule {{
+                    {method}
+                }}
+
+                export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+                "
+            );
+            let result = try_parse_schema(&src);
+
+            assert!(result.is_err(), "{spec_doc} {method}");
+        }
+    }
+
+    #[test]
+    fn test_async_fn() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        /** @thread serial */
+        export interface Spec extends NativeModule {
+            /**
+             * Fetches the resource.
+             *
+             * @async
+             */
+            fetch(url: string): Promise<string>;
+            read(path: string): Promise<string>;
+        }
+
+        export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+        let methods = &schemas[0].methods;
+
+        // `async fn` methods are driven by the async executor instead of the default thread
+        assert!(methods[0].async_fn);
+        assert_eq!(methods[0].thread, None);
+        assert_eq!(methods[0].doc.as_deref(), Some("Fetches the resource."));
+        assert!(!methods[1].async_fn);
+        assert_eq!(methods[1].thread, Some(ThreadAffinity::Serial));
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_async_fn() {
+        let methods = [
+            // Promise methods only
+            "/** @async */ add(a: number, b: number): number;",
+            "/** @async */ lines(): AsyncIterable<string>;",
+            // Host objects are not supported
+            "/** @async */ open(): Promise<Connection>;",
+            // Thread affinity is not applicable
+            "/**\n * @async\n * @thread io\n */ read(): Promise<string>;",
+            // Reserved argument name
+            "/** @async */ read(promise: string): Promise<string>;",
+        ];
+
+        for method in methods {
+            let src = format!(
+                "
+                import type {{ NativeModule, HostObject }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                export interface Connection extends HostObject {{
+                    close(): void;
+                }}
+
+                export interface Spec extends NativeModule {{
+                    {method}
+                }}
+
+                export const Foo = NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+                "
+            );
+            let result = try_parse_schema(&src);
+
+            assert!(result.is_err(), "{method}");
+        }
     }
 }
@@ -1,6 +1,7 @@
 use std::hash::{DefaultHasher, Hash, Hasher};
 
 use oxc::{diagnostics::OxcDiagnostic, semantic::ReferenceId};
+use rustc_hash::FxHashMap;
 use serde::Serialize;
 use thiserror::Error;
 
@@ -12,6 +13,9 @@ pub enum ParseError {
     Oxc { diagnostics: Vec<OxcDiagnostic> },
 }
 
+/// Types declared in the imported source file (key: type name)
+pub type ImportedTypes = FxHashMap<String, TypeAnnotation>;
+
 #[derive(Debug)]
 pub struct Spec {
     /// Spec name
@@ -20,19 +24,52 @@ pub struct Spec {
     pub methods: Vec<Method>,
     /// Module signals
     pub signals: Vec<Signal>,
+    /// Module constants (eg. `getConstants(): T`)
+    pub constants: Option<TypeAnnotation>,
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize)]
 pub struct Method {
     pub name: String,
     pub params: Vec<Param>,
     pub ret_type: TypeAnnotation,
+    /// Whether the sync method returns `Result<T, craby::Error>` (`@throws` tag)
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub throws: bool,
+    /// Thread that runs the async method (`@thread` tag, `None` for sync methods)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<ThreadAffinity>,
+    /// Whether the promise method is implemented as an `async fn` (`@async` tag)
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub async_fn: bool,
+    /// JSDoc comment of the declaration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+/// Thread affinity of the async method (eg. `@thread serial`)
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
+pub enum ThreadAffinity {
+    /// Runs on the JS thread (`@thread js`)
+    Js,
+    /// Runs on the shared worker pool of the module (default)
+    Pool,
+    /// Runs on the serial queue of the module in call order (`@thread serial`)
+    Serial,
+    /// Runs on the dedicated thread of the module in call order (eg. `@thread io`)
+    Named(String),
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize)]
 pub struct Param {
     pub name: String,
     pub type_annotation: TypeAnnotation,
+    /// Whether the parameter is optional (eg. `arg?: T`)
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// JSDoc comment of the parameter (or `@param` tag of the method)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
@@ -41,11 +78,29 @@ pub enum TypeAnnotation {
     Boolean,
     Number,
     String,
+    // Branded numeric types of `craby-modules` (eg. `Int32`)
+    Int32,
+    Int64,
+    UInt8,
+    Float,
+    ArrayBuffer,
     Array(Box<TypeAnnotation>),
+    // `Record<string, T>` or `{ [key: string]: T }`
+    Map(Box<TypeAnnotation>),
     Object(ObjectTypeAnnotation),
     Enum(EnumTypeAnnotation),
+    // `{ kind: 'a', ... } | { kind: 'b', ... }`
+    Union(UnionTypeAnnotation),
     Promise(Box<TypeAnnotation>),
+    // `AsyncIterable<T>` (method return type only)
+    Stream(Box<TypeAnnotation>),
     Nullable(Box<TypeAnnotation>),
+    // `(arg: T) => void` (`Void` if the callback has no argument)
+    Callback(Box<TypeAnnotation>),
+    // `AbortSignal` (promise method parameter only)
+    AbortSignal,
+    // `interface Connection extends HostObject` (method return type only)
+    Handle(HandleTypeAnnotation),
     // Reference to `TypeAnnotation::Object` or `TypeAnnotation::Enum` or Alias types (eg. `Promise`)
     Ref(RefTypeAnnotation),
 }
@@ -71,39 +126,254 @@ impl TypeAnnotation {
         }
     }
 
+    pub fn as_union(&self) -> Option<&UnionTypeAnnotation> {
+        match self {
+            TypeAnnotation::Union(union_type) => Some(union_type),
+            _ => None,
+        }
+    }
+
     pub fn is_nullable(&self) -> bool {
         matches!(self, TypeAnnotation::Nullable(..))
     }
+
+    pub fn is_map(&self) -> bool {
+        matches!(self, TypeAnnotation::Map(..))
+    }
+
+    pub fn is_callback(&self) -> bool {
+        matches!(self, TypeAnnotation::Callback(..))
+    }
+
+    pub fn is_abort_signal(&self) -> bool {
+        matches!(self, TypeAnnotation::AbortSignal)
+    }
+
+    /// Whether the type contains a host object handle (eg. `Connection[]`).
+    pub fn contains_handle(&self) -> bool {
+        match self {
+            TypeAnnotation::Handle(..) => true,
+            TypeAnnotation::Object(obj) => obj
+                .props
+                .iter()
+                .any(|prop| prop.type_annotation.contains_handle()),
+            TypeAnnotation::Union(union_type) => union_type
+                .variants
+                .iter()
+                .filter_map(|variant| variant.data.as_ref())
+                .any(|data| {
+                    data.props
+                        .iter()
+                        .any(|prop| prop.type_annotation.contains_handle())
+                }),
+            TypeAnnotation::Array(t)
+            | TypeAnnotation::Map(t)
+            | TypeAnnotation::Promise(t)
+            | TypeAnnotation::Stream(t)
+            | TypeAnnotation::Nullable(t)
+            | TypeAnnotation::Callback(t) => t.contains_handle(),
+            _ => false,
+        }
+    }
+
+    /// Whether the type is bridged with a generated struct (`Nullable*`, `*Map`, `*Union`).
+    pub fn is_bridge_type(&self) -> bool {
+        matches!(
+            self,
+            TypeAnnotation::Nullable(..) | TypeAnnotation::Map(..) | TypeAnnotation::Union(..)
+        )
+    }
+
+    /// Returns the types that are bridged with generated structs (`Nullable*`, `*Map`, `*Union`).
+    ///
+    /// Inner types come first (eg. `[Map<T>, Nullable<Map<T>>]`).
+    pub fn bridge_types(&self) -> Vec<&TypeAnnotation> {
+        match self {
+            TypeAnnotation::Nullable(base_type) => {
+                let mut types = base_type.bridge_types();
+                types.push(self);
+                types
+            }
+            TypeAnnotation::Map(..) | TypeAnnotation::Union(..) => vec![self],
+            TypeAnnotation::Array(element_type) => element_type.bridge_types(),
+            TypeAnnotation::Promise(resolved_type) => resolved_type.bridge_types(),
+            TypeAnnotation::Stream(item_type) => item_type.bridge_types(),
+            TypeAnnotation::Callback(arg_type) => arg_type.bridge_types(),
+            _ => vec![],
+        }
+    }
+
+    /// Wraps the type with `Nullable` if it is not nullable yet.
+    pub fn into_nullable(self) -> TypeAnnotation {
+        if self.is_nullable() {
+            self
+        } else {
+            TypeAnnotation::Nullable(Box::new(self))
+        }
+    }
+}
+
+impl Method {
+    /// Returns `true` if the method returns a promise or a stream.
+    pub fn is_async(&self) -> bool {
+        matches!(
+            self.ret_type,
+            TypeAnnotation::Promise(..) | TypeAnnotation::Stream(..)
+        )
+    }
+
+    /// Returns the method documentation with the documented parameters
+    /// (and the executor note of the `async fn` methods).
+    ///
+    /// ```text
+    /// Multiplies two numbers.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The first number.
+    /// ```
+    pub fn doc_with_params(&self) -> Option<String> {
+        let param_docs = self
+            .params
+            .iter()
+            .filter_map(|param| {
+                param
+                    .doc
+                    .as_ref()
+                    .map(|doc| format!("* `{}` - {}", param.name, doc))
+            })
+            .collect::<Vec<_>>();
+
+        let docs = [
+            self.doc.clone(),
+            (!param_docs.is_empty()).then(|| format!("# Arguments\n\n{}", param_docs.join("\n"))),
+            // The embedded executor is shared by every `async fn` method of all modules
+            self.async_fn.then(|| {
+                "# Executor\n\nThe future is polled on the single-threaded executor of `craby::runtime` that drives every `async fn` method of all modules, so it must not block the thread (use `craby::runtime::spawn_blocking` for the blocking work).".to_string()
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        (!docs.is_empty()).then(|| docs.join("\n\n"))
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
 pub struct ObjectTypeAnnotation {
     pub name: String,
     pub props: Vec<Prop>,
+    /// JSDoc comment of the declaration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
 pub struct Prop {
     pub name: String,
     pub type_annotation: TypeAnnotation,
+    /// Whether the property is optional (eg. `prop?: T`)
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// JSDoc comment of the declaration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
 pub struct EnumTypeAnnotation {
     pub name: String,
     pub members: Vec<EnumMember>,
+    /// Integer type of the enum discriminants (eg. `/** @repr i32 */`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repr: Option<String>,
+    /// Whether the string enum is referenced as the error codes (eg. `@throws {MyError}`)
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub error_code: bool,
+    /// JSDoc comment of the declaration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
 pub struct EnumMember {
     pub name: String,
     pub value: EnumMemberValue,
+    /// JSDoc comment of the declaration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
 pub enum EnumMemberValue {
     String(String),
-    Number(usize),
+    /// Integer value (eg. `1`, `-1`)
+    Number(i64),
+    /// Non-integer value (eg. `0.5`)
+    ///
+    /// Kept as the formatted number since `f64` is not hashable.
+    Float(String),
+}
+
+impl EnumMemberValue {
+    /// Converts the numeric value into `Number` (integer) or `Float`.
+    pub fn from_f64(value: f64) -> Result<Self, anyhow::Error> {
+        if !value.is_finite() {
+            anyhow::bail!("Enum member value must be a finite number: {value}");
+        }
+
+        if value.fract() == 0.0 && value >= i64::MIN as f64 && value < i64::MAX as f64 {
+            Ok(EnumMemberValue::Number(value as i64))
+        } else {
+            Ok(EnumMemberValue::Float(value.to_string()))
+        }
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, EnumMemberValue::String(..))
+    }
+}
+
+impl std::fmt::Display for EnumMemberValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnumMemberValue::String(val) | EnumMemberValue::Float(val) => write!(f, "{val}"),
+            EnumMemberValue::Number(val) => write!(f, "{val}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
+pub struct UnionTypeAnnotation {
+    pub name: String,
+    /// Discriminant property name (eg. `kind`)
+    pub tag: String,
+    pub variants: Vec<UnionVariant>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
+pub struct UnionVariant {
+    pub name: String,
+    /// Discriminant value (eg. `'ok'`)
+    pub value: EnumMemberValue,
+    /// Properties except the discriminant (`None` if the variant has no other properties)
+    pub data: Option<ObjectTypeAnnotation>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
+pub struct HandleTypeAnnotation {
+    pub name: String,
+}
+
+/// Host object interface (eg. `interface Connection extends HostObject { ... }`)
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+pub struct Handle {
+    pub name: String,
+    pub methods: Vec<Method>,
+    /// JSDoc comment of the declaration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
@@ -116,6 +386,9 @@ pub struct RefTypeAnnotation {
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize)]
 pub struct Signal {
     pub name: String,
+    /// Payload type of the signal (eg. `Signal<T>`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_type: Option<TypeAnnotation>,
 }
 
 #[cfg(test)]
@@ -129,7 +402,10 @@ mod tests {
             props: vec![Prop {
                 name: "prop".to_string(),
                 type_annotation: TypeAnnotation::String,
+                optional: false,
+                doc: None,
             }],
+            doc: None,
         });
 
         let t2 = TypeAnnotation::Object(ObjectTypeAnnotation {
@@ -137,7 +413,10 @@ mod tests {
             props: vec![Prop {
                 name: "prop".to_string(),
                 type_annotation: TypeAnnotation::String,
+                optional: false,
+                doc: None,
             }],
+            doc: None,
         });
 
         let t3 = TypeAnnotation::Object(ObjectTypeAnnotation {
@@ -146,12 +425,17 @@ mod tests {
                 Prop {
                     name: "prop".to_string(),
                     type_annotation: TypeAnnotation::String,
+                    optional: false,
+                    doc: None,
                 },
                 Prop {
                     name: "prop2".to_string(),
                     type_annotation: TypeAnnotation::String,
+                    optional: false,
+                    doc: None,
                 },
             ],
+            doc: None,
         });
 
         assert_eq!(t1.to_id(), t2.to_id());
@@ -1,6 +1,6 @@
 use std::collections::{btree_map::Entry as BTreeMapEntry, BTreeMap};
 
-use craby_common::utils::string::camel_case;
+use craby_common::utils::string::{camel_case, pascal_case};
 use indoc::formatdoc;
 use log::debug;
 use template::{cxx_arg_ref, cxx_arg_var};
@@ -8,10 +8,13 @@ use template::{cxx_arg_ref, cxx_arg_var};
 use crate::{
     common::IntoCode,
     constants::specs::RESERVED_ARG_NAME_MODULE,
-    parser::types::{EnumTypeAnnotation, Method, ObjectTypeAnnotation, TypeAnnotation},
+    parser::types::{
+        EnumTypeAnnotation, Handle, HandleTypeAnnotation, Method, ObjectTypeAnnotation,
+        ThreadAffinity, TypeAnnotation, UnionTypeAnnotation,
+    },
     platform::cxx::template::CxxBridgingTemplate,
     types::{CxxModuleName, CxxNamespace, Schema},
-    utils::{calc_deps_order, indent_str},
+    utils::{calc_deps_order, indent_str, with_doc_comment},
 };
 
 #[derive(Debug)]
@@ -45,6 +48,24 @@ pub struct CxxMethod {
     /// }
     /// ```
     pub impl_func: String,
+    /// Method documentation (JSDoc comment of the spec)
+    pub doc: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CxxHostObject {
+    /// Host object class name
+    pub name: String,
+    /// `jsi::HostObject` class definition
+    ///
+    /// ```cpp
+    /// class ConnectionHostObject : public jsi::HostObject {
+    ///   // Implementation here
+    /// };
+    /// ```
+    pub class_def: String,
+    /// Static member function implementations of the host object methods
+    pub method_impls: Vec<String>,
 }
 
 impl TypeAnnotation {
@@ -55,18 +76,27 @@ impl TypeAnnotation {
     /// ```cpp
     /// bool                          // Boolean
     /// double                        // Number
+    /// int32_t                       // Int32
+    /// int64_t                       // Int64
     /// rust::Str                     // String (arguments)
     /// rust::String                  // String
+    /// rust::Vec<uint8_t>            // ArrayBuffer
     /// rust::Vec<double>             // Array<Number>
     /// craby::mymodule::bridging::MyEnum       // Enum
     /// craby::mymodule::bridging::MyStruct     // Object
     /// craby::mymodule::bridging::NullableNumber  // Nullable<Number>
+    /// craby::mymodule::bridging::NumberMap       // Map<Number>
     /// ```
     pub fn as_cxx_type(&self, cxx_ns: &CxxNamespace) -> Result<String, anyhow::Error> {
         let cxx_type = match self {
             TypeAnnotation::Boolean => "bool".to_string(),
             TypeAnnotation::Number => "double".to_string(),
             TypeAnnotation::String => "rust::String".to_string(),
+            TypeAnnotation::Int32 => "int32_t".to_string(),
+            TypeAnnotation::Int64 => "int64_t".to_string(),
+            TypeAnnotation::UInt8 => "uint8_t".to_string(),
+            TypeAnnotation::Float => "float".to_string(),
+            TypeAnnotation::ArrayBuffer => "rust::Vec<uint8_t>".to_string(),
             TypeAnnotation::Array(element_type) => {
                 format!("rust::Vec<{}>", element_type.as_cxx_type(cxx_ns)?)
             }
@@ -76,13 +106,23 @@ impl TypeAnnotation {
             TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => {
                 format!("{cxx_ns}::bridging::{name}")
             }
+            TypeAnnotation::Map(..) | TypeAnnotation::Union(..) => {
+                format!("{cxx_ns}::bridging::{}", self.as_rs_type()?.into_code())
+            }
             TypeAnnotation::Nullable(type_annotation) => {
                 let cxx_struct = match &**type_annotation {
                     TypeAnnotation::Boolean => "NullableBoolean".to_string(),
                     TypeAnnotation::Number => "NullableNumber".to_string(),
                     TypeAnnotation::String => "NullableString".to_string(),
+                    TypeAnnotation::Int32 => "NullableInt32".to_string(),
+                    TypeAnnotation::Int64 => "NullableInt64".to_string(),
+                    TypeAnnotation::UInt8 => "NullableUInt8".to_string(),
+                    TypeAnnotation::Float => "NullableFloat".to_string(),
+                    TypeAnnotation::ArrayBuffer => "NullableArrayBuffer".to_string(),
                     TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => format!("Nullable{}", name),
                     TypeAnnotation::Enum(EnumTypeAnnotation { name, .. }) => format!("Nullable{}", name),
+                    TypeAnnotation::Union(UnionTypeAnnotation { name, .. }) => format!("Nullable{}", name),
+                    TypeAnnotation::Map(..) => self.as_rs_type()?.into_code(),
                     TypeAnnotation::Array(element_type) => match &**element_type {
                         TypeAnnotation::Boolean => "NullableBooleanArray".to_string(),
                         TypeAnnotation::Number=> {
@@ -91,6 +131,9 @@ impl TypeAnnotation {
                         TypeAnnotation::String => {
                             "NullableStringArray".to_string()
                         }
+                        TypeAnnotation::Int32 => "NullableInt32Array".to_string(),
+                        TypeAnnotation::Int64 => "NullableInt64Array".to_string(),
+                        TypeAnnotation::Float => "NullableFloatArray".to_string(),
                         TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => {
                             format!("Nullable{name}Array")
                         }
@@ -132,7 +175,10 @@ impl TypeAnnotation {
     /// ```cpp
     /// false                                 // Boolean
     /// 0.0                                   // Number
+    /// 0                                     // Int32, Int64, UInt8
+    /// 0.0f                                  // Float
     /// rust::String()                        // String
+    /// rust::Vec<uint8_t>()                  // ArrayBuffer
     /// rust::Vec<double>()                   // Array<Number>
     /// MyEnum::FirstMember                   // Enum
     /// craby::mymodule::bridging::MyStruct{} // Object
@@ -141,7 +187,12 @@ impl TypeAnnotation {
         let default_val = match self {
             TypeAnnotation::Boolean => "false".to_string(),
             TypeAnnotation::Number => "0.0".to_string(),
+            TypeAnnotation::Int32 | TypeAnnotation::Int64 | TypeAnnotation::UInt8 => {
+                "0".to_string()
+            }
+            TypeAnnotation::Float => "0.0f".to_string(),
             TypeAnnotation::String => "rust::String()".to_string(),
+            TypeAnnotation::ArrayBuffer => "rust::Vec<uint8_t>()".to_string(),
             TypeAnnotation::Array(element_type) => {
                 format!("rust::Vec<{}>()", element_type.as_cxx_type(cxx_ns)?)
             }
@@ -153,7 +204,7 @@ impl TypeAnnotation {
 
                 format!("{enum_type}::{}", first_member.name)
             }
-            TypeAnnotation::Object(..) => {
+            TypeAnnotation::Object(..) | TypeAnnotation::Map(..) | TypeAnnotation::Union(..) => {
                 let cxx_type = self.as_cxx_type(cxx_ns)?;
                 format!("{cxx_type}{{}}")
             }
@@ -184,6 +235,7 @@ impl TypeAnnotation {
     ///
     /// ```cpp
     /// facebook::react::bridging::fromJs<T>(rt, value, callInvoker)
+    /// craby::mymodule::utils::asInt32(rt, value) // Numeric types (range checked)
     /// ```
     pub fn as_cxx_from_js(
         &self,
@@ -191,12 +243,19 @@ impl TypeAnnotation {
         ident: &str,
     ) -> Result<CxxFromJs, anyhow::Error> {
         let from_js_expr = match self {
+            TypeAnnotation::Int32 => format!("{cxx_ns}::utils::asInt32(rt, {ident})"),
+            TypeAnnotation::Int64 => format!("{cxx_ns}::utils::asInt64(rt, {ident})"),
+            TypeAnnotation::UInt8 => format!("{cxx_ns}::utils::asUInt8(rt, {ident})"),
+            TypeAnnotation::Float => format!("{cxx_ns}::utils::asFloat(rt, {ident})"),
             TypeAnnotation::Boolean
             | TypeAnnotation::Number
             | TypeAnnotation::String
+            | TypeAnnotation::ArrayBuffer
             | TypeAnnotation::Array(..)
+            | TypeAnnotation::Map(..)
             | TypeAnnotation::Enum(..)
             | TypeAnnotation::Object(..)
+            | TypeAnnotation::Union(..)
             | TypeAnnotation::Nullable(..) => format!(
                 "react::bridging::fromJs<{}>(rt, {ident}, callInvoker)",
                 self.as_cxx_type(cxx_ns)?,
@@ -216,19 +275,33 @@ impl TypeAnnotation {
     ///
     /// ```cpp
     /// react::bridging::toJs(rt, value)
+    /// react::bridging::toJs(rt, std::move(value)) // ArrayBuffer (ownership is moved to JS)
+    /// jsi::Object::createFromHostObject(rt, value) // Host object
     /// ```
     pub fn as_cxx_to_js(&self, ident: &str) -> Result<CxxToJs, anyhow::Error> {
         let to_js_expr = match self {
+            TypeAnnotation::ArrayBuffer => {
+                format!("react::bridging::toJs(rt, std::move({}))", ident)
+            }
             TypeAnnotation::Boolean
             | TypeAnnotation::Number
             | TypeAnnotation::String
+            | TypeAnnotation::Int32
+            | TypeAnnotation::Int64
+            | TypeAnnotation::UInt8
+            | TypeAnnotation::Float
             | TypeAnnotation::Array(..)
+            | TypeAnnotation::Map(..)
             | TypeAnnotation::Enum(..)
             | TypeAnnotation::Object(..)
+            | TypeAnnotation::Union(..)
             | TypeAnnotation::Nullable(..) => format!("react::bridging::toJs(rt, {})", ident),
             TypeAnnotation::Promise(..) => {
                 format!("react::bridging::toJs(rt, {})", ident)
             }
+            TypeAnnotation::Handle(..) => {
+                format!("jsi::Object::createFromHostObject(rt, {})", ident)
+            }
             TypeAnnotation::Void => "jsi::Value::undefined()".to_string(),
             _ => {
                 return Err(anyhow::anyhow!(
@@ -240,6 +313,62 @@ impl TypeAnnotation {
 
         Ok(CxxToJs { expr: to_js_expr })
     }
+
+    /// Returns the expression that takes the return value of the FFI function call.
+    ///
+    /// ```cpp
+    /// craby::mymodule::bridging::myFunc(*it_, arg0)
+    /// std::make_shared<craby::mymodule::handles::ConnectionHostObject>(craby::mymodule::bridging::open(*it_, arg0), callInvoker) // Host object
+    /// ```
+    pub fn as_cxx_ret_expr(&self, cxx_ns: &CxxNamespace, call: &str) -> String {
+        match self {
+            // The returned `rust::Box` is owned by the host object and dropped when it is garbage collected
+            TypeAnnotation::Handle(handle_type) => format!(
+                "std::make_shared<{cxx_ns}::handles::{}>({call}, callInvoker)",
+                handle_type.as_cxx_host_object()
+            ),
+            _ => call.to_string(),
+        }
+    }
+}
+
+impl HandleTypeAnnotation {
+    /// Returns the C++ `jsi::HostObject` class name of the host object.
+    ///
+    /// ```cpp
+    /// ConnectionHostObject
+    /// ```
+    pub fn as_cxx_host_object(&self) -> String {
+        format!("{}HostObject", pascal_case(&self.name))
+    }
+}
+
+impl ThreadAffinity {
+    /// Returns the module member that runs the async method (`None` for the JS thread).
+    ///
+    /// ```cpp
+    /// threadPool_  // pool
+    /// serialQueue_ // serial
+    /// ioThread_    // io
+    /// ```
+    pub fn as_cxx_executor(&self) -> Option<String> {
+        match self {
+            ThreadAffinity::Js => None,
+            ThreadAffinity::Pool => Some("threadPool_".to_string()),
+            ThreadAffinity::Serial => Some("serialQueue_".to_string()),
+            ThreadAffinity::Named(name) => Some(format!("{}Thread_", camel_case(name))),
+        }
+    }
+
+    /// Returns the name of the dedicated thread (eg. `serial`, `io`).
+    pub fn as_cxx_thread_name(&self) -> &str {
+        match self {
+            ThreadAffinity::Js => "js",
+            ThreadAffinity::Pool => "pool",
+            ThreadAffinity::Serial => "serial",
+            ThreadAffinity::Named(name) => name,
+        }
+    }
 }
 
 impl Method {
@@ -269,7 +398,7 @@ impl Method {
     ///   } catch (const jsi::JSError &err) {
     ///     throw err;
     ///   } catch (const std::exception &err) {
-    ///     throw jsi::JSError(rt, craby::calculator::utils::errorMessage(err));
+    ///     throw jsi::JSError(rt, craby::calculator::utils::makeJsError(rt, craby::calculator::utils::errorMessage(err)));
     ///   }
     /// }
     /// ```
@@ -278,37 +407,314 @@ impl Method {
         cxx_ns: &CxxNamespace,
         cxx_mod: &CxxModuleName,
     ) -> Result<CxxMethod, anyhow::Error> {
+        let fn_name = camel_case(&self.name);
+        let args_count = self.params.len();
+
+        // ```cpp
+        // MethodMetadata{{1, &CxxMyTestModule::myFunc}}
+        // ```
+        let metadata = formatdoc! {
+            r#"
+            MethodMetadata{{{args_count}, &{cxx_mod}::{fn_name}}}"#,
+        };
+
+        let body = indent_str(&self.try_into_cxx_body(cxx_ns)?, 2);
+        let impl_func = formatdoc! {
+            r#"
+            jsi::Value {cxx_mod}::{fn_name}(jsi::Runtime &rt,
+                                            react::TurboModule &turboModule,
+                                            const jsi::Value args[],
+                                            size_t count) {{
+              auto &thisModule = static_cast<{cxx_mod} &>(turboModule);
+              auto callInvoker = thisModule.callInvoker_;
+              auto it_ = thisModule.module_;
+
+            {body}
+            }}"#,
+        };
+
+        Ok(CxxMethod {
+            name: self.name.clone(),
+            metadata,
+            impl_func,
+            doc: self.as_cxx_doc(),
+        })
+    }
+
+    /// Converts the host object method to the static member function of the host object class.
+    ///
+    /// # Generated Code
+    ///
+    /// ```cpp
+    /// inline jsi::Value ConnectionHostObject::callQuery(jsi::Runtime &rt,
+    ///                                               std::shared_ptr<craby::mymodule::bridging::ConnectionHandle> it_,
+    ///                                               std::shared_ptr<react::CallInvoker> callInvoker,
+    ///                                               const jsi::Value args[],
+    ///                                               size_t count) {
+    ///   try {
+    ///     // Same as the module methods
+    ///   }
+    /// }
+    /// ```
+    pub fn as_cxx_handle_method(
+        &self,
+        cxx_ns: &CxxNamespace,
+        handle_type: &HandleTypeAnnotation,
+    ) -> Result<String, anyhow::Error> {
+        let fn_name = format!("call{}", pascal_case(&self.name));
+        let host_object = handle_type.as_cxx_host_object();
+        let handle = handle_type.as_rs_handle_type();
+        let body = indent_str(&self.try_into_cxx_body(cxx_ns)?, 2);
+
+        Ok(formatdoc! {
+            r#"
+            inline jsi::Value {host_object}::{fn_name}(jsi::Runtime &rt,
+                                                      std::shared_ptr<{cxx_ns}::bridging::{handle}> it_,
+                                                      std::shared_ptr<react::CallInvoker> callInvoker,
+                                                      const jsi::Value args[],
+                                                      size_t count) {{
+            {body}
+            }}"#,
+        })
+    }
+
+    /// Returns the method body that converts the arguments, invokes the FFI function and converts the result.
+    ///
+    /// `rt`, `args`, `count`, `callInvoker` and `it_` (the Rust receiver) must be in the scope.
+    fn try_into_cxx_body(&self, cxx_ns: &CxxNamespace) -> Result<String, anyhow::Error> {
         let fn_name = camel_case(&self.name);
         // ["arg0", "arg1", "arg2"]
         let mut args = Vec::with_capacity(self.params.len() + 1);
         // ["auto arg0 = facebook::react::bridging::fromJs<T>(rt, value, callInvoker)", "..."]
         let mut args_decls = Vec::with_capacity(self.params.len());
+        // Values that must outlive the borrowed arguments in the async task (eg. `arg0$raw`)
+        let mut owned_args = vec![];
+        // Declarations that depend on the promise (eg. `AbortSignal` registration)
+        let mut promise_decls = vec![];
+        // `async fn` methods (`@async`) borrow the arguments in this scope only (copied by Rust)
+        let is_async = self.is_async() && !self.async_fn;
 
         for (idx, param) in self.params.iter().enumerate() {
-            let arg_ref = cxx_arg_ref(idx);
+            let mut arg_ref = cxx_arg_ref(idx);
             let arg_var = cxx_arg_var(idx);
 
+            // Optional arguments can be omitted by the caller (treated as `undefined`)
+            if param.optional {
+                let value_var = format!("{arg_var}$value");
+                args_decls.push(format!(
+                    "auto {value_var} = {idx} < count ? jsi::Value(rt, {arg_ref}) : jsi::Value::undefined();"
+                ));
+                arg_ref = value_var;
+            }
+
             // `rust::Str` holds a reference to `std::string`.
             // To avoid dangling pointers, the converted `std::string` is retained within the scope for the lifetime of the reference.
-            let from_js = if let TypeAnnotation::String = &param.type_annotation {
-                // Capture the converted `std::string` within the scope of the reference
-                let str_var = format!("{arg_var}$raw");
-                args_decls.push(format!("auto {str_var} = {arg_ref}.asString(rt).utf8(rt);",));
+            let from_js = match &param.type_annotation {
+                // Async methods run on a worker thread after this scope ends,
+                // so the converted `std::string` is owned by the task instead.
+                TypeAnnotation::String if is_async => {
+                    let str_var = format!("{arg_var}$raw");
+                    args_decls.push(format!(
+                        "auto {str_var} = std::make_shared<std::string>({arg_ref}.asString(rt).utf8(rt));",
+                    ));
+                    owned_args.push(str_var.clone());
+
+                    format!("rust::Str({str_var}->data(), {str_var}->size())")
+                }
+                TypeAnnotation::String => {
+                    // Capture the converted `std::string` within the scope of the reference
+                    let str_var = format!("{arg_var}$raw");
+                    args_decls.push(format!("auto {str_var} = {arg_ref}.asString(rt).utf8(rt);",));
 
-                // Convert the `std::string` to `rust::Str`
-                format!("rust::Str({str_var}.data(), {str_var}.size())")
-            } else {
-                param.type_annotation.as_cxx_from_js(cxx_ns, &arg_ref)?.expr
+                    // Convert the `std::string` to `rust::Str`
+                    format!("rust::Str({str_var}.data(), {str_var}.size())")
+                }
+                // `rust::Slice` borrows the `ArrayBuffer` memory directly (zero-copy).
+                // Promise methods run on a worker thread after the JS value may be collected,
+                // so the bytes are copied into a `rust::Vec` that is owned by the task instead.
+                TypeAnnotation::ArrayBuffer if is_async => {
+                    let buf_var = format!("{arg_var}$raw");
+                    args_decls.push(format!(
+                        "auto {buf_var} = std::make_shared<rust::Vec<uint8_t>>(react::bridging::fromJs<rust::Vec<uint8_t>>(rt, {arg_ref}, callInvoker));",
+                    ));
+                    owned_args.push(buf_var.clone());
+
+                    format!("rust::Slice<const uint8_t>({buf_var}->data(), {buf_var}->size())")
+                }
+                TypeAnnotation::ArrayBuffer => format!(
+                    "react::bridging::fromJs<rust::Slice<const uint8_t>>(rt, {arg_ref}, callInvoker)"
+                ),
+                // The JS function is retained by the `CallbackManager` and the registered id is passed to Rust.
+                // Invocations from Rust are scheduled on the JS thread through the `CallInvoker`.
+                TypeAnnotation::Callback(arg_type) => {
+                    let fn_var = format!("{arg_var}$fn");
+                    args_decls.push(format!(
+                        "auto {fn_var} = {cxx_ns}::utils::makeSharedFunction(rt, {arg_ref}, callInvoker);"
+                    ));
+
+                    let call_stmts = match &**arg_type {
+                        TypeAnnotation::Void => format!("{fn_var}->call(rt);"),
+                        arg_type => formatdoc! {
+                            r#"
+                            auto arg = react::bridging::toJs(rt, *std::static_pointer_cast<{payload_type}>(payload));
+                            {fn_var}->call(rt, arg);"#,
+                            payload_type = arg_type.as_cxx_type(cxx_ns)?,
+                        },
+                    };
+                    let call_stmts = indent_str(&call_stmts, 6);
+
+                    formatdoc! {
+                        r#"
+                        {cxx_ns}::callbacks::CallbackManager::getInstance().registerCallback(
+                          reinterpret_cast<uintptr_t>(&thisModule),
+                          [callInvoker, {fn_var}](std::shared_ptr<void> payload) {{
+                            callInvoker->invokeAsync([{fn_var}, payload](jsi::Runtime &rt) {{
+                        {call_stmts}
+                            }});
+                          }})"#,
+                    }
+                }
+                // The cancellation token is registered with the promise and the registered id is passed to Rust.
+                // The promise is rejected with `AbortError` when the signal is aborted or the module is invalidated.
+                TypeAnnotation::AbortSignal => {
+                    promise_decls.push(format!(
+                        "auto {arg_var} = {cxx_ns}::utils::registerAbortSignal(rt, {arg_ref}, reinterpret_cast<uintptr_t>(&thisModule), promise);"
+                    ));
+                    args.push(arg_var);
+                    continue;
+                }
+                _ => param.type_annotation.as_cxx_from_js(cxx_ns, &arg_ref)?.expr,
             };
             args.push(arg_var.clone());
             args_decls.push(format!("auto {arg_var} = {from_js};"));
         }
 
-        let invoke_stmts = match &self.ret_type {
-            TypeAnnotation::Promise(resolve_type) => {
-                let mut bind_args = Vec::with_capacity(args.len() + 2);
+        let executor = self
+            .thread
+            .as_ref()
+            .unwrap_or(&ThreadAffinity::Pool)
+            .as_cxx_executor();
+        let invoke_stmts = match (&self.ret_type, executor) {
+            // Register the promise and spawn the future on the async executor (`@async`)
+            (TypeAnnotation::Promise(resolve_type), _) if self.async_fn => {
+                args.insert(0, format!("*{}", RESERVED_ARG_NAME_MODULE));
+                args.push("promiseId".to_string());
+                let fn_args = args.join(", ");
+
+                let (resolve_param, resolve_stmts) = if let TypeAnnotation::Void = &**resolve_type {
+                    (
+                        "std::shared_ptr<void>",
+                        "promise->resolve([](jsi::Runtime &) -> jsi::Value { return jsi::Value::undefined(); });"
+                            .to_string(),
+                    )
+                } else {
+                    (
+                        "std::shared_ptr<void> value",
+                        formatdoc! {
+                            r#"
+                        promise->resolve([value](jsi::Runtime &rt) -> jsi::Value {{
+                          auto &ret = *std::static_pointer_cast<{resolve_type}>(value);
+                          return {to_js};
+                        }});"#,
+                            resolve_type = resolve_type.as_cxx_type(cxx_ns)?,
+                            to_js = resolve_type.as_cxx_to_js("ret")?.expr,
+                        },
+                    )
+                };
+
+                let resolve_stmts = indent_str(&resolve_stmts, 4);
+                let promise_decls = [
+                    format!(
+                        "auto promise = std::make_shared<{cxx_ns}::utils::Promise>(callInvoker);"
+                    ),
+                    "auto promiseValue = promise->create(rt);".to_string(),
+                ]
+                .into_iter()
+                .chain(promise_decls)
+                .collect::<Vec<_>>()
+                .join("\n");
+
+                // Failed calls reject through the manager to release the registered promise
+                formatdoc! {
+                    r#"
+                    {promise_decls}
+                    auto promiseId = {cxx_ns}::promises::PromiseManager::getInstance().registerPromise(
+                      [promise]({resolve_param}) {{
+                    {resolve_stmts}
+                      }},
+                      [promise](std::string message) {{ promise->reject(std::move(message)); }});
+
+                    try {{
+                      {cxx_ns}::bridging::{fn_name}({fn_args});
+                    }} catch (const jsi::JSError &err) {{
+                      auto message = err.getMessage();
+                      {cxx_ns}::promises::PromiseManager::getInstance().reject(promiseId, rust::Str(message.data(), message.size()));
+                    }} catch (const std::exception &err) {{
+                      auto message = {cxx_ns}::utils::errorMessage(err);
+                      {cxx_ns}::promises::PromiseManager::getInstance().reject(promiseId, rust::Str(message.data(), message.size()));
+                    }}
+
+                    return promiseValue;"#,
+                }
+            }
+            // Settle the promise on the JS thread without the worker thread (`@thread js`)
+            (TypeAnnotation::Promise(resolve_type), None) => {
+                args.insert(0, format!("*{}", RESERVED_ARG_NAME_MODULE));
+                let fn_args = args.join(", ");
+                let call = format!("{cxx_ns}::bridging::{fn_name}({fn_args})");
+
+                let ret_stmts = if let TypeAnnotation::Void = &**resolve_type {
+                    formatdoc! {
+                        r#"
+                        {call};
+                        promise->resolve([](jsi::Runtime &) -> jsi::Value {{ return jsi::Value::undefined(); }});"#,
+                    }
+                } else {
+                    formatdoc! {
+                        r#"
+                        auto ret = {ret_expr};
+                        promise->resolve([ret](jsi::Runtime &rt) mutable -> jsi::Value {{ return {to_js}; }});"#,
+                        ret_expr = resolve_type.as_cxx_ret_expr(cxx_ns, &call),
+                        to_js = resolve_type.as_cxx_to_js("ret")?.expr,
+                    }
+                };
+
+                let ret_stmts = indent_str(&ret_stmts, 2);
+                let promise_decls = [
+                    format!(
+                        "auto promise = std::make_shared<{cxx_ns}::utils::Promise>(callInvoker);"
+                    ),
+                    "auto promiseValue = promise->create(rt);".to_string(),
+                ]
+                .into_iter()
+                .chain(promise_decls)
+                .collect::<Vec<_>>()
+                .join("\n");
+
+                formatdoc! {
+                    r#"
+                    {promise_decls}
+
+                    try {{
+                    {ret_stmts}
+                    }} catch (const jsi::JSError &err) {{
+                      promise->reject(err.getMessage());
+                    }} catch (const std::exception &err) {{
+                      promise->reject({cxx_ns}::utils::errorMessage(err));
+                    }}
+
+                    return promiseValue;"#,
+                }
+            }
+            (TypeAnnotation::Promise(resolve_type), Some(executor)) => {
+                let mut bind_args = Vec::with_capacity(args.len() + 3);
                 bind_args.push(RESERVED_ARG_NAME_MODULE.to_string());
                 bind_args.push("promise".to_string());
+                // The host object is created with the `CallInvoker` in the worker thread
+                if let TypeAnnotation::Handle(..) = &**resolve_type {
+                    bind_args.push("callInvoker".to_string());
+                }
+                bind_args.extend(owned_args);
                 bind_args.extend(args.clone());
 
                 args.insert(0, format!("*{}", RESERVED_ARG_NAME_MODULE));
@@ -318,41 +724,121 @@ impl Method {
                     formatdoc! {
                         r#"
                         {cxx_ns}::bridging::{fn_name}({fn_args});
-                        promise.resolve();
-                        "#,
+                        promise->resolve([](jsi::Runtime &) -> jsi::Value {{ return jsi::Value::undefined(); }});"#,
                     }
                 } else {
                     formatdoc! {
                         r#"
-                        auto ret = {cxx_ns}::bridging::{fn_name}({fn_args});
-                        promise.resolve(ret);
-                        "#,
+                        auto ret = {ret_expr};
+                        promise->resolve([ret](jsi::Runtime &rt) mutable -> jsi::Value {{ return {to_js}; }});"#,
+                        ret_expr = resolve_type.as_cxx_ret_expr(cxx_ns, &format!("{cxx_ns}::bridging::{fn_name}({fn_args})")),
+                        to_js = resolve_type.as_cxx_to_js("ret")?.expr,
                     }
                 };
 
                 let bind_args = bind_args.join(", ");
                 let ret_stmts = indent_str(&ret_stmts, 4);
-                let ret_type = resolve_type.as_cxx_type(cxx_ns)?;
-                let ret = self.ret_type.as_cxx_to_js("promise")?.expr;
-
-                // Create a promise object and invoke the FFI function in a separate thread
+                let promise_decls = [
+                    format!(
+                        "auto promise = std::make_shared<{cxx_ns}::utils::Promise>(callInvoker);"
+                    ),
+                    "auto promiseValue = promise->create(rt);".to_string(),
+                ]
+                .into_iter()
+                .chain(promise_decls)
+                .collect::<Vec<_>>()
+                .join("\n");
+
+                // Create a promise object and invoke the FFI function in a separate thread.
+                // The promise is rejected on the JS thread with the structured error (if any),
+                // or with `TASK_REJECTED` if the worker pool rejects the task (eg. the queue is full).
                 formatdoc! {
                     r#"
-                    react::AsyncPromise<{ret_type}> promise(rt, callInvoker);
+                    {promise_decls}
 
-                    thisModule.threadPool_->enqueue([{bind_args}]() mutable {{
+                    auto task = [{bind_args}]() mutable {{
                       try {{
                     {ret_stmts}
                       }} catch (const jsi::JSError &err) {{
-                        promise.reject(err.getMessage());
+                        promise->reject(err.getMessage());
+                      }} catch (const std::exception &err) {{
+                        promise->reject({cxx_ns}::utils::errorMessage(err));
+                      }}
+                    }};
+
+                    if (!thisModule.{executor}->enqueue(std::move(task), reinterpret_cast<uintptr_t>(&thisModule))) {{
+                      promise->reject({cxx_ns}::utils::rejectedTaskError());
+                    }}
+
+                    return promiseValue;"#,
+                }
+            }
+            (TypeAnnotation::Stream(item_type), Some(executor)) => {
+                let mut bind_args = Vec::with_capacity(args.len() + 3);
+                bind_args.push(RESERVED_ARG_NAME_MODULE.to_string());
+                bind_args.push("stream".to_string());
+                bind_args.push("streamId".to_string());
+                bind_args.extend(owned_args);
+                bind_args.extend(args.clone());
+
+                args.insert(0, format!("*{}", RESERVED_ARG_NAME_MODULE));
+                args.push("streamId".to_string());
+                let fn_args = args.join(", ");
+
+                let bind_args = bind_args.join(", ");
+                let item_type = item_type.as_cxx_type(cxx_ns)?;
+
+                // The producer is blocked for the whole stream life, so the streams of the worker pool
+                // run on a dedicated thread to keep the pool workers for the other methods.
+                let spawn_stmts = match self.thread.as_ref().unwrap_or(&ThreadAffinity::Pool) {
+                    ThreadAffinity::Pool => formatdoc! {
+                        r#"
+                        try {{
+                          std::thread(std::move(task)).detach();
+                        }} catch (const std::system_error &) {{
+                          stream->fail({cxx_ns}::utils::rejectedTaskError());
+                        }}"#,
+                    },
+                    _ => formatdoc! {
+                        r#"
+                        if (!thisModule.{executor}->enqueue(std::move(task), reinterpret_cast<uintptr_t>(&thisModule))) {{
+                          stream->fail({cxx_ns}::utils::rejectedTaskError());
+                        }}"#,
+                    },
+                };
+
+                // Register the stream and produce the items in a separate thread.
+                // The producer is blocked by the stream buffer until JS pulls the next item (backpressure).
+                formatdoc! {
+                    r#"
+                    auto stream = std::make_shared<{cxx_ns}::streams::Stream>();
+                    auto streamId = {cxx_ns}::streams::StreamManager::getInstance().registerStream(
+                      reinterpret_cast<uintptr_t>(&thisModule), stream);
+
+                    auto task = [{bind_args}]() mutable {{
+                      try {{
+                        {cxx_ns}::bridging::{fn_name}({fn_args});
+                      }} catch (const jsi::JSError &err) {{
+                        stream->fail(err.getMessage());
                       }} catch (const std::exception &err) {{
-                        promise.reject({cxx_ns}::utils::errorMessage(err));
+                        stream->fail({cxx_ns}::utils::errorMessage(err));
                       }}
-                    }});
 
-                    return {ret};"#,
+                      // Finish the stream if the Rust handle has already been dropped
+                      stream->release();
+                    }};
+
+                    {spawn_stmts}
+
+                    return {cxx_ns}::utils::makeAsyncIterable(
+                      rt, callInvoker, stream, [](jsi::Runtime &rt, std::shared_ptr<void> item) -> jsi::Value {{
+                        return react::bridging::toJs(rt, *std::static_pointer_cast<{item_type}>(item));
+                      }});"#,
                 }
             }
+            (TypeAnnotation::Stream(..), None) => {
+                anyhow::bail!("Stream methods cannot run on the JS thread")
+            }
             _ => {
                 // Invoke the FFI function synchronously and return the result
                 //
@@ -362,10 +848,14 @@ impl Method {
                 // ```
                 args.insert(0, format!("*{RESERVED_ARG_NAME_MODULE}"));
                 let fn_args = args.join(", ");
+                let call = format!("{cxx_ns}::bridging::{fn_name}({fn_args})");
                 let ret_stmts = if let TypeAnnotation::Void = &self.ret_type {
-                    format!("{cxx_ns}::bridging::{fn_name}({fn_args});")
+                    format!("{call};")
                 } else {
-                    format!("auto ret = {cxx_ns}::bridging::{fn_name}({fn_args});")
+                    format!(
+                        "auto ret = {};",
+                        self.ret_type.as_cxx_ret_expr(cxx_ns, &call)
+                    )
                 };
 
                 formatdoc! {
@@ -380,45 +870,215 @@ impl Method {
 
         let args_decls = args_decls.join("\n");
         let args_count = self.params.len();
+        let required_args_count = self.params.iter().filter(|p| !p.optional).count();
 
         // ```cpp
-        // MethodMetadata{{1, &CxxMyTestModule::myFunc}}
+        // if (2 != count) {
+        //   throw jsi::JSError(rt, "Expected 2 arguments");
+        // }
+        //
+        // // With optional arguments
+        // if (1 > count || 2 < count) {
+        //   throw jsi::JSError(rt, "Expected 1 to 2 arguments");
+        // }
         // ```
-        let metadata = formatdoc! {
-            r#"
-            MethodMetadata{{{args_count}, &{cxx_mod}::{fn_name}}}"#,
+        let count_cond = if required_args_count == args_count {
+            format!("{args_count} != count")
+        } else {
+            format!("{required_args_count} > count || {args_count} < count")
+        };
+        let count_err = if required_args_count == args_count {
+            format!(
+                "Expected {args_count} argument{}",
+                if args_count > 1 { "s" } else { "" }
+            )
+        } else {
+            format!("Expected {required_args_count} to {args_count} arguments")
         };
 
-        let invoke_stmts = indent_str([args_decls, invoke_stmts].join("\n").trim(), 4);
-        let impl_func = formatdoc! {
+        let invoke_stmts = indent_str([args_decls, invoke_stmts].join("\n").trim(), 2);
+        // Structured errors (`@throws` errors and panics) are thrown with `code` and `details`
+        let js_error =
+            format!("{cxx_ns}::utils::makeJsError(rt, {cxx_ns}::utils::errorMessage(err))");
+        Ok(formatdoc! {
             r#"
-            jsi::Value {cxx_mod}::{fn_name}(jsi::Runtime &rt,
-                                            react::TurboModule &turboModule,
-                                            const jsi::Value args[],
-                                            size_t count) {{
-              auto &thisModule = static_cast<{cxx_mod} &>(turboModule);
-              auto callInvoker = thisModule.callInvoker_;
-              auto it_ = thisModule.module_;
-
-              try {{
-                if ({args_count} != count) {{
-                  throw jsi::JSError(rt, "Expected {args_count} argument{plural}");
-                }}
+            try {{
+              if ({count_cond}) {{
+                throw jsi::JSError(rt, "{count_err}");
+              }}
 
             {invoke_stmts}
-              }} catch (const jsi::JSError &err) {{
-                throw err;
-              }} catch (const std::exception &err) {{
-                throw jsi::JSError(rt, {cxx_ns}::utils::errorMessage(err));
-              }}
+            }} catch (const jsi::JSError &err) {{
+              throw err;
+            }} catch (const std::exception &err) {{
+              throw jsi::JSError(rt, {js_error});
             }}"#,
-            plural = if args_count > 1 { "s" } else { "" },
+        })
+    }
+
+    /// Returns the method documentation with the `@param` tags of the documented parameters.
+    ///
+    /// ```cpp
+    /// /// Multiplies two numbers.
+    /// ///
+    /// /// @param a The first number.
+    /// ```
+    pub fn as_cxx_doc(&self) -> Option<String> {
+        let param_docs = self
+            .params
+            .iter()
+            .filter_map(|param| {
+                param
+                    .doc
+                    .as_ref()
+                    .map(|doc| format!("@param {} {}", param.name, doc))
+            })
+            .collect::<Vec<_>>();
+
+        let docs = [
+            self.doc.clone(),
+            (!param_docs.is_empty()).then(|| param_docs.join("\n")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        (!docs.is_empty()).then(|| docs.join("\n\n"))
+    }
+}
+
+impl Handle {
+    /// Generates the `jsi::HostObject` class that owns the Rust handle of the host object.
+    ///
+    /// The handle is dropped when the host object is garbage collected
+    /// (and the methods that are retained by JS are released).
+    ///
+    /// # Generated Code
+    ///
+    /// ```cpp
+    /// class ConnectionHostObject : public jsi::HostObject {
+    /// public:
+    ///   ConnectionHostObject(rust::Box<craby::mymodule::bridging::ConnectionHandle> handle,
+    ///                        std::shared_ptr<react::CallInvoker> callInvoker)
+    ///       : handle_(std::shared_ptr<craby::mymodule::bridging::ConnectionHandle>(
+    ///           handle.into_raw(),
+    ///           [](craby::mymodule::bridging::ConnectionHandle *ptr) { rust::Box<craby::mymodule::bridging::ConnectionHandle>::from_raw(ptr); })),
+    ///         callInvoker_(std::move(callInvoker)) {}
+    ///
+    ///   jsi::Value get(jsi::Runtime &rt, const jsi::PropNameID &name) override {
+    ///     auto prop = name.utf8(rt);
+    ///
+    ///     if (prop == "query") {
+    ///       return jsi::Function::createFromHostFunction(
+    ///         rt, name, 1,
+    ///         [it_ = handle_, callInvoker = callInvoker_](jsi::Runtime &rt, const jsi::Value &, const jsi::Value *args, size_t count) -> jsi::Value {
+    ///           return callQuery(rt, it_, callInvoker, args, count);
+    ///         });
+    ///     }
+    ///
+    ///     return jsi::Value::undefined();
+    ///   }
+    ///
+    ///   // getPropertyNames, method declarations and members...
+    /// };
+    /// ```
+    pub fn as_cxx_host_object(
+        &self,
+        cxx_ns: &CxxNamespace,
+    ) -> Result<CxxHostObject, anyhow::Error> {
+        let handle_type = self.as_handle_type();
+        let host_object = handle_type.as_cxx_host_object();
+        let handle = format!("{cxx_ns}::bridging::{}", handle_type.as_rs_handle_type());
+
+        let props = self
+            .methods
+            .iter()
+            .map(|method| {
+                formatdoc! {
+                    r#"
+                    if (prop == "{fn_name}") {{
+                      return jsi::Function::createFromHostFunction(
+                        rt, name, {args_count},
+                        [it_ = handle_, callInvoker = callInvoker_](jsi::Runtime &rt, const jsi::Value &, const jsi::Value *args, size_t count) -> jsi::Value {{
+                          return call{pascal_name}(rt, it_, callInvoker, args, count);
+                        }});
+                    }}"#,
+                    fn_name = camel_case(&method.name),
+                    pascal_name = pascal_case(&method.name),
+                    args_count = method.params.len(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let prop_names = self
+            .methods
+            .iter()
+            .map(|method| {
+                format!(
+                    "names.push_back(jsi::PropNameID::forAscii(rt, \"{}\"));",
+                    camel_case(&method.name)
+                )
+            })
+            .collect::<Vec<_>>();
+        let method_decls = self
+            .methods
+            .iter()
+            .map(|method| {
+                formatdoc! {
+                    r#"
+                    static jsi::Value call{pascal_name}(jsi::Runtime &rt,
+                        std::shared_ptr<{handle}> it_,
+                        std::shared_ptr<react::CallInvoker> callInvoker,
+                        const jsi::Value args[], size_t count);"#,
+                    pascal_name = pascal_case(&method.name),
+                }
+            })
+            .collect::<Vec<_>>();
+        let method_impls = self
+            .methods
+            .iter()
+            .map(|method| method.as_cxx_handle_method(cxx_ns, &handle_type))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let props = indent_str(&props.join("\n\n"), 4);
+        let prop_names = indent_str(&prop_names.join("\n"), 4);
+        let method_decls = indent_str(&method_decls.join("\n\n"), 2);
+        let class_def = formatdoc! {
+            r#"
+            class {host_object} : public jsi::HostObject {{
+            public:
+              {host_object}(rust::Box<{handle}> handle,
+                  std::shared_ptr<react::CallInvoker> callInvoker)
+                  : handle_(std::shared_ptr<{handle}>(
+                      handle.into_raw(),
+                      []({handle} *ptr) {{ rust::Box<{handle}>::from_raw(ptr); }})),
+                    callInvoker_(std::move(callInvoker)) {{}}
+
+              jsi::Value get(jsi::Runtime &rt, const jsi::PropNameID &name) override {{
+                auto prop = name.utf8(rt);
+
+            {props}
+
+                return jsi::Value::undefined();
+              }}
+
+              std::vector<jsi::PropNameID> getPropertyNames(jsi::Runtime &rt) override {{
+                std::vector<jsi::PropNameID> names;
+            {prop_names}
+                return names;
+              }}
+
+            private:
+            {method_decls}
+
+              std::shared_ptr<{handle}> handle_;
+              std::shared_ptr<react::CallInvoker> callInvoker_;
+            }};"#,
         };
 
-        Ok(CxxMethod {
-            name: self.name.clone(),
-            metadata,
-            impl_func,
+        Ok(CxxHostObject {
+            name: host_object,
+            class_def: with_doc_comment(self.doc.as_deref(), class_def),
+            method_impls,
         })
     }
 }
@@ -459,7 +1119,7 @@ impl Schema {
         let cxx_ns = CxxNamespace::from(project_name);
         let mut bridging_templates = BTreeMap::new();
         let mut enum_bridging_templates = BTreeMap::new();
-        let mut nullable_bridging_templates = self.collect_nullable_types(project_name)?;
+        let mut bridge_type_templates = self.collect_bridge_types(project_name)?;
 
         for type_annotation in &self.aliases {
             let alias_spec = type_annotation.as_object().unwrap();
@@ -490,19 +1150,19 @@ impl Schema {
             }
 
             if let Some(template) =
-                nullable_bridging_templates.remove(&format!("{cxx_ns}::bridging::{name}"))
+                bridge_type_templates.remove(&format!("{cxx_ns}::bridging::{name}"))
             {
                 ordered_templates.push(template);
             }
         });
 
         ordered_templates.extend(bridging_templates.into_values());
-        ordered_templates.extend(nullable_bridging_templates.into_values());
+        ordered_templates.extend(bridge_type_templates.into_values());
 
         Ok(ordered_templates)
     }
 
-    /// Collects all nullable types from schema to generate bridging templates.
+    /// Collects all bridge types (nullable, map and union types) from schema to generate bridging templates.
     ///
     /// # Generated Code
     ///
@@ -510,7 +1170,7 @@ impl Schema {
     /// template <>
     /// struct Bridging<craby::mymodule::bridging::NullableNumber> {
     ///   static craby::mymodule::bridging::NullableNumber fromJs(jsi::Runtime &rt, const jsi::Value& value, std::shared_ptr<CallInvoker> callInvoker) {
-    ///     if (value.isNull()) {
+    ///     if (value.isNull() || value.isUndefined()) {
     ///       return craby::mymodule::bridging::NullableNumber{true, 0.0};
     ///     }
     ///
@@ -529,63 +1189,70 @@ impl Schema {
     ///   }
     /// };
     /// ```
-    pub fn collect_nullable_types(
+    pub fn collect_bridge_types(
         &self,
         project_name: &str,
     ) -> Result<BTreeMap<String, String>, anyhow::Error> {
         let cxx_ns = CxxNamespace::from(project_name);
         let mut templates = BTreeMap::new();
 
+        let mut collect = |type_annotation: &TypeAnnotation| -> Result<(), anyhow::Error> {
+            for bridge_type in type_annotation.bridge_types() {
+                let key = bridge_type.as_cxx_type(&cxx_ns)?;
+                if let BTreeMapEntry::Vacant(e) = templates.entry(key) {
+                    let bridging_template = match bridge_type {
+                        TypeAnnotation::Nullable(inner_type_annotation) => {
+                            CxxBridgingTemplate::try_into_nullable_template(
+                                &cxx_ns,
+                                bridge_type,
+                                inner_type_annotation,
+                            )?
+                        }
+                        TypeAnnotation::Map(value_type_annotation) => {
+                            CxxBridgingTemplate::try_into_map_template(
+                                &cxx_ns,
+                                bridge_type,
+                                value_type_annotation,
+                            )?
+                        }
+                        TypeAnnotation::Union(union_type) => {
+                            CxxBridgingTemplate::try_into_union_template(
+                                &cxx_ns,
+                                bridge_type,
+                                union_type,
+                            )?
+                        }
+                        _ => unreachable!(),
+                    };
+                    e.insert(bridging_template.into_code());
+                }
+            }
+            Ok(())
+        };
+
         for method in &self.methods {
             for param in &method.params {
-                if let nullable_type @ TypeAnnotation::Nullable(inner_type_annotation) =
-                    &param.type_annotation
-                {
-                    let key = nullable_type.as_cxx_type(&cxx_ns)?;
-                    if let BTreeMapEntry::Vacant(e) = templates.entry(key) {
-                        let bridging_template = CxxBridgingTemplate::try_into_nullable_template(
-                            &cxx_ns,
-                            nullable_type,
-                            inner_type_annotation,
-                        )?
-                        .into_code();
-                        e.insert(bridging_template);
-                    }
-                }
+                collect(&param.type_annotation)?;
             }
+            collect(&method.ret_type)?;
+        }
 
-            if let nullable_type @ TypeAnnotation::Nullable(inner_type_annotation) =
-                &method.ret_type
-            {
-                let key = nullable_type.as_cxx_type(&cxx_ns)?;
-                if let BTreeMapEntry::Vacant(e) = templates.entry(key) {
-                    let bridging_template = CxxBridgingTemplate::try_into_nullable_template(
-                        &cxx_ns,
-                        nullable_type,
-                        inner_type_annotation,
-                    )?
-                    .into_code();
-                    e.insert(bridging_template);
-                }
+        for signal in &self.signals {
+            if let Some(payload_type) = &signal.payload_type {
+                collect(payload_type)?;
+            }
+        }
+
+        for method in self.handles.iter().flat_map(|handle| &handle.methods) {
+            for param in &method.params {
+                collect(&param.type_annotation)?;
             }
+            collect(&method.ret_type)?;
         }
 
         for type_annotation in &self.aliases {
             for prop in &type_annotation.as_object().unwrap().props {
-                if let nullable_type @ TypeAnnotation::Nullable(inner_type_annotation) =
-                    &prop.type_annotation
-                {
-                    let key = nullable_type.as_cxx_type(&cxx_ns)?;
-                    if let BTreeMapEntry::Vacant(e) = templates.entry(key) {
-                        let bridging_template = CxxBridgingTemplate::try_into_nullable_template(
-                            &cxx_ns,
-                            nullable_type,
-                            inner_type_annotation,
-                        )?
-                        .into_code();
-                        e.insert(bridging_template);
-                    }
-                }
+                collect(&prop.type_annotation)?;
             }
         }
 
@@ -601,7 +1268,7 @@ pub mod template {
         common::IntoCode,
         parser::types::{
             EnumMemberValue as ParserEnumMemberValue, EnumTypeAnnotation, ObjectTypeAnnotation,
-            TypeAnnotation,
+            TypeAnnotation, UnionTypeAnnotation,
         },
         types::CxxNamespace,
         utils::indent_str,
@@ -711,11 +1378,27 @@ pub mod template {
 
                 // ```cpp
                 // obj.setProperty(rt, "name", _obj$name);
+                //
+                // // Optional properties are omitted when the value is null
+                // if (!value.name.null) {
+                //   obj.setProperty(rt, "name", _obj$name);
+                // }
                 // ```
                 let set_prop = format!(
                     "obj.setProperty(rt, \"{}\", {});",
                     prop.name, converted_ident
                 );
+                let set_prop = if prop.optional {
+                    formatdoc! {
+                        r#"
+                        if (!value.{field}.null) {{
+                          {set_prop}
+                        }}"#,
+                        field = snake_case(&prop.name),
+                    }
+                } else {
+                    set_prop
+                };
 
                 // ```cpp
                 // auto _obj$name = react::bridging::fromJs<T>(rt, value.name, callInvoker);
@@ -805,10 +1488,7 @@ pub mod template {
             enum_spec: &EnumTypeAnnotation,
         ) -> Result<CxxBridgingTemplate, anyhow::Error> {
             let enum_namespace = format!("{cxx_ns}::bridging::{}", enum_spec.name);
-            let is_str = match enum_spec.members.first().unwrap().value {
-                ParserEnumMemberValue::String { .. } => true,
-                ParserEnumMemberValue::Number { .. } => false,
-            };
+            let is_str = enum_spec.members.first().unwrap().value.is_string();
 
             let as_raw = if is_str {
                 "value.asString(rt).utf8(rt)"
@@ -832,10 +1512,7 @@ pub mod template {
             enum_spec.members.iter().enumerate().try_for_each(
                 |(idx, member)| -> Result<(), anyhow::Error> {
                     let enum_namespace = format!("{}::{}", enum_namespace, member.name);
-                    let raw_member = match &member.value {
-                        ParserEnumMemberValue::String(val) => to_raw_member(val),
-                        ParserEnumMemberValue::Number(val) => to_raw_member(&val.to_string()),
-                    };
+                    let raw_member = to_raw_member(&member.value.to_string());
 
                     let from_js_cond = if idx == 0 {
                         // ```cpp
@@ -955,7 +1632,7 @@ pub mod template {
         /// template <>
         /// struct Bridging<craby::mymodule::bridging::NullableNumber> {
         ///   static craby::mymodule::bridging::NullableNumber fromJs(jsi::Runtime &rt, const jsi::Value& value, std::shared_ptr<CallInvoker> callInvoker) {
-        ///     if (value.isNull()) {
+        ///     if (value.isNull() || value.isUndefined()) {
         ///       return craby::mymodule::bridging::NullableNumber{true, 0.0};
         ///     }
         ///
@@ -979,17 +1656,18 @@ pub mod template {
             nullable_type_annotation: &TypeAnnotation,
             type_annotation: &TypeAnnotation,
         ) -> Result<CxxBridgingTemplate, anyhow::Error> {
-            let origin_namespace = type_annotation.as_cxx_type(cxx_ns)?;
             let default_value = type_annotation.as_cxx_default_val(cxx_ns)?;
             let nullable_type_namespace = nullable_type_annotation.as_cxx_type(cxx_ns)?;
+            let val_from_js = type_annotation.as_cxx_from_js(cxx_ns, "value")?.expr;
+            let val_to_js = type_annotation.as_cxx_to_js("value.val")?.expr;
 
             let from_js_impl = formatdoc! {
                 r#"
-                if (value.isNull()) {{
+                if (value.isNull() || value.isUndefined()) {{
                   return {nullable_type_namespace}{{true, {default_value}}};
                 }}
 
-                auto val = react::bridging::fromJs<{origin_namespace}>(rt, value, callInvoker);
+                auto val = {val_from_js};
                 auto ret = {nullable_type_namespace}{{false, val}};
 
                 return ret;"#,
@@ -1001,7 +1679,7 @@ pub mod template {
                   return jsi::Value::null();
                 }}
 
-                return react::bridging::toJs(rt, value.val);"#,
+                return {val_to_js};"#,
             };
 
             Ok(CxxBridgingTemplate {
@@ -1010,6 +1688,235 @@ pub mod template {
                 to_js: to_js_impl,
             })
         }
+
+        /// Generates C++ bridging template for map types.
+        ///
+        /// # Generated Code
+        ///
+        /// ```cpp
+        /// template <>
+        /// struct Bridging<craby::mymodule::bridging::NumberMap> {
+        ///   static craby::mymodule::bridging::NumberMap fromJs(jsi::Runtime &rt, const jsi::Value& value, std::shared_ptr<CallInvoker> callInvoker) {
+        ///     auto obj = value.asObject(rt);
+        ///     auto keys = obj.getPropertyNames(rt);
+        ///     auto len = keys.size(rt);
+        ///     craby::mymodule::bridging::NumberMap ret{};
+        ///     ret.keys.reserve(len);
+        ///     ret.values.reserve(len);
+        ///
+        ///     for (size_t i = 0; i < len; i++) {
+        ///       auto key = keys.getValueAtIndex(rt, i).asString(rt);
+        ///       auto val = obj.getProperty(rt, key);
+        ///       ret.keys.push_back(rust::String(key.utf8(rt)));
+        ///       ret.values.push_back(react::bridging::fromJs<double>(rt, val, callInvoker));
+        ///     }
+        ///
+        ///     return ret;
+        ///   }
+        ///
+        ///   static jsi::Value toJs(jsi::Runtime &rt, craby::mymodule::bridging::NumberMap value) {
+        ///     jsi::Object obj = jsi::Object(rt);
+        ///
+        ///     for (size_t i = 0; i < value.keys.size(); i++) {
+        ///       auto key = jsi::PropNameID::forUtf8(rt, std::string(value.keys[i].data(), value.keys[i].size()));
+        ///       obj.setProperty(rt, key, react::bridging::toJs(rt, value.values[i]));
+        ///     }
+        ///
+        ///     return jsi::Value(rt, obj);
+        ///   }
+        /// };
+        /// ```
+        pub fn try_into_map_template(
+            cxx_ns: &CxxNamespace,
+            map_type_annotation: &TypeAnnotation,
+            value_type_annotation: &TypeAnnotation,
+        ) -> Result<CxxBridgingTemplate, anyhow::Error> {
+            let map_type_namespace = map_type_annotation.as_cxx_type(cxx_ns)?;
+            let value_from_js = value_type_annotation.as_cxx_from_js(cxx_ns, "val")?.expr;
+            let value_to_js = value_type_annotation.as_cxx_to_js("value.values[i]")?.expr;
+
+            let from_js_impl = formatdoc! {
+                r#"
+                auto obj = value.asObject(rt);
+                auto keys = obj.getPropertyNames(rt);
+                auto len = keys.size(rt);
+                {map_type_namespace} ret{{}};
+                ret.keys.reserve(len);
+                ret.values.reserve(len);
+
+                for (size_t i = 0; i < len; i++) {{
+                  auto key = keys.getValueAtIndex(rt, i).asString(rt);
+                  auto val = obj.getProperty(rt, key);
+                  ret.keys.push_back(rust::String(key.utf8(rt)));
+                  ret.values.push_back({value_from_js});
+                }}
+
+                return ret;"#,
+            };
+
+            let to_js_impl = formatdoc! {
+                r#"
+                jsi::Object obj = jsi::Object(rt);
+
+                for (size_t i = 0; i < value.keys.size(); i++) {{
+                  auto key = jsi::PropNameID::forUtf8(rt, std::string(value.keys[i].data(), value.keys[i].size()));
+                  obj.setProperty(rt, key, {value_to_js});
+                }}
+
+                return jsi::Value(rt, obj);"#,
+            };
+
+            Ok(CxxBridgingTemplate {
+                namespace: map_type_namespace,
+                from_js: from_js_impl,
+                to_js: to_js_impl,
+            })
+        }
+
+        /// Generates C++ bridging template for union types.
+        ///
+        /// # Generated Code
+        ///
+        /// ```cpp
+        /// template <>
+        /// struct Bridging<craby::mymodule::bridging::OutcomeUnion> {
+        ///   static craby::mymodule::bridging::OutcomeUnion fromJs(jsi::Runtime &rt, const jsi::Value& value, std::shared_ptr<CallInvoker> callInvoker) {
+        ///     auto obj = value.asObject(rt);
+        ///     auto raw = obj.getProperty(rt, "kind").asString(rt).utf8(rt);
+        ///     craby::mymodule::bridging::OutcomeUnion ret{};
+        ///
+        ///     if (raw == "ok") {
+        ///       ret.tag = 0;
+        ///       ret.ok = react::bridging::fromJs<craby::mymodule::bridging::OutcomeOk>(rt, value, callInvoker);
+        ///     } else {
+        ///       throw jsi::JSError(rt, "Invalid union tag (Outcome)");
+        ///     }
+        ///
+        ///     return ret;
+        ///   }
+        ///
+        ///   static jsi::Value toJs(jsi::Runtime &rt, craby::mymodule::bridging::OutcomeUnion value) {
+        ///     switch (value.tag) {
+        ///       case 0: {
+        ///         auto obj = react::bridging::toJs(rt, value.ok).asObject(rt);
+        ///         obj.setProperty(rt, "kind", react::bridging::toJs(rt, "ok"));
+        ///         return jsi::Value(rt, obj);
+        ///       }
+        ///       default:
+        ///         throw jsi::JSError(rt, "Invalid union tag (Outcome)");
+        ///     }
+        ///   }
+        /// };
+        /// ```
+        pub fn try_into_union_template(
+            cxx_ns: &CxxNamespace,
+            union_type_annotation: &TypeAnnotation,
+            union_type: &UnionTypeAnnotation,
+        ) -> Result<CxxBridgingTemplate, anyhow::Error> {
+            let union_type_namespace = union_type_annotation.as_cxx_type(cxx_ns)?;
+            let tag = &union_type.tag;
+            let is_str = matches!(
+                union_type.variants.first().map(|variant| &variant.value),
+                Some(ParserEnumMemberValue::String(..))
+            );
+
+            let as_raw = if is_str {
+                format!("obj.getProperty(rt, \"{tag}\").asString(rt).utf8(rt)")
+            } else {
+                format!("obj.getProperty(rt, \"{tag}\").asNumber()")
+            };
+
+            let mut from_js_conds = vec![];
+            let mut to_js_conds = vec![];
+
+            for (idx, variant) in union_type.variants.iter().enumerate() {
+                let raw_member = match &variant.value {
+                    ParserEnumMemberValue::String(val) => format!("\"{val}\""),
+                    num_val => num_val.to_string(),
+                };
+                let cond = if idx == 0 { "if" } else { "else if" };
+
+                match &variant.data {
+                    Some(data) => {
+                        let field = snake_case(&variant.name);
+                        let data_namespace = format!("{cxx_ns}::bridging::{}", data.name);
+
+                        from_js_conds.push(formatdoc! {
+                            r#"
+                            {cond} (raw == {raw_member}) {{
+                              ret.tag = {idx};
+                              ret.{field} = react::bridging::fromJs<{data_namespace}>(rt, value, callInvoker);
+                            }}"#,
+                        });
+                        to_js_conds.push(formatdoc! {
+                            r#"
+                            case {idx}: {{
+                              auto obj = react::bridging::toJs(rt, value.{field}).asObject(rt);
+                              obj.setProperty(rt, "{tag}", react::bridging::toJs(rt, {raw_member}));
+                              return jsi::Value(rt, obj);
+                            }}"#,
+                        });
+                    }
+                    None => {
+                        from_js_conds.push(formatdoc! {
+                            r#"
+                            {cond} (raw == {raw_member}) {{
+                              ret.tag = {idx};
+                            }}"#,
+                        });
+                        to_js_conds.push(formatdoc! {
+                            r#"
+                            case {idx}: {{
+                              auto obj = jsi::Object(rt);
+                              obj.setProperty(rt, "{tag}", react::bridging::toJs(rt, {raw_member}));
+                              return jsi::Value(rt, obj);
+                            }}"#,
+                        });
+                    }
+                }
+            }
+
+            from_js_conds.push(formatdoc! {
+                r#"
+                else {{
+                  throw jsi::JSError(rt, "Invalid union tag ({union_name})");
+                }}"#,
+                union_name = union_type.name,
+            });
+            to_js_conds.push(formatdoc! {
+                r#"
+                default:
+                  throw jsi::JSError(rt, "Invalid union tag ({union_name})");"#,
+                union_name = union_type.name,
+            });
+
+            let from_js_conds = from_js_conds.join(" ");
+            let to_js_conds = indent_str(&to_js_conds.join("\n"), 2);
+
+            let from_js_impl = formatdoc! {
+                r#"
+                auto obj = value.asObject(rt);
+                auto raw = {as_raw};
+                {union_type_namespace} ret{{}};
+
+                {from_js_conds}
+
+                return ret;"#,
+            };
+
+            let to_js_impl = formatdoc! {
+                r#"
+                switch (value.tag) {{
+                {to_js_conds}
+                }}"#,
+            };
+
+            Ok(CxxBridgingTemplate {
+                namespace: union_type_namespace,
+                from_js: from_js_impl,
+                to_js: to_js_impl,
+            })
+        }
     }
 
     /// Generates C++ argument reference expression.
@@ -2,21 +2,28 @@ use std::collections::{
     btree_map::Entry as BTreeMapEntry, hash_map::Entry as HashMapEntry, BTreeMap,
 };
 
-use craby_common::utils::string::{camel_case, pascal_case, snake_case};
+use craby_common::{
+    config::{ConcurrencyMode, PanicConfig},
+    utils::string::{camel_case, pascal_case, snake_case},
+};
 use indoc::formatdoc;
 use rustc_hash::FxHashMap;
 
 use crate::{
     common::IntoCode,
-    constants::specs::RESERVED_ARG_NAME_MODULE,
+    constants::specs::{
+        RESERVED_ARG_NAME_MODULE, RESERVED_ARG_NAME_PROMISE, RESERVED_ARG_NAME_STREAM,
+    },
     parser::types::{
-        EnumTypeAnnotation, Method, ObjectTypeAnnotation, Param, RefTypeAnnotation, TypeAnnotation,
+        EnumMemberValue, EnumTypeAnnotation, Handle, HandleTypeAnnotation, Method,
+        ObjectTypeAnnotation, Param, RefTypeAnnotation, Signal, TypeAnnotation,
+        UnionTypeAnnotation,
     },
     platform::rust::template::{
-        collect_alias_default_impls, RsDefaultImpl, RsNullableStruct, RsStruct,
+        collect_alias_default_impls, error_code_impl, RsBridgeStruct, RsDefaultImpl, RsStruct,
     },
     types::Schema,
-    utils::indent_str,
+    utils::{indent_str, rs_doc, with_rs_doc_comment, with_rs_method_doc_comment},
 };
 
 #[derive(Debug)]
@@ -49,10 +56,10 @@ impl IntoCode for RsImplType {
 /// Collection of Rust code for FFI.
 #[derive(Debug, Clone)]
 pub struct RsCxxBridge {
-    /// The impl struct type name.
+    /// The module instance type name.
     ///
     /// ```rust,ignore
-    /// type MyModule;
+    /// type MyModuleInstance;
     /// ```
     pub impl_type: String,
     /// The struct definition.
@@ -94,9 +101,80 @@ pub struct RsCxxBridge {
     ///         let ret = it_.my_func(arg1, arg2);
     ///         ret
     ///     })
+    ///     .map_err(craby::errors::into_ffi_error)
     /// }
     /// ```
     pub func_impls: Vec<String>,
+    /// The signal emit function declaration for the signals with payload.
+    ///
+    /// **Example**
+    ///
+    /// ```rust,ignore
+    /// #[cxx_name = "emit"]
+    /// fn emit_my_module_on_progress(self: &SignalManager, id: usize, name: &str, payload: &Progress);
+    /// ```
+    pub signal_extern_sigs: Vec<String>,
+    /// The callback invoke function declaration for the callbacks with argument.
+    ///
+    /// **Example**
+    ///
+    /// ```rust,ignore
+    /// #[cxx_name = "invoke"]
+    /// fn invoke_my_module_subscribe_on_data(self: &CallbackManager, id: usize, payload: &Chunk);
+    /// ```
+    pub callback_extern_sigs: Vec<String>,
+    /// The stream push function declaration for the stream methods.
+    ///
+    /// **Example**
+    ///
+    /// ```rust,ignore
+    /// #[cxx_name = "push"]
+    /// fn push_my_module_read_lines(self: &StreamManager, id: usize, item: &String) -> bool;
+    /// ```
+    pub stream_extern_sigs: Vec<String>,
+    /// The promise resolve function declaration for the `async fn` methods with resolved value.
+    ///
+    /// **Example**
+    ///
+    /// ```rust,ignore
+    /// #[cxx_name = "resolve"]
+    /// fn resolve_my_module_fetch(self: &PromiseManager, id: usize, value: &String);
+    /// ```
+    pub promise_extern_sigs: Vec<String>,
+    /// The host object handle type and its method declarations.
+    ///
+    /// **Example**
+    ///
+    /// ```rust,ignore
+    /// type ConnectionHandle;
+    ///
+    /// #[cxx_name = "query"]
+    /// fn connection_handle_query(it_: &mut ConnectionHandle, sql: &str) -> Result<Vec<String>>;
+    /// ```
+    pub handle_externs: Vec<String>,
+    /// The implementation functions of the host object method declarations.
+    pub handle_impls: Vec<String>,
+}
+
+/// Returns the type of the module instance that is shared by the JS thread and the worker threads.
+///
+/// # Generated Code
+///
+/// ```rust,ignore
+/// MyModuleInstance
+/// ```
+pub fn as_rs_instance_type(module_name: &str) -> String {
+    format!("{}Instance", pascal_case(module_name))
+}
+
+/// Returns the receiver of the module spec trait methods.
+///
+/// Modules in the `shared` mode are accessed concurrently, so the methods take `&self`.
+pub fn as_rs_self_param(concurrency: ConcurrencyMode) -> &'static str {
+    match concurrency {
+        ConcurrencyMode::Lock => "&mut self",
+        ConcurrencyMode::Shared => "&self",
+    }
 }
 
 impl TypeAnnotation {
@@ -107,12 +185,18 @@ impl TypeAnnotation {
     /// ```rust,ignore
     /// bool                          // Boolean
     /// f64                           // Number
+    /// i32                           // Int32
+    /// i64                           // Int64
     /// String                        // String
+    /// Vec<u8>                       // ArrayBuffer
     /// Vec<f64>                      // Array<Number>
     /// MyEnum                        // Enum
     /// MyStruct                      // Object
+    /// MyUnionUnion                  // Union (bridge struct of `MyUnion`)
     /// NullableNumber                // Nullable<Number>
+    /// NumberMap                     // Map<Number>
     /// Result<f64, anyhow::Error>    // Promise<Number>
+    /// Box<ConnectionHandle>         // Host object (`interface Connection extends HostObject`)
     /// ```
     pub fn as_rs_type(&self) -> Result<RsType, anyhow::Error> {
         let rs_type = match self {
@@ -120,17 +204,62 @@ impl TypeAnnotation {
             TypeAnnotation::Boolean => "bool".to_string(),
             TypeAnnotation::Number => "f64".to_string(),
             TypeAnnotation::String => "String".to_string(),
+            TypeAnnotation::Int32 => "i32".to_string(),
+            TypeAnnotation::Int64 => "i64".to_string(),
+            TypeAnnotation::UInt8 => "u8".to_string(),
+            TypeAnnotation::Float => "f32".to_string(),
+            TypeAnnotation::ArrayBuffer => "Vec<u8>".to_string(),
             TypeAnnotation::Array(element_type) => {
-                if let TypeAnnotation::Array(..) = &**element_type {
+                if let TypeAnnotation::Array(..) | TypeAnnotation::ArrayBuffer = &**element_type {
                     return Err(anyhow::anyhow!(
                         "Nested array type is not supported: {:?}",
                         element_type
                     ));
                 }
+                // `Vec<u8>` is reserved for `ArrayBuffer`
+                if let TypeAnnotation::UInt8 = &**element_type {
+                    return Err(anyhow::anyhow!(
+                        "Array of UInt8 type is not supported (use `ArrayBuffer` instead)"
+                    ));
+                }
+                if let TypeAnnotation::Map(..) = &**element_type {
+                    return Err(anyhow::anyhow!(
+                        "Array of map type is not supported: {:?}",
+                        element_type
+                    ));
+                }
                 format!("Vec<{}>", element_type.as_rs_type()?.into_code())
             }
+            TypeAnnotation::Map(value_type) => match &**value_type {
+                TypeAnnotation::Boolean => "BooleanMap".to_string(),
+                TypeAnnotation::Number => "NumberMap".to_string(),
+                TypeAnnotation::String => "StringMap".to_string(),
+                TypeAnnotation::Int32 => "Int32Map".to_string(),
+                TypeAnnotation::Int64 => "Int64Map".to_string(),
+                TypeAnnotation::UInt8 => "UInt8Map".to_string(),
+                TypeAnnotation::Float => "FloatMap".to_string(),
+                TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => {
+                    format!("{name}Map")
+                }
+                TypeAnnotation::Enum(EnumTypeAnnotation { name, .. }) => {
+                    format!("{name}Map")
+                }
+                TypeAnnotation::Ref(RefTypeAnnotation { name, .. }) => {
+                    format!("{name}Map")
+                }
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "[as_rs_type] Unsupported type annotation for map type: {:?}",
+                        value_type
+                    ))
+                }
+            },
             TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => name.clone(),
             TypeAnnotation::Enum(EnumTypeAnnotation { name, .. }) => name.clone(),
+            TypeAnnotation::Union(UnionTypeAnnotation { name, .. }) => format!("{name}Union"),
+            TypeAnnotation::Handle(handle_type) => {
+                format!("Box<{}>", handle_type.as_rs_handle_type())
+            }
             TypeAnnotation::Promise(resolve_type) => {
                 format!(
                     "Result<{}, anyhow::Error>",
@@ -141,19 +270,33 @@ impl TypeAnnotation {
                 TypeAnnotation::Boolean => "NullableBoolean".to_string(),
                 TypeAnnotation::Number => "NullableNumber".to_string(),
                 TypeAnnotation::String => "NullableString".to_string(),
+                TypeAnnotation::Int32 => "NullableInt32".to_string(),
+                TypeAnnotation::Int64 => "NullableInt64".to_string(),
+                TypeAnnotation::UInt8 => "NullableUInt8".to_string(),
+                TypeAnnotation::Float => "NullableFloat".to_string(),
+                TypeAnnotation::ArrayBuffer => "NullableArrayBuffer".to_string(),
                 TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => {
                     format!("Nullable{name}")
                 }
                 TypeAnnotation::Enum(EnumTypeAnnotation { name, .. }) => {
                     format!("Nullable{name}")
                 }
+                TypeAnnotation::Union(UnionTypeAnnotation { name, .. }) => {
+                    format!("Nullable{name}")
+                }
                 TypeAnnotation::Ref(RefTypeAnnotation { name, .. }) => {
                     format!("Nullable{name}")
                 }
+                map_type @ TypeAnnotation::Map(..) => {
+                    format!("Nullable{}", map_type.as_rs_type()?.into_code())
+                }
                 TypeAnnotation::Array(element_type) => match &**element_type {
                     TypeAnnotation::Boolean => "NullableBooleanArray".to_string(),
                     TypeAnnotation::Number => "NullableNumberArray".to_string(),
                     TypeAnnotation::String => "NullableStringArray".to_string(),
+                    TypeAnnotation::Int32 => "NullableInt32Array".to_string(),
+                    TypeAnnotation::Int64 => "NullableInt64Array".to_string(),
+                    TypeAnnotation::Float => "NullableFloatArray".to_string(),
                     TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => {
                         format!("Nullable{name}Array")
                     }
@@ -216,10 +359,17 @@ impl TypeAnnotation {
     /// ```rust,ignore
     /// Boolean                       // Boolean (aliased bool)
     /// Number                        // Number (aliased f64)
+    /// Int32                         // Int32 (aliased i32)
     /// String                        // String
+    /// ArrayBuffer                   // ArrayBuffer (aliased Vec<u8>)
     /// Array<Number>                 // Array<Number>
+    /// Map<Number>                   // Map<Number> (aliased HashMap<String, f64>)
     /// Promise<Number>               // Promise<Number>
     /// Nullable<Number>              // Nullable<Number>
+    /// Callback<Number>              // Callback<Number>
+    /// Stream<String>                // Stream<String> (AsyncIterable<string>)
+    /// CancellationToken             // AbortSignal
+    /// Box<dyn ConnectionSpec>       // Host object (`interface Connection extends HostObject`)
     /// ```
     pub fn as_rs_impl_type(&self) -> Result<RsImplType, anyhow::Error> {
         let rs_type = match self {
@@ -227,8 +377,14 @@ impl TypeAnnotation {
             TypeAnnotation::Boolean => "Boolean".to_string(),
             TypeAnnotation::Number => "Number".to_string(),
             TypeAnnotation::String => "String".to_string(),
+            TypeAnnotation::Int32 => "Int32".to_string(),
+            TypeAnnotation::Int64 => "Int64".to_string(),
+            TypeAnnotation::UInt8 => "UInt8".to_string(),
+            TypeAnnotation::Float => "Float".to_string(),
+            TypeAnnotation::ArrayBuffer => "ArrayBuffer".to_string(),
             TypeAnnotation::Array(element_type) => {
-                if let TypeAnnotation::Array { .. } = &**element_type {
+                if let TypeAnnotation::Array { .. } | TypeAnnotation::ArrayBuffer = &**element_type
+                {
                     return Err(anyhow::anyhow!(
                         "Nested array type is not supported: {:?}",
                         element_type
@@ -236,8 +392,12 @@ impl TypeAnnotation {
                 }
                 format!("Array<{}>", element_type.as_rs_impl_type()?.into_code())
             }
+            TypeAnnotation::Map(value_type) => {
+                format!("Map<{}>", value_type.as_rs_impl_type()?.into_code())
+            }
             TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => name.clone(),
             TypeAnnotation::Enum(EnumTypeAnnotation { name, .. }) => name.clone(),
+            TypeAnnotation::Union(UnionTypeAnnotation { name, .. }) => name.clone(),
             TypeAnnotation::Promise(resolved_type) => {
                 format!("Promise<{}>", resolved_type.as_rs_impl_type()?.into_code())
             }
@@ -245,6 +405,16 @@ impl TypeAnnotation {
                 let type_annotation = type_annotation.as_rs_impl_type()?.into_code();
                 format!("Nullable<{type_annotation}>")
             }
+            TypeAnnotation::Callback(arg_type) => {
+                format!("Callback<{}>", arg_type.as_rs_impl_type()?.into_code())
+            }
+            TypeAnnotation::Stream(item_type) => {
+                format!("Stream<{}>", item_type.as_rs_impl_type()?.into_code())
+            }
+            TypeAnnotation::AbortSignal => "CancellationToken".to_string(),
+            TypeAnnotation::Handle(handle_type) => {
+                format!("Box<dyn {}>", handle_type.as_rs_spec_trait())
+            }
             TypeAnnotation::Ref(..) => unreachable!(),
         };
         Ok(RsImplType(rs_type))
@@ -256,28 +426,34 @@ impl TypeAnnotation {
     ///
     /// ```rust,ignore
     /// false                         // Boolean
-    /// 0.0                           // Number
+    /// 0.0                           // Number, Float
+    /// 0                             // Int32, Int64, UInt8
     /// String::default()             // String
     /// Vec::default()                // Array
     /// MyEnum::default()             // Enum
     /// MyStruct::default()           // Object
     /// NullableNumber::default()     // Nullable<Number>
+    /// NumberMap::default()          // Map<Number>
+    /// MyUnionUnion::default()       // Union
     /// ```
     pub fn as_rs_default_val(&self) -> Result<String, anyhow::Error> {
         let default_val = match self {
             TypeAnnotation::Boolean => "false".to_string(),
-            TypeAnnotation::Number => "0.0".to_string(),
+            TypeAnnotation::Number | TypeAnnotation::Float => "0.0".to_string(),
+            TypeAnnotation::Int32 | TypeAnnotation::Int64 | TypeAnnotation::UInt8 => {
+                "0".to_string()
+            }
             TypeAnnotation::String => "String::default()".to_string(),
-            TypeAnnotation::Array(..) => "Vec::default()".to_string(),
+            TypeAnnotation::ArrayBuffer | TypeAnnotation::Array(..) => "Vec::default()".to_string(),
             TypeAnnotation::Enum(EnumTypeAnnotation { name, .. }) => {
                 format!("{name}::default()")
             }
             TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => {
                 format!("{name}::default()")
             }
-            TypeAnnotation::Nullable(..) => {
-                let nullable_type = self.as_rs_type()?.into_code();
-                format!("{nullable_type}::default()")
+            TypeAnnotation::Nullable(..) | TypeAnnotation::Map(..) | TypeAnnotation::Union(..) => {
+                let bridge_type = self.as_rs_type()?.into_code();
+                format!("{bridge_type}::default()")
             }
             _ => {
                 return Err(anyhow::anyhow!(
@@ -289,9 +465,53 @@ impl TypeAnnotation {
 
         Ok(default_val)
     }
+
+    /// Returns the expression that converts the value between the bridge type and the implementation type.
+    ///
+    /// # Generated Code Examples
+    ///
+    /// ```rust,ignore
+    /// arg                                     // Number
+    /// arg.into()                              // Nullable<Number>, Map<Number>, Union
+    /// arg.into_iter().map(Into::into).collect() // Array<Union>
+    /// Box::new(ConnectionHandle::new(arg))    // Host object
+    /// ```
+    pub fn as_rs_into_expr(&self, ident: &str) -> String {
+        match self {
+            TypeAnnotation::Handle(handle_type) => {
+                format!(
+                    "Box::new({}::new({ident}))",
+                    handle_type.as_rs_handle_type()
+                )
+            }
+            bridge_type if bridge_type.is_bridge_type() => format!("{ident}.into()"),
+            TypeAnnotation::Array(element_type) if element_type.is_bridge_type() => {
+                format!("{ident}.into_iter().map(Into::into).collect()")
+            }
+            _ => ident.to_string(),
+        }
+    }
 }
 
 impl Method {
+    /// Returns `true` if the method takes the owned module reference (`ModuleRef<Self>`) instead of `&mut self`.
+    ///
+    /// Stream methods and `async fn` methods keep running after the call returns, so the modules
+    /// in the `lock` mode lock the instance only while accessing the state (not for the whole call).
+    pub fn takes_module_ref(&self, concurrency: ConcurrencyMode) -> bool {
+        concurrency == ConcurrencyMode::Lock
+            && (self.async_fn || matches!(self.ret_type, TypeAnnotation::Stream(..)))
+    }
+
+    /// Returns the receiver of the method in the module spec trait.
+    pub fn as_rs_self_param(&self, concurrency: ConcurrencyMode) -> &'static str {
+        if self.takes_module_ref(concurrency) {
+            "this: ModuleRef<Self>"
+        } else {
+            as_rs_self_param(concurrency)
+        }
+    }
+
     /// Converts Method to Rust trait method signature.
     ///
     /// # Generated Code
@@ -299,98 +519,797 @@ impl Method {
     /// ```rust,ignore
     /// fn multiply(&mut self, a: Number, b: Number) -> Number
     /// fn add_async(&mut self, a: Number, b: Number) -> Promise<Number>
+    /// fn read_lines(this: ModuleRef<Self>, path: &str, stream: Stream<String>) -> Void
+    /// fn fetch(&self, url: String) -> impl std::future::Future<Output = Promise<String>> + Send
     /// ```
-    pub fn try_into_impl_sig(&self) -> Result<String, anyhow::Error> {
-        let return_type = self.ret_type.as_rs_impl_type()?.into_code();
-        let params_sig = std::iter::once("&mut self".to_string())
+    pub fn try_into_impl_sig(&self, self_param: &str) -> Result<String, anyhow::Error> {
+        let (fn_name, params_sig, return_type) = self.try_into_impl_sig_parts(self_param)?;
+        // `async fn` methods (`@async`) return the future that is driven by the async executor
+        let return_type = if self.async_fn {
+            format!("impl std::future::Future<Output = {return_type}> + Send")
+        } else {
+            return_type
+        };
+        let ret_annotation = if return_type == "()" {
+            String::new()
+        } else {
+            format!(" -> {return_type}")
+        };
+
+        Ok(format!("fn {fn_name}({params_sig}){ret_annotation}"))
+    }
+
+    /// Converts Method to the method signature of the implementation (eg. the generated module template).
+    ///
+    /// Same as [`Method::try_into_impl_sig`] except `async fn` methods.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// fn multiply(&mut self, a: Number, b: Number) -> Number
+    /// async fn fetch(&self, url: String) -> Promise<String>
+    /// ```
+    pub fn try_into_impl_fn_sig(&self, self_param: &str) -> Result<String, anyhow::Error> {
+        if !self.async_fn {
+            return self.try_into_impl_sig(self_param);
+        }
+
+        let (fn_name, params_sig, return_type) = self.try_into_impl_sig_parts(self_param)?;
+        Ok(format!("async fn {fn_name}({params_sig}) -> {return_type}"))
+    }
+
+    /// Returns the function name, the parameters and the return type of the implementation signature.
+    fn try_into_impl_sig_parts(
+        &self,
+        self_param: &str,
+    ) -> Result<(String, String, String), anyhow::Error> {
+        // Stream methods take the producer handle instead of returning the items
+        let (return_type, stream_sig) = match &self.ret_type {
+            stream_type @ TypeAnnotation::Stream(..) => (
+                TypeAnnotation::Void.as_rs_impl_type()?.into_code(),
+                Some(format!(
+                    "{RESERVED_ARG_NAME_STREAM}: {}",
+                    stream_type.as_rs_impl_type()?.into_code()
+                )),
+            ),
+            // Fallible sync methods (`@throws`) return the structured error instead of panicking
+            ret_type if self.throws => (
+                format!(
+                    "Result<{}, craby::Error>",
+                    ret_type.as_rs_impl_type()?.into_code()
+                ),
+                None,
+            ),
+            ret_type => (ret_type.as_rs_impl_type()?.into_code(), None),
+        };
+        let params_sig = std::iter::once(self_param.to_string())
             .chain(
                 self.params
                     .iter()
-                    .map(|param| param.try_into_impl_sig())
+                    .map(|param| {
+                        // The future of `async fn` methods outlives the borrowed arguments
+                        if self.async_fn {
+                            param.try_into_owned_impl_sig()
+                        } else {
+                            param.try_into_impl_sig()
+                        }
+                    })
                     .collect::<Result<Vec<_>, _>>()?,
             )
+            .chain(stream_sig)
             .collect::<Vec<_>>()
             .join(", ");
 
+        Ok((snake_case(&self.name), params_sig, return_type))
+    }
+
+    /// Generates the extern function declaration of the method and its implementation
+    /// that invokes the method on the receiver (the module or the host object handle).
+    ///
+    /// The module instance is accessed with the concurrency mode of the module
+    /// (`None` for the host object handles that are only accessed by the JS thread).
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// #[cxx_name = "multiply"]
+    /// fn my_module_multiply(it_: &MyModuleInstance, a: f64, b: f64) -> Result<f64>;
+    ///
+    /// fn my_module_multiply(it_: &MyModuleInstance, a: f64, b: f64) -> Result<f64, anyhow::Error> {
+    ///     craby::catch_panic!(it_.0, {
+    ///         let mut it_ = it_.0.lock();
+    ///         let ret = it_.multiply(a, b);
+    ///         ret
+    ///     })
+    ///     .map_err(craby::errors::into_ffi_error)
+    /// }
+    /// ```
+    pub fn try_into_rs_bridge_func(
+        &self,
+        module_name: &str,
+        receiver_type: &str,
+        concurrency: Option<ConcurrencyMode>,
+    ) -> Result<(String, String), anyhow::Error> {
+        // Stream methods return nothing (the items are pushed through the registered stream)
+        let is_stream = matches!(self.ret_type, TypeAnnotation::Stream(..));
+        // The producer blocked by the backpressure must not hold the lock that the JS thread (the consumer) waits for
+        let module_ref = concurrency.is_some_and(|concurrency| self.takes_module_ref(concurrency));
+        // `async fn` methods return nothing (the promise is settled by the async executor)
+        let ret_type_annotation = if is_stream || self.async_fn {
+            &TypeAnnotation::Void
+        } else {
+            &self.ret_type
+        };
+        let ret_type = ret_type_annotation.as_rs_type()?.into_code();
+        let ret_type = match ret_type_annotation {
+            TypeAnnotation::Promise(_) => ret_type,
+            _ => format!("Result<{ret_type}, anyhow::Error>"),
+        };
+        let ret_extern_type = ret_type_annotation.as_rs_bridge_type()?.into_code();
+        let ret_extern_type = match ret_type_annotation {
+            TypeAnnotation::Promise(_) => ret_extern_type,
+            _ => format!("Result<{ret_extern_type}>"),
+        };
+
+        let params_sig = self
+            .params
+            .iter()
+            .map(|param| param.try_into_cxx_sig())
+            .collect::<Result<Vec<_>, _>>()
+            .map(|mut params| {
+                let receiver_sig = match concurrency {
+                    Some(_) => format!("&{}", as_rs_instance_type(receiver_type)),
+                    None => format!("&mut {receiver_type}"),
+                };
+                params.insert(0, format!("{RESERVED_ARG_NAME_MODULE}: {receiver_sig}"));
+                if is_stream {
+                    params.push(format!("{RESERVED_ARG_NAME_STREAM}: usize"));
+                }
+                if self.async_fn {
+                    params.push(format!("{RESERVED_ARG_NAME_PROMISE}: usize"));
+                }
+                params.join(", ")
+            })?;
+
+        let mod_name = snake_case(receiver_type);
         let fn_name = snake_case(&self.name);
-        let ret_annotation = if return_type == "()" {
-            String::new()
+        let mut fn_args = self
+            .params
+            .iter()
+            .map(|param| {
+                param
+                    .type_annotation
+                    .as_rs_into_expr(&snake_case(&param.name))
+            })
+            .collect::<Vec<_>>();
+        if is_stream {
+            fn_args.push(RESERVED_ARG_NAME_STREAM.to_string());
+        }
+
+        let cxx_extern_fn_name = camel_case(&self.name);
+        let prefixed_fn_name = format!("{mod_name}_{fn_name}");
+        let ret_extern_annotation = format!(" -> {ret_extern_type}");
+        let ret_annotation = format!(" -> {ret_type}");
+        let extern_func = formatdoc! {
+            r#"
+            #[cxx_name = "{cxx_extern_fn_name}"]
+            fn {prefixed_fn_name}({params_sig}){ret_extern_annotation};"#,
+        };
+
+        // Wrap the registered ids with `Callback`, `CancellationToken` and `Stream` handles
+        let callback_decls = self
+            .params
+            .iter()
+            .map(|param| {
+                param
+                    .try_into_rs_callback(module_name, &self.name)
+                    .map(|decl| decl.or_else(|| param.as_rs_cancellation_token()))
+            })
+            .chain(std::iter::once(self.try_into_rs_stream(module_name)))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        // `Result` of the promise methods and the fallible sync methods (`@throws`)
+        let ok_type = match &self.ret_type {
+            TypeAnnotation::Promise(resolved_type) => Some(&**resolved_type),
+            ret_type if self.throws => Some(ret_type),
+            _ => None,
+        };
+        let ret = match ok_type {
+            Some(ok_type) if ok_type.is_bridge_type() => "ret.map(Into::into)".to_string(),
+            Some(ok_type) => match ok_type.as_rs_into_expr("ret") {
+                expr if expr == "ret" => expr,
+                expr => format!("ret.map(|ret| {expr})"),
+            },
+            None => self.ret_type.as_rs_into_expr("ret"),
+        };
+
+        // Methods taking the module reference are called as the associated functions of the module
+        let module_type = pascal_case(receiver_type);
+        let ref_fn_args = |module_ref_expr: String| {
+            std::iter::once(module_ref_expr)
+                .chain(fn_args.iter().cloned())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let invoke_stmts = match self.try_into_rs_async_promise(module_name)? {
+            // Spawn the future on the async executor that settles the promise
+            Some(promise_decl) => {
+                // The future of `async fn` methods takes the owned arguments
+                let owned_decls = self.params.iter().filter_map(|param| {
+                    let name = snake_case(&param.name);
+                    match &param.type_annotation {
+                        TypeAnnotation::String => Some(format!("let {name} = {name}.to_string();")),
+                        TypeAnnotation::ArrayBuffer => {
+                            Some(format!("let {name} = {name}.to_vec();"))
+                        }
+                        _ => None,
+                    }
+                });
+
+                // The future owns a reference of the module instance (kept alive after the module is invalidated)
+                let spawn_stmt = if module_ref {
+                    let fn_args = ref_fn_args(RESERVED_ARG_NAME_MODULE.to_string());
+                    formatdoc! {
+                        r#"
+                        craby::runtime::spawn_promise({RESERVED_ARG_NAME_MODULE}.0.clone(), {RESERVED_ARG_NAME_PROMISE}, move |{RESERVED_ARG_NAME_MODULE}| {{
+                            {module_type}::{fn_name}({fn_args})
+                        }});"#,
+                    }
+                } else {
+                    let fn_args = fn_args.join(", ");
+                    formatdoc! {
+                        r#"
+                        craby::runtime::spawn_promise({RESERVED_ARG_NAME_MODULE}.0.clone(), {RESERVED_ARG_NAME_PROMISE}, move |{RESERVED_ARG_NAME_MODULE}| async move {{
+                            {RESERVED_ARG_NAME_MODULE}.{fn_name}({fn_args}).await
+                        }});"#,
+                    }
+                };
+
+                std::iter::once(promise_decl)
+                    .chain(owned_decls)
+                    .chain([spawn_stmt])
+                    .collect()
+            }
+            // The method locks the module reference by itself
+            None if module_ref => {
+                let fn_args = ref_fn_args(format!("{RESERVED_ARG_NAME_MODULE}.0.clone()"));
+                vec![
+                    format!("let ret = {module_type}::{fn_name}({fn_args});"),
+                    ret,
+                ]
+            }
+            None => {
+                let access_stmt = match concurrency {
+                    // Calls are serialized by the per-instance lock
+                    Some(ConcurrencyMode::Lock) => Some(format!(
+                        "let mut {RESERVED_ARG_NAME_MODULE} = {RESERVED_ARG_NAME_MODULE}.0.lock();"
+                    )),
+                    Some(ConcurrencyMode::Shared) => Some(format!(
+                        "let {RESERVED_ARG_NAME_MODULE} = &*{RESERVED_ARG_NAME_MODULE}.0;"
+                    )),
+                    None => None,
+                };
+
+                let fn_args = fn_args.join(", ");
+                access_stmt
+                    .into_iter()
+                    .chain([
+                        format!("let ret = {RESERVED_ARG_NAME_MODULE}.{fn_name}({fn_args});"),
+                        ret,
+                    ])
+                    .collect()
+            }
+        };
+        let body_stmts = [callback_decls, invoke_stmts].concat().join("\n");
+        let body_stmts = indent_str(&body_stmts, 8);
+        // The module instance (or the poison flag of the host object) fails fast if it is poisoned by a previous panic
+        let catch_panic_args = if concurrency.is_some() {
+            format!("{RESERVED_ARG_NAME_MODULE}.0, ")
         } else {
-            format!(" -> {return_type}")
+            format!("{RESERVED_ARG_NAME_MODULE}.1, ")
+        };
+        // Deprecated methods (`@deprecated`) are still called by the bindings
+        let allow_attr = if self
+            .doc
+            .as_deref()
+            .is_some_and(|doc| rs_doc(doc).deprecated.is_some())
+        {
+            "#[allow(deprecated)]\n"
+        } else {
+            ""
+        };
+        let impl_func = match self.ret_type {
+            // The structured error is converted into `anyhow::Error` without panicking
+            _ if self.throws => formatdoc! {
+                r#"
+                {allow_attr}fn {prefixed_fn_name}({params_sig}){ret_annotation} {{
+                    craby::catch_panic!({catch_panic_args}{{
+                {body_stmts}
+                    }})
+                    .and_then(|r| r.map_err(anyhow::Error::from))
+                    .map_err(craby::errors::into_ffi_error)
+                }}"#,
+            },
+            TypeAnnotation::Promise(_) if !self.async_fn => formatdoc! {
+                r#"
+                {allow_attr}fn {prefixed_fn_name}({params_sig}){ret_annotation} {{
+                    craby::catch_panic!({catch_panic_args}{{
+                {body_stmts}
+                    }})
+                    .and_then(|r| r)
+                    .map_err(craby::errors::into_ffi_error)
+                }}"#,
+            },
+            _ => formatdoc! {
+                r#"
+                {allow_attr}fn {prefixed_fn_name}({params_sig}){ret_annotation} {{
+                    craby::catch_panic!({catch_panic_args}{{
+                {body_stmts}
+                    }})
+                    .map_err(craby::errors::into_ffi_error)
+                }}"#,
+            },
         };
 
-        Ok(format!("fn {fn_name}({params_sig}){ret_annotation}"))
+        Ok((extern_func, impl_func))
+    }
+
+    /// Returns the Rust function name of the `PromiseManager` resolve binding for the `async fn` methods.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// resolve_my_module_fetch
+    /// ```
+    pub fn as_rs_resolve_fn_name(&self, module_name: &str) -> String {
+        format!(
+            "resolve_{}_{}",
+            snake_case(module_name),
+            snake_case(&self.name)
+        )
+    }
+
+    /// Generates the `AsyncPromise` handle from the registered promise id (`async fn` methods).
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// let promise = AsyncPromise::new(
+    ///     promise,
+    ///     |id, ret: String| get_promise_manager().resolve_my_module_fetch(id, &ret),
+    ///     |id, message| get_promise_manager().reject(id, message),
+    ///     |id| get_promise_manager().release(id),
+    /// );
+    /// ```
+    pub fn try_into_rs_async_promise(
+        &self,
+        module_name: &str,
+    ) -> Result<Option<String>, anyhow::Error> {
+        let resolved_type = match &self.ret_type {
+            TypeAnnotation::Promise(resolved_type) if self.async_fn => resolved_type,
+            _ => return Ok(None),
+        };
+
+        let name = RESERVED_ARG_NAME_PROMISE;
+        let resolve = match &**resolved_type {
+            TypeAnnotation::Void => "|id, _: Void| get_promise_manager().resolve(id)".to_string(),
+            resolved_type => {
+                let ret_impl_type = resolved_type.as_rs_impl_type()?.into_code();
+                let ret = format!("&{}", resolved_type.as_rs_into_expr("ret"));
+                let resolve_fn_name = self.as_rs_resolve_fn_name(module_name);
+
+                format!(
+                    "|id, ret: {ret_impl_type}| get_promise_manager().{resolve_fn_name}(id, {ret})"
+                )
+            }
+        };
+
+        Ok(Some(formatdoc! {
+            r#"
+            let {name} = AsyncPromise::new(
+                {name},
+                {resolve},
+                |id, message| get_promise_manager().reject(id, message),
+                |id| get_promise_manager().release(id),
+            );"#,
+        }))
+    }
+
+    /// Returns the Rust function name of the `StreamManager` push binding for the stream items.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// push_my_module_read_lines
+    /// ```
+    pub fn as_rs_push_fn_name(&self, module_name: &str) -> String {
+        format!(
+            "push_{}_{}",
+            snake_case(module_name),
+            snake_case(&self.name)
+        )
+    }
+
+    /// Generates the `Stream` handle from the registered stream id.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// let stream = Stream::new(
+    ///     stream,
+    ///     |id, item: String| get_stream_manager().push_my_module_read_lines(id, &item),
+    ///     |id| get_stream_manager().finish(id),
+    ///     |id, message| get_stream_manager().fail(id, message),
+    ///     |id| get_stream_manager().release(id),
+    /// );
+    /// ```
+    pub fn try_into_rs_stream(&self, module_name: &str) -> Result<Option<String>, anyhow::Error> {
+        let item_type = match &self.ret_type {
+            TypeAnnotation::Stream(item_type) => item_type,
+            _ => return Ok(None),
+        };
+
+        let name = RESERVED_ARG_NAME_STREAM;
+        let item_impl_type = item_type.as_rs_impl_type()?.into_code();
+        let item = format!("&{}", item_type.as_rs_into_expr("item"));
+        let push_fn_name = self.as_rs_push_fn_name(module_name);
+
+        Ok(Some(formatdoc! {
+            r#"
+            let {name} = Stream::new(
+                {name},
+                |id, item: {item_impl_type}| get_stream_manager().{push_fn_name}(id, {item}),
+                |id| get_stream_manager().finish(id),
+                |id, message| get_stream_manager().fail(id, message),
+                |id| get_stream_manager().release(id),
+            );"#,
+        }))
+    }
+}
+
+impl Param {
+    /// Converts parameter to FFI function signature.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// a: f64
+    /// name: &str
+    /// data: &[u8]
+    /// items: Vec<MyStruct>
+    /// on_data: usize // Callback (registered callback id)
+    /// signal: usize  // AbortSignal (registered token id)
+    /// ```
+    pub fn try_into_cxx_sig(&self) -> Result<String, anyhow::Error> {
+        let param_type = match &self.type_annotation {
+            TypeAnnotation::String => "&str".to_string(),
+            TypeAnnotation::ArrayBuffer => "&[u8]".to_string(),
+            TypeAnnotation::Callback(..) | TypeAnnotation::AbortSignal => "usize".to_string(),
+            _ => self.type_annotation.as_rs_type()?.into_code(),
+        };
+        Ok(format!("{}: {}", snake_case(&self.name), param_type))
+    }
+
+    /// Converts parameter to implementation function signature.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// a: Number
+    /// name: &str
+    /// data: &[u8]
+    /// items: Array<MyStruct>
+    /// on_data: Callback<Chunk>
+    /// signal: CancellationToken
+    /// ```
+    pub fn try_into_impl_sig(&self) -> Result<String, anyhow::Error> {
+        let param_type = match &self.type_annotation {
+            TypeAnnotation::String => "&str".to_string(),
+            TypeAnnotation::ArrayBuffer => "&[u8]".to_string(),
+            _ => self.type_annotation.as_rs_impl_type()?.into_code(),
+        };
+        Ok(format!("{}: {}", snake_case(&self.name), param_type))
+    }
+
+    /// Converts parameter to implementation function signature with the owned types (`async fn` methods).
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// name: String
+    /// data: ArrayBuffer
+    /// ```
+    pub fn try_into_owned_impl_sig(&self) -> Result<String, anyhow::Error> {
+        Ok(format!(
+            "{}: {}",
+            snake_case(&self.name),
+            self.type_annotation.as_rs_impl_type()?.into_code()
+        ))
+    }
+
+    /// Returns the Rust function name of the `CallbackManager` invoke binding for the callback argument.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// invoke_my_module_subscribe_on_data
+    /// ```
+    pub fn as_rs_invoke_fn_name(&self, module_name: &str, method_name: &str) -> String {
+        format!(
+            "invoke_{}_{}_{}",
+            snake_case(module_name),
+            snake_case(method_name),
+            snake_case(&self.name)
+        )
+    }
+
+    /// Generates the `Callback` handle from the registered callback id.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// let on_data = Callback::new(
+    ///     on_data,
+    ///     |id, payload: Chunk| get_callback_manager().invoke_my_module_subscribe_on_data(id, &payload),
+    ///     |id| get_callback_manager().release(id),
+    /// );
+    /// ```
+    pub fn try_into_rs_callback(
+        &self,
+        module_name: &str,
+        method_name: &str,
+    ) -> Result<Option<String>, anyhow::Error> {
+        let arg_type = match &self.type_annotation {
+            TypeAnnotation::Callback(arg_type) => arg_type,
+            _ => return Ok(None),
+        };
+
+        let name = snake_case(&self.name);
+        let invoke = match &**arg_type {
+            TypeAnnotation::Void => "|id, _: Void| get_callback_manager().invoke(id)".to_string(),
+            arg_type => {
+                let payload_impl_type = arg_type.as_rs_impl_type()?.into_code();
+                let payload = format!("&{}", arg_type.as_rs_into_expr("payload"));
+                let invoke_fn_name = self.as_rs_invoke_fn_name(module_name, method_name);
+
+                format!("|id, payload: {payload_impl_type}| get_callback_manager().{invoke_fn_name}(id, {payload})")
+            }
+        };
+
+        Ok(Some(formatdoc! {
+            r#"
+            let {name} = Callback::new(
+                {name},
+                {invoke},
+                |id| get_callback_manager().release(id),
+            );"#,
+        }))
+    }
+
+    /// Generates the `CancellationToken` handle from the registered token id.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// let signal = CancellationToken::new(
+    ///     signal,
+    ///     |id| get_cancellation_manager().is_cancelled(id),
+    ///     |id| get_cancellation_manager().release(id),
+    /// );
+    /// ```
+    pub fn as_rs_cancellation_token(&self) -> Option<String> {
+        if !self.type_annotation.is_abort_signal() {
+            return None;
+        }
+
+        let name = snake_case(&self.name);
+        Some(formatdoc! {
+            r#"
+            let {name} = CancellationToken::new(
+                {name},
+                |id| get_cancellation_manager().is_cancelled(id),
+                |id| get_cancellation_manager().release(id),
+            );"#,
+        })
     }
 }
 
-impl Param {
-    /// Converts parameter to FFI function signature.
+impl Signal {
+    /// Returns the Rust function name of the `SignalManager` emit binding for the signal payload.
     ///
     /// # Generated Code
     ///
     /// ```rust,ignore
-    /// a: f64
-    /// name: String
-    /// items: Vec<MyStruct>
+    /// emit_my_module_on_progress
     /// ```
-    pub fn try_into_cxx_sig(&self) -> Result<String, anyhow::Error> {
-        let param_type = if let TypeAnnotation::String = &self.type_annotation {
-            "&str".to_string()
-        } else {
-            self.type_annotation.as_rs_type()?.into_code()
-        };
-        Ok(format!("{}: {}", snake_case(&self.name), param_type))
+    pub fn as_rs_emit_fn_name(&self, module_name: &str) -> String {
+        format!(
+            "emit_{}_{}",
+            snake_case(module_name),
+            snake_case(&self.name)
+        )
     }
+}
 
-    /// Converts parameter to implementation function signature.
+impl HandleTypeAnnotation {
+    /// Returns the Rust type of the host object handle passed through the FFI.
+    ///
+    /// ```rust,ignore
+    /// ConnectionHandle
+    /// ```
+    pub fn as_rs_handle_type(&self) -> String {
+        format!("{}Handle", pascal_case(&self.name))
+    }
+
+    /// Returns the spec trait name of the host object.
+    ///
+    /// ```rust,ignore
+    /// ConnectionSpec
+    /// ```
+    pub fn as_rs_spec_trait(&self) -> String {
+        format!("{}Spec", pascal_case(&self.name))
+    }
+}
+
+impl Handle {
+    pub fn as_handle_type(&self) -> HandleTypeAnnotation {
+        HandleTypeAnnotation {
+            name: self.name.clone(),
+        }
+    }
+
+    /// Generates the spec trait of the host object and the handle type that owns the implementation.
+    ///
+    /// The implementation can be created on a worker thread (promise methods) and used by the JS thread,
+    /// so the trait requires `Send`:
+    ///
+    /// ```compile_fail
+    /// pub trait ConnectionSpec: Send {}
+    ///
+    /// struct Connection(std::rc::Rc<()>);
+    ///
+    /// impl ConnectionSpec for Connection {}
+    /// ```
     ///
     /// # Generated Code
     ///
     /// ```rust,ignore
-    /// a: Number
-    /// name: String
-    /// items: Array<MyStruct>
+    /// pub trait ConnectionSpec: Send {
+    ///     fn close(&mut self);
+    ///     fn query(&mut self, sql: &str) -> Array<String>;
+    /// }
+    ///
+    /// pub struct ConnectionHandle(pub Box<dyn ConnectionSpec>, pub(crate) craby::sync::PoisonFlag);
+    ///
+    /// impl std::ops::Deref for ConnectionHandle {
+    ///     type Target = dyn ConnectionSpec;
+    ///
+    ///     fn deref(&self) -> &Self::Target {
+    ///         self.0.as_ref()
+    ///     }
+    /// }
     /// ```
-    pub fn try_into_impl_sig(&self) -> Result<String, anyhow::Error> {
-        let param_type = if let TypeAnnotation::String = &self.type_annotation {
-            "&str".to_string()
-        } else {
-            self.type_annotation.as_rs_impl_type()?.into_code()
-        };
-        Ok(format!("{}: {}", snake_case(&self.name), param_type))
+    pub fn try_into_rs_spec(&self) -> Result<String, anyhow::Error> {
+        let handle_type = self.as_handle_type();
+        let trait_name = handle_type.as_rs_spec_trait();
+        let handle_name = handle_type.as_rs_handle_type();
+        let methods = self
+            .methods
+            .iter()
+            .map(|spec| -> Result<String, anyhow::Error> {
+                let sig = spec.try_into_impl_sig("&mut self")?;
+                Ok(with_rs_method_doc_comment(
+                    spec.doc_with_params().as_deref(),
+                    format!("{sig};"),
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let method_defs = indent_str(&methods.join("\n"), 4);
+        let spec_trait = with_rs_doc_comment(
+            self.doc.as_deref(),
+            formatdoc! {
+                r#"
+                pub trait {trait_name}: Send {{
+                {method_defs}
+                }}"#,
+            },
+        );
+
+        Ok(formatdoc! {
+            r#"
+            {spec_trait}
+
+            pub struct {handle_name}(pub Box<dyn {trait_name}>, pub(crate) craby::sync::PoisonFlag);
+
+            impl std::ops::Deref for {handle_name} {{
+                type Target = dyn {trait_name};
+
+                fn deref(&self) -> &Self::Target {{
+                    self.0.as_ref()
+                }}
+            }}
+
+            impl std::ops::DerefMut for {handle_name} {{
+                fn deref_mut(&mut self) -> &mut Self::Target {{
+                    self.0.as_mut()
+                }}
+            }}"#,
+        })
     }
 }
 
 impl Schema {
+    /// Returns the constants hook signature of the module spec trait.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// fn constants(ctx: &Context) -> MyModuleConstants
+    /// ```
+    pub fn try_into_constants_sig(&self) -> Result<Option<String>, anyhow::Error> {
+        match &self.constants {
+            Some(constants) => {
+                let constants_type = constants.as_rs_impl_type()?.into_code();
+                Ok(Some(format!(
+                    "fn constants(ctx: &Context) -> {constants_type}"
+                )))
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Generates complete Rust FFI bridge including externs, structs, enums, and implementations.
     ///
     /// # Generated Code
     ///
     /// ```rust,ignore
     /// // In ffi.rs extern block:
-    /// type MyModule;
+    /// type MyModuleInstance;
     ///
     /// #[cxx_name = "createMyModule"]
-    /// fn create_my_module(id: usize, data_path: &str) -> Box<MyModule>;
+    /// fn create_my_module(id: usize, data_path: &str) -> Result<Box<MyModuleInstance>>;
     ///
     /// #[cxx_name = "multiply"]
-    /// fn my_module_multiply(it_: &mut MyModule, a: f64, b: f64) -> Result<f64>;
+    /// fn my_module_multiply(it_: &MyModuleInstance, a: f64, b: f64) -> Result<f64>;
     ///
     /// // Implementation:
-    /// fn create_my_module(id: usize, data_path: &str) -> Box<MyModule> {
-    ///     Box::new(MyModule::new(id, data_path))
-    /// }
+    /// pub struct MyModuleInstance(std::sync::Arc<craby::sync::ModuleLock<MyModule>>);
     ///
-    /// fn my_module_multiply(it_: &mut MyModule, a: f64, b: f64) -> Result<f64> {
+    /// fn create_my_module(id: usize, data_path: &str) -> Result<Box<MyModuleInstance>, anyhow::Error> {
     ///     craby::catch_panic!({
+    ///         let ctx = Context::new(id, data_path);
+    ///         let module = craby::sync::ModuleLock::new(MyModule::new(ctx));
+    ///         Box::new(MyModuleInstance(std::sync::Arc::new(module)))
+    ///     })
+    ///     .map_err(craby::errors::into_ffi_error)
+    /// }
+    ///
+    /// fn my_module_multiply(it_: &MyModuleInstance, a: f64, b: f64) -> Result<f64> {
+    ///     craby::catch_panic!(it_.0, {
+    ///         let mut it_ = it_.0.lock();
     ///         let ret = it_.multiply(a, b);
     ///         ret
     ///     })
+    ///     .map_err(craby::errors::into_ffi_error)
     /// }
     /// ```
-    pub fn as_rs_cxx_bridge(&self) -> Result<RsCxxBridge, anyhow::Error> {
+    pub fn as_rs_cxx_bridge(
+        &self,
+        concurrency: ConcurrencyMode,
+        panic: &PanicConfig,
+    ) -> Result<RsCxxBridge, anyhow::Error> {
         let module_name = pascal_case(&self.module_name);
         let snake_module_name = snake_case(&self.module_name);
+        let instance_type = as_rs_instance_type(&self.module_name);
+        // `ModuleLock` requires `Send` and `Shared` requires `Send + Sync` modules
+        let wrapper_type = match concurrency {
+            ConcurrencyMode::Lock => "craby::sync::ModuleLock",
+            ConcurrencyMode::Shared => "craby::sync::Shared",
+        };
 
         let mut func_extern_sigs = Vec::with_capacity(self.methods.len() + 1);
         let mut func_impls = Vec::with_capacity(self.methods.len() + 1);
@@ -400,125 +1319,215 @@ impl Schema {
         func_extern_sigs.push(formatdoc! {
             r#"
             #[cxx_name = "create{module_name}"]
-            fn create_{snake_module_name}(id: usize, data_path: &str) -> Box<{module_name}>;"#,
+            fn create_{snake_module_name}(id: usize, data_path: &str) -> Result<Box<{instance_type}>>;"#,
         });
 
+        // Panic options (`[project.panic]` in `craby.toml`)
+        let backtrace_stmt = if panic.backtrace {
+            "craby::errors::set_panic_backtrace(true);\n        "
+        } else {
+            ""
+        };
+        let poison_call = if panic.poison {
+            ".poison_on_panic()"
+        } else {
+            ""
+        };
         func_impls.push(formatdoc! {
             r#"
-            fn create_{snake_module_name}(id: usize, data_path: &str) -> Box<{module_name}> {{
-                let ctx = Context::new(id, data_path);
-                Box::new({module_name}::new(ctx))
+            pub struct {instance_type}(std::sync::Arc<{wrapper_type}<{module_name}>>);
+
+            fn create_{snake_module_name}(id: usize, data_path: &str) -> Result<Box<{instance_type}>, anyhow::Error> {{
+                craby::catch_panic!({{
+                    {backtrace_stmt}let ctx = Context::new(id, data_path);
+                    let module = {wrapper_type}::new({module_name}::new(ctx)){poison_call};
+                    Box::new({instance_type}(std::sync::Arc::new(module)))
+                }})
+                .map_err(craby::errors::into_ffi_error)
             }}"#,
         });
 
+        // Module constants are evaluated once per instance and cached by the C++ module
+        if let Some(constants) = &self.constants {
+            let constants_type = constants.as_rs_bridge_type()?.into_code();
+
+            func_extern_sigs.push(formatdoc! {
+                r#"
+                #[cxx_name = "get{module_name}Constants"]
+                fn {snake_module_name}_constants(id: usize, data_path: &str) -> Result<{constants_type}>;"#,
+            });
+
+            func_impls.push(formatdoc! {
+                r#"
+                fn {snake_module_name}_constants(id: usize, data_path: &str) -> Result<{constants_type}, anyhow::Error> {{
+                    craby::catch_panic!({{
+                        let ctx = Context::new(id, data_path);
+                        let ret = {module_name}::constants(&ctx);
+                        ret
+                    }})
+                    .map_err(craby::errors::into_ffi_error)
+                }}"#,
+            });
+        }
+
+        // Lifecycle hooks called by the C++ module on the teardown (`invalidate()` and the destructor)
+        for hook in Schema::lifecycle_hooks() {
+            let (extern_func, impl_func) =
+                hook.try_into_rs_bridge_func(&self.module_name, &module_name, Some(concurrency))?;
+            func_extern_sigs.push(extern_func);
+            func_impls.push(impl_func);
+        }
+
         // Collect extern function signatures and implementations
         for method_spec in &self.methods {
-            // Collect nullable parameters
+            // Collect nullable and map parameters
             for param in &method_spec.params {
-                if param.type_annotation.is_nullable() {
-                    let id = param.type_annotation.to_id();
-                    if let HashMapEntry::Vacant(e) = struct_defs.entry(id) {
-                        let nullable = RsNullableStruct::try_from(&param.type_annotation)?;
-                        e.insert(nullable.definition);
-                        type_impls.push(nullable.implementation);
+                for bridge_type in param.type_annotation.bridge_types() {
+                    if let HashMapEntry::Vacant(e) = struct_defs.entry(bridge_type.to_id()) {
+                        let bridge_struct = RsBridgeStruct::try_from(bridge_type)?;
+                        e.insert(bridge_struct.definition);
+                        type_impls.push(bridge_struct.implementation);
                     }
                 }
             }
 
-            // Collect nullable return type
-            if method_spec.ret_type.is_nullable() {
-                let id = method_spec.ret_type.to_id();
-                if let HashMapEntry::Vacant(e) = struct_defs.entry(id) {
-                    let nullable = RsNullableStruct::try_from(&method_spec.ret_type)?;
-                    e.insert(nullable.definition);
-                    type_impls.push(nullable.implementation);
+            // Collect nullable and map return type
+            for bridge_type in method_spec.ret_type.bridge_types() {
+                if let HashMapEntry::Vacant(e) = struct_defs.entry(bridge_type.to_id()) {
+                    let bridge_struct = RsBridgeStruct::try_from(bridge_type)?;
+                    e.insert(bridge_struct.definition);
+                    type_impls.push(bridge_struct.implementation);
                 }
             }
 
-            let ret_type = method_spec.ret_type.as_rs_type()?.into_code();
-            let ret_type = match method_spec.ret_type {
-                TypeAnnotation::Promise(_) => ret_type,
-                _ => format!("Result<{ret_type}, anyhow::Error>"),
-            };
-            let ret_extern_type = method_spec.ret_type.as_rs_bridge_type()?.into_code();
-            let ret_extern_type = match method_spec.ret_type {
-                TypeAnnotation::Promise(_) => ret_extern_type,
-                _ => format!("Result<{ret_extern_type}>"),
-            };
+            let (extern_func, impl_func) = method_spec.try_into_rs_bridge_func(
+                &self.module_name,
+                &module_name,
+                Some(concurrency),
+            )?;
+            func_extern_sigs.push(extern_func);
+            func_impls.push(impl_func);
+        }
 
-            let params_sig = method_spec
-                .params
-                .iter()
-                .map(|param| param.try_into_cxx_sig())
-                .collect::<Result<Vec<_>, _>>()
-                .map(|mut params| {
-                    params.insert(
-                        0,
-                        format!(
-                            "{RESERVED_ARG_NAME_MODULE}: &mut {}",
-                            pascal_case(&self.module_name)
-                        ),
-                    );
-                    params.join(", ")
-                })?;
-
-            let mod_name = snake_case(&self.module_name);
-            let fn_name = snake_case(&method_spec.name);
-            let fn_args = method_spec
-                .params
-                .iter()
-                .map(|param| {
-                    let name = snake_case(&param.name);
-                    if let TypeAnnotation::Nullable(..) = &param.type_annotation {
-                        format!("{name}.into()")
-                    } else {
-                        name
-                    }
-                })
-                .collect::<Vec<_>>();
-
-            let cxx_extern_fn_name = camel_case(&method_spec.name);
-            let prefixed_fn_name = format!("{mod_name}_{fn_name}");
-            let ret_extern_annotation = format!(" -> {ret_extern_type}");
-            let ret_annotation = format!(" -> {ret_type}");
-            let extern_func = formatdoc! {
+        // Collect the host object handle types and the extern functions of their methods
+        let mut handle_externs = vec![];
+        let mut handle_impls = vec![];
+        for handle in &self.handles {
+            let handle_type = handle.as_handle_type().as_rs_handle_type();
+            let trait_name = handle.as_handle_type().as_rs_spec_trait();
+            let mut externs = vec![format!("type {handle_type};")];
+
+            // Host objects are poisoned by the panics like the module instances
+            handle_impls.push(formatdoc! {
                 r#"
-                #[cxx_name = "{cxx_extern_fn_name}"]
-                fn {prefixed_fn_name}({params_sig}){ret_extern_annotation};"#,
-            };
+                impl {handle_type} {{
+                    fn new(inner: Box<dyn {trait_name}>) -> Self {{
+                        {handle_type}(inner, craby::sync::PoisonFlag::new({poison}))
+                    }}
+                }}"#,
+                poison = panic.poison,
+            });
 
-            let ret = if let TypeAnnotation::Nullable(..) = &method_spec.ret_type {
-                "ret.into()"
-            } else {
-                "ret"
-            };
+            for method_spec in &handle.methods {
+                for type_annotation in method_spec
+                    .params
+                    .iter()
+                    .map(|param| &param.type_annotation)
+                    .chain(std::iter::once(&method_spec.ret_type))
+                {
+                    for bridge_type in type_annotation.bridge_types() {
+                        if let HashMapEntry::Vacant(e) = struct_defs.entry(bridge_type.to_id()) {
+                            let bridge_struct = RsBridgeStruct::try_from(bridge_type)?;
+                            e.insert(bridge_struct.definition);
+                            type_impls.push(bridge_struct.implementation);
+                        }
+                    }
+                }
+
+                let (extern_func, impl_func) =
+                    method_spec.try_into_rs_bridge_func(&self.module_name, &handle_type, None)?;
+                externs.push(extern_func);
+                handle_impls.push(impl_func);
+            }
+
+            handle_externs.push(externs.join("\n\n"));
+        }
+
+        // Collect signal emit functions for the signals with payload
+        let mut signal_extern_sigs = vec![];
+        for signal in &self.signals {
+            if let Some(payload_type) = &signal.payload_type {
+                for bridge_type in payload_type.bridge_types() {
+                    if let HashMapEntry::Vacant(e) = struct_defs.entry(bridge_type.to_id()) {
+                        let bridge_struct = RsBridgeStruct::try_from(bridge_type)?;
+                        e.insert(bridge_struct.definition);
+                        type_impls.push(bridge_struct.implementation);
+                    }
+                }
 
-            let fn_args = fn_args.join(", ");
-            let impl_func = match method_spec.ret_type {
-                TypeAnnotation::Promise(_) => formatdoc! {
+                let payload_type = payload_type.as_rs_bridge_type()?.into_code();
+                signal_extern_sigs.push(formatdoc! {
                     r#"
-                    fn {prefixed_fn_name}({params_sig}){ret_annotation} {{
-                        craby::catch_panic!({{
-                            let ret = {it}.{fn_name}({fn_args});
-                            {ret}
-                        }}).and_then(|r| r)
-                    }}"#,
-                    it = RESERVED_ARG_NAME_MODULE,
-                },
-                _ => formatdoc! {
+                    #[cxx_name = "emit"]
+                    fn {emit_fn_name}(self: &SignalManager, id: usize, name: &str, payload: &{payload_type});"#,
+                    emit_fn_name = signal.as_rs_emit_fn_name(&self.module_name),
+                });
+            }
+        }
+
+        // Collect callback invoke functions for the callbacks with argument
+        let mut callback_extern_sigs = vec![];
+        for method_spec in &self.methods {
+            for param in &method_spec.params {
+                if let TypeAnnotation::Callback(arg_type) = &param.type_annotation {
+                    if let TypeAnnotation::Void = &**arg_type {
+                        continue;
+                    }
+
+                    let payload_type = arg_type.as_rs_bridge_type()?.into_code();
+                    callback_extern_sigs.push(formatdoc! {
+                        r#"
+                        #[cxx_name = "invoke"]
+                        fn {invoke_fn_name}(self: &CallbackManager, id: usize, payload: &{payload_type});"#,
+                        invoke_fn_name = param.as_rs_invoke_fn_name(&self.module_name, &method_spec.name),
+                    });
+                }
+            }
+        }
+
+        // Collect stream push functions for the stream methods
+        let mut stream_extern_sigs = vec![];
+        for method_spec in &self.methods {
+            if let TypeAnnotation::Stream(item_type) = &method_spec.ret_type {
+                let item_type = item_type.as_rs_bridge_type()?.into_code();
+                stream_extern_sigs.push(formatdoc! {
                     r#"
-                    fn {prefixed_fn_name}({params_sig}){ret_annotation} {{
-                        craby::catch_panic!({{
-                            let ret = {it}.{fn_name}({fn_args});
-                            {ret}
-                        }})
-                    }}"#,
-                    it = RESERVED_ARG_NAME_MODULE,
-                },
-            };
+                    #[cxx_name = "push"]
+                    fn {push_fn_name}(self: &StreamManager, id: usize, item: &{item_type}) -> bool;"#,
+                    push_fn_name = method_spec.as_rs_push_fn_name(&self.module_name),
+                });
+            }
+        }
 
-            func_extern_sigs.push(extern_func);
-            func_impls.push(impl_func);
+        // Collect promise resolve functions for the `async fn` methods
+        let mut promise_extern_sigs = vec![];
+        for method_spec in &self.methods {
+            match &method_spec.ret_type {
+                TypeAnnotation::Promise(resolved_type) if method_spec.async_fn => {
+                    if let TypeAnnotation::Void = &**resolved_type {
+                        continue;
+                    }
+
+                    let resolved_type = resolved_type.as_rs_bridge_type()?.into_code();
+                    promise_extern_sigs.push(formatdoc! {
+                        r#"
+                        #[cxx_name = "resolve"]
+                        fn {resolve_fn_name}(self: &PromiseManager, id: usize, value: &{resolved_type});"#,
+                        resolve_fn_name = method_spec.as_rs_resolve_fn_name(&self.module_name),
+                    });
+                }
+                _ => {}
+            }
         }
 
         // Collect alias types (struct)
@@ -529,11 +1538,10 @@ impl Schema {
                 e.insert(RsStruct::try_from(obj)?.into_code());
 
                 for prop in &obj.props {
-                    if prop.type_annotation.is_nullable() {
-                        let id = prop.type_annotation.to_id();
-                        if let HashMapEntry::Vacant(e) = struct_defs.entry(id) {
-                            let nullable = RsNullableStruct::try_from(&prop.type_annotation)?;
-                            e.insert(nullable.definition);
+                    for bridge_type in prop.type_annotation.bridge_types() {
+                        if let HashMapEntry::Vacant(e) = struct_defs.entry(bridge_type.to_id()) {
+                            let bridge_struct = RsBridgeStruct::try_from(bridge_type)?;
+                            e.insert(bridge_struct.definition);
                         }
                     }
                 }
@@ -557,29 +1565,54 @@ impl Schema {
             .iter()
             .map(|type_annotation| {
                 let enum_schema = type_annotation.as_enum().unwrap();
+                // Integer enums keep the values as discriminants (eg. `Unknown = -1`)
+                let is_int = enum_schema
+                    .members
+                    .iter()
+                    .all(|m| matches!(m.value, EnumMemberValue::Number(..)));
                 let members = enum_schema
                     .members
                     .iter()
-                    .map(|m| format!("{},", m.name))
+                    .map(|m| {
+                        let member = if is_int {
+                            format!("{} = {},", m.name, m.value)
+                        } else {
+                            format!("{},", m.name)
+                        };
+                        with_rs_doc_comment(m.doc.as_deref(), member)
+                    })
                     .collect::<Vec<_>>();
 
                 let members = indent_str(&members.join("\n"), 4);
-                formatdoc! {
+                let repr = enum_schema
+                    .repr
+                    .as_ref()
+                    .map(|repr| format!("#[repr({repr})]\n"))
+                    .unwrap_or_default();
+                let enum_def = formatdoc! {
                     r#"
-                    enum {name} {{
+                    {repr}enum {name} {{
                     {members}
                     }}"#,
                     name = enum_schema.name,
-                }
+                };
+
+                with_rs_doc_comment(enum_schema.doc.as_deref(), enum_def)
             })
             .collect();
 
         Ok(RsCxxBridge {
-            impl_type: format!("type {module_name};"),
+            impl_type: format!("type {instance_type};"),
             struct_defs: struct_defs.into_values().collect(),
             enum_defs,
             func_extern_sigs,
             func_impls,
+            signal_extern_sigs,
+            callback_extern_sigs,
+            stream_extern_sigs,
+            promise_extern_sigs,
+            handle_externs,
+            handle_impls,
         })
     }
 
@@ -610,22 +1643,56 @@ impl Schema {
         // Collect extern function signatures and implementations
         for method_spec in &self.methods {
             for param in &method_spec.params {
-                // Collect nullable parameters
-                if param.type_annotation.is_nullable() {
-                    let id = param.type_annotation.to_id();
-                    if let BTreeMapEntry::Vacant(e) = type_impls.entry(id) {
-                        let nullable = RsNullableStruct::try_from(&param.type_annotation)?;
-                        e.insert(nullable.implementation);
+                // Collect nullable and map parameters
+                for bridge_type in param.type_annotation.bridge_types() {
+                    if let BTreeMapEntry::Vacant(e) = type_impls.entry(bridge_type.to_id()) {
+                        let bridge_struct = RsBridgeStruct::try_from(bridge_type)?;
+                        e.insert(bridge_struct.implementation);
+                    }
+                }
+            }
+
+            // Collect nullable and map return type
+            for bridge_type in method_spec.ret_type.bridge_types() {
+                if let BTreeMapEntry::Vacant(e) = type_impls.entry(bridge_type.to_id()) {
+                    let bridge_struct = RsBridgeStruct::try_from(bridge_type)?;
+                    e.insert(bridge_struct.implementation);
+                }
+            }
+        }
+
+        // Collect the spec traits of the host objects and the bridge types of their methods
+        for handle in &self.handles {
+            for method_spec in &handle.methods {
+                for type_annotation in method_spec
+                    .params
+                    .iter()
+                    .map(|param| &param.type_annotation)
+                    .chain(std::iter::once(&method_spec.ret_type))
+                {
+                    for bridge_type in type_annotation.bridge_types() {
+                        if let BTreeMapEntry::Vacant(e) = type_impls.entry(bridge_type.to_id()) {
+                            let bridge_struct = RsBridgeStruct::try_from(bridge_type)?;
+                            e.insert(bridge_struct.implementation);
+                        }
                     }
                 }
             }
 
-            // Collect nullable return type
-            if method_spec.ret_type.is_nullable() {
-                let id = method_spec.ret_type.to_id();
-                if let BTreeMapEntry::Vacant(e) = type_impls.entry(id) {
-                    let nullable = RsNullableStruct::try_from(&method_spec.ret_type)?;
-                    e.insert(nullable.implementation);
+            let id = TypeAnnotation::Handle(handle.as_handle_type()).to_id();
+            if let BTreeMapEntry::Vacant(e) = type_impls.entry(id) {
+                e.insert(handle.try_into_rs_spec()?);
+            }
+        }
+
+        // Collect nullable and map signal payload types
+        for signal in &self.signals {
+            if let Some(payload_type) = &signal.payload_type {
+                for bridge_type in payload_type.bridge_types() {
+                    if let BTreeMapEntry::Vacant(e) = type_impls.entry(bridge_type.to_id()) {
+                        let bridge_struct = RsBridgeStruct::try_from(bridge_type)?;
+                        e.insert(bridge_struct.implementation);
+                    }
                 }
             }
         }
@@ -643,7 +1710,11 @@ impl Schema {
             let id = type_annotation.to_id();
             if let BTreeMapEntry::Vacant(e) = type_impls.entry(id) {
                 let enum_type_annotation = type_annotation.as_enum().unwrap();
-                e.insert(RsDefaultImpl::try_from(enum_type_annotation)?.into_code());
+                let default_impl = RsDefaultImpl::try_from(enum_type_annotation)?.into_code();
+                e.insert(match error_code_impl(enum_type_annotation) {
+                    Some(error_code_impl) => format!("{default_impl}\n\n{error_code_impl}"),
+                    None => default_impl,
+                });
             }
         }
 
@@ -659,8 +1730,10 @@ pub mod template {
 
     use crate::{
         common::IntoCode,
-        parser::types::{EnumTypeAnnotation, ObjectTypeAnnotation, TypeAnnotation},
-        utils::indent_str,
+        parser::types::{
+            EnumTypeAnnotation, ObjectTypeAnnotation, TypeAnnotation, UnionTypeAnnotation,
+        },
+        utils::{indent_str, with_rs_doc_comment},
     };
 
     /// Rust struct definition for FFI.
@@ -695,10 +1768,13 @@ pub mod template {
                 // bar: f64,
                 // baz: bool,
                 // ```
-                props.push(format!(
-                    "{}: {},",
-                    snake_case(&prop.name),
-                    prop.type_annotation.as_rs_bridge_type()?.into_code()
+                props.push(with_rs_doc_comment(
+                    prop.doc.as_deref(),
+                    format!(
+                        "{}: {},",
+                        snake_case(&prop.name),
+                        prop.type_annotation.as_rs_bridge_type()?.into_code()
+                    ),
                 ));
             }
 
@@ -711,71 +1787,341 @@ pub mod template {
                 name = obj.name,
             };
 
-            Ok(RsStruct(struct_def))
+            Ok(RsStruct(with_rs_doc_comment(
+                obj.doc.as_deref(),
+                struct_def,
+            )))
         }
     }
 
-    /// Rust struct definition for nullable types.
-    pub struct RsNullableStruct {
+    /// Rust struct definition for the bridge types (nullable and map types).
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// // Nullable<Number>
+    /// struct NullableNumber {
+    ///     null: bool,
+    ///     val: f64,
+    /// }
+    ///
+    /// // Map<Number>
+    /// struct NumberMap {
+    ///     keys: Vec<String>,
+    ///     values: Vec<f64>,
+    /// }
+    ///
+    /// // Union (`{ kind: 'ok', value: number } | { kind: 'err', reason: string }`)
+    /// struct OutcomeUnion {
+    ///     tag: u32,
+    ///     ok: OutcomeOk,
+    ///     err: OutcomeErr,
+    /// }
+    /// ```
+    pub struct RsBridgeStruct {
         pub definition: String,
         pub implementation: String,
     }
 
-    impl TryFrom<&TypeAnnotation> for RsNullableStruct {
+    impl TryFrom<&TypeAnnotation> for RsBridgeStruct {
         type Error = anyhow::Error;
 
-        fn try_from(nullable_type: &TypeAnnotation) -> Result<Self, Self::Error> {
-            if let TypeAnnotation::Nullable(type_annotation) = nullable_type {
-                let struct_type = nullable_type.as_rs_bridge_type()?.into_code();
-                let base_type = type_annotation.as_rs_type()?.into_code();
-                let rs_impl_type = type_annotation.as_rs_impl_type()?.into_code();
-                let default_val = type_annotation.as_rs_default_val()?;
-
-                let struct_def = formatdoc! {
-                    r#"
-                    struct {struct_type} {{
-                        null: bool,
-                        val: {base_type},
-                    }}"#,
-                };
-
-                let struct_impl = formatdoc! {
-                    r#"
-                    impl Default for {struct_type} {{
-                        fn default() -> Self {{
-                            {struct_type} {{
-                                null: true,
-                                val: {default_val},
+        fn try_from(bridge_type: &TypeAnnotation) -> Result<Self, Self::Error> {
+            match bridge_type {
+                TypeAnnotation::Nullable(type_annotation) => {
+                    let struct_type = bridge_type.as_rs_bridge_type()?.into_code();
+                    let base_type = type_annotation.as_rs_type()?.into_code();
+                    let rs_impl_type = type_annotation.as_rs_impl_type()?.into_code();
+                    let default_val = type_annotation.as_rs_default_val()?;
+
+                    // Map and union values are converted from/into the bridge struct (eg. `NumberMap`)
+                    let (into_impl, into_bridge) = if type_annotation.is_bridge_type() {
+                        ("val.val.into()", "val.map(Into::into)")
+                    } else {
+                        ("val.val", "val")
+                    };
+
+                    let struct_def = formatdoc! {
+                        r#"
+                        struct {struct_type} {{
+                            null: bool,
+                            val: {base_type},
+                        }}"#,
+                    };
+
+                    let struct_impl = formatdoc! {
+                        r#"
+                        impl Default for {struct_type} {{
+                            fn default() -> Self {{
+                                {struct_type} {{
+                                    null: true,
+                                    val: {default_val},
+                                }}
                             }}
                         }}
-                    }}
 
-                    impl From<{struct_type}> for Nullable<{rs_impl_type}> {{
-                        fn from(val: {struct_type}) -> Self {{
-                            Nullable::new(if val.null {{ None }} else {{ Some(val.val) }})
+                        impl From<{struct_type}> for Nullable<{rs_impl_type}> {{
+                            fn from(val: {struct_type}) -> Self {{
+                                Nullable::new(if val.null {{ None }} else {{ Some({into_impl}) }})
+                            }}
                         }}
-                    }}
     
-                    impl From<Nullable<{rs_impl_type}>> for {struct_type} {{
-                        fn from(val: Nullable<{rs_impl_type}>) -> Self {{
-                            let val = val.into_value();
-                            let null = val.is_none();
-                            {struct_type} {{
-                                val: val.unwrap_or({default_val}),
-                                null,
+                        impl From<Nullable<{rs_impl_type}>> for {struct_type} {{
+                            fn from(val: Nullable<{rs_impl_type}>) -> Self {{
+                                let val = val.into_value();
+                                let null = val.is_none();
+                                {struct_type} {{
+                                    val: {into_bridge}.unwrap_or({default_val}),
+                                    null,
+                                }}
+                            }}
+                        }}"#,
+                    };
+
+                    Ok(RsBridgeStruct {
+                        definition: struct_def,
+                        implementation: struct_impl,
+                    })
+                }
+                TypeAnnotation::Map(value_type) => {
+                    let struct_type = bridge_type.as_rs_bridge_type()?.into_code();
+                    let value_rs_type = value_type.as_rs_type()?.into_code();
+                    let rs_impl_type = bridge_type.as_rs_impl_type()?.into_code();
+
+                    let struct_def = formatdoc! {
+                        r#"
+                        struct {struct_type} {{
+                            keys: Vec<String>,
+                            values: Vec<{value_rs_type}>,
+                        }}"#,
+                    };
+
+                    let struct_impl = formatdoc! {
+                        r#"
+                        impl Default for {struct_type} {{
+                            fn default() -> Self {{
+                                {struct_type} {{
+                                    keys: Vec::default(),
+                                    values: Vec::default(),
+                                }}
                             }}
                         }}
-                    }}"#,
-                };
 
-                return Ok(RsNullableStruct {
-                    definition: struct_def,
-                    implementation: struct_impl,
-                });
+                        impl From<{struct_type}> for {rs_impl_type} {{
+                            fn from(val: {struct_type}) -> Self {{
+                                val.keys.into_iter().zip(val.values).collect()
+                            }}
+                        }}
+
+                        impl From<{rs_impl_type}> for {struct_type} {{
+                            fn from(val: {rs_impl_type}) -> Self {{
+                                let (keys, values) = val.into_iter().unzip();
+                                {struct_type} {{ keys, values }}
+                            }}
+                        }}"#,
+                    };
+
+                    Ok(RsBridgeStruct {
+                        definition: struct_def,
+                        implementation: struct_impl,
+                    })
+                }
+                TypeAnnotation::Union(union_type) => {
+                    let struct_type = bridge_type.as_rs_bridge_type()?.into_code();
+                    Ok(RsBridgeStruct {
+                        definition: union_bridge_struct_def(&struct_type, union_type),
+                        implementation: union_bridge_struct_impl(&struct_type, union_type)?,
+                    })
+                }
+                _ => anyhow::bail!("Not a bridge type: {:?}", bridge_type),
             }
+        }
+    }
+
+    /// Bridge struct definition for the union type.
+    ///
+    /// The `tag` field is the index of the active variant and the data of the other variants are default values.
+    fn union_bridge_struct_def(struct_type: &str, union_type: &UnionTypeAnnotation) -> String {
+        let fields = std::iter::once("tag: u32,".to_string())
+            .chain(union_type.variants.iter().filter_map(|variant| {
+                variant
+                    .data
+                    .as_ref()
+                    .map(|data| format!("{}: {},", snake_case(&variant.name), data.name))
+            }))
+            .collect::<Vec<_>>();
+
+        let fields = indent_str(&fields.join("\n"), 4);
+        formatdoc! {
+            r#"
+            struct {struct_type} {{
+            {fields}
+            }}"#,
+        }
+    }
+
+    /// Rust enum definition of the union type and the conversions from/into the bridge struct.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// pub enum Outcome {
+    ///     Ok {
+    ///         value: Number,
+    ///     },
+    ///     Err {
+    ///         reason: String,
+    ///         detail: Nullable<SubObject>,
+    ///     },
+    /// }
+    ///
+    /// impl From<OutcomeUnion> for Outcome {
+    ///     fn from(val: OutcomeUnion) -> Self {
+    ///         match val.tag {
+    ///             0 => Outcome::Ok {
+    ///                 value: val.ok.value,
+    ///             },
+    ///             // ...
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn union_bridge_struct_impl(
+        struct_type: &str,
+        union_type: &UnionTypeAnnotation,
+    ) -> Result<String, anyhow::Error> {
+        let name = &union_type.name;
+        let data_count = union_type
+            .variants
+            .iter()
+            .filter(|variant| variant.data.is_some())
+            .count();
+
+        let mut variant_defs = vec![];
+        let mut default_fields = vec!["tag: 0,".to_string()];
+        let mut into_impl_arms = vec![];
+        let mut into_bridge_arms = vec![];
+
+        for (idx, variant) in union_type.variants.iter().enumerate() {
+            let variant_name = &variant.name;
+
+            match &variant.data {
+                Some(data) => {
+                    let field = snake_case(variant_name);
+                    let mut props = vec![];
+                    let mut prop_names = vec![];
+                    let mut into_impl_props = vec![];
+                    let mut into_bridge_props = vec![];
+
+                    // Variant fields use the implementation types (eg. `Nullable<SubObject>`) like the method signatures
+                    for prop in &data.props {
+                        let prop_name = snake_case(&prop.name);
+                        let type_annotation = &prop.type_annotation;
+                        props.push(format!(
+                            "{prop_name}: {},",
+                            type_annotation.as_rs_impl_type()?.into_code()
+                        ));
+                        into_impl_props.push(format!(
+                            "{prop_name}: {},",
+                            type_annotation.as_rs_into_expr(&format!("val.{field}.{prop_name}"))
+                        ));
+                        into_bridge_props.push(match type_annotation.as_rs_into_expr(&prop_name) {
+                            expr if expr == prop_name => expr,
+                            expr => format!("{prop_name}: {expr}"),
+                        });
+                        prop_names.push(prop_name);
+                    }
 
-            anyhow::bail!("Not a nullable type: {:?}", nullable_type);
+                    let props = indent_str(&props.join("\n"), 4);
+                    let into_impl_props = indent_str(&into_impl_props.join("\n"), 4);
+                    let prop_names = prop_names.join(", ");
+                    let into_bridge_props = into_bridge_props.join(", ");
+                    let rest = if data_count > 1 {
+                        "\n    ..Default::default()"
+                    } else {
+                        ""
+                    };
+
+                    variant_defs.push(formatdoc! {
+                        r#"
+                        {variant_name} {{
+                        {props}
+                        }},"#,
+                    });
+                    default_fields.push(format!("{field}: {}::default(),", data.name));
+                    into_impl_arms.push(formatdoc! {
+                        r#"
+                        {idx} => {name}::{variant_name} {{
+                        {into_impl_props}
+                        }},"#,
+                    });
+                    into_bridge_arms.push(formatdoc! {
+                        r#"
+                        {name}::{variant_name} {{ {prop_names} }} => {struct_type} {{
+                            tag: {idx},
+                            {field}: {data_name} {{ {into_bridge_props} }},{rest}
+                        }},"#,
+                        data_name = data.name,
+                    });
+                }
+                None => {
+                    let rest = if data_count > 0 {
+                        "\n    ..Default::default()"
+                    } else {
+                        ""
+                    };
+
+                    variant_defs.push(format!("{variant_name},"));
+                    into_impl_arms.push(format!("{idx} => {name}::{variant_name},"));
+                    into_bridge_arms.push(formatdoc! {
+                        r#"
+                        {name}::{variant_name} => {struct_type} {{
+                            tag: {idx},{rest}
+                        }},"#,
+                    });
+                }
+            }
         }
+
+        into_impl_arms.push(format!(
+            "_ => unreachable!(\"Invalid union tag ({name}): {{}}\", val.tag),"
+        ));
+
+        let variant_defs = indent_str(&variant_defs.join("\n"), 4);
+        let default_fields = indent_str(&default_fields.join("\n"), 12);
+        let into_impl_arms = indent_str(&into_impl_arms.join("\n"), 12);
+        let into_bridge_arms = indent_str(&into_bridge_arms.join("\n"), 12);
+
+        Ok(formatdoc! {
+            r#"
+            pub enum {name} {{
+            {variant_defs}
+            }}
+
+            impl Default for {struct_type} {{
+                fn default() -> Self {{
+                    {struct_type} {{
+            {default_fields}
+                    }}
+                }}
+            }}
+
+            impl From<{struct_type}> for {name} {{
+                fn from(val: {struct_type}) -> Self {{
+                    match val.tag {{
+            {into_impl_arms}
+                    }}
+                }}
+            }}
+
+            impl From<{name}> for {struct_type} {{
+                fn from(val: {name}) -> Self {{
+                    match val {{
+            {into_bridge_arms}
+                    }}
+                }}
+            }}"#,
+        })
     }
 
     /// Default implementation for struct types.
@@ -864,17 +2210,62 @@ pub mod template {
         }
     }
 
+    /// Generates the `craby::errors::ErrorCode` implementation for the string enum referenced as the error codes (eg. `@throws {MyError}`).
+    ///
+    /// The member value is used as the error code (eg. `craby::Error::new(MyError::NotFound, "...")`).
+    /// The shared enums of `cxx` are not exhaustive, so the unknown value is not a panic but its discriminant.
+    ///
+    /// ```rust,ignore
+    /// impl craby::errors::ErrorCode for MyError {
+    ///     fn code(&self) -> String {
+    ///         match *self {
+    ///             MyError::NotFound => "NOT_FOUND",
+    ///             MyError { repr } => return repr.to_string(),
+    ///         }
+    ///         .to_string()
+    ///     }
+    /// }
+    /// ```
+    pub fn error_code_impl(enum_type_annotation: &EnumTypeAnnotation) -> Option<String> {
+        if !enum_type_annotation.error_code {
+            return None;
+        }
+
+        let name = &enum_type_annotation.name;
+        let arms = enum_type_annotation
+            .members
+            .iter()
+            .map(|member| format!("{name}::{} => {:?},", member.name, member.value.to_string()))
+            .chain(std::iter::once(format!(
+                "{name} {{ repr }} => return repr.to_string(),"
+            )))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let arms = indent_str(&arms, 12);
+
+        Some(formatdoc! {
+            r#"
+            impl craby::errors::ErrorCode for {name} {{
+                fn code(&self) -> String {{
+                    match *self {{
+            {arms}
+                    }}
+                    .to_string()
+                }}
+            }}"#,
+        })
+    }
+
     pub fn collect_alias_default_impls(
         id: u64,
         obj: &ObjectTypeAnnotation,
         type_impls: &mut BTreeMap<u64, String>,
     ) -> Result<(), anyhow::Error> {
         for prop in &obj.props {
-            if prop.type_annotation.is_nullable() {
-                let id = prop.type_annotation.to_id();
-                if let BTreeMapEntry::Vacant(e) = type_impls.entry(id) {
-                    let nullable = RsNullableStruct::try_from(&prop.type_annotation)?;
-                    e.insert(nullable.implementation);
+            for bridge_type in prop.type_annotation.bridge_types() {
+                if let BTreeMapEntry::Vacant(e) = type_impls.entry(bridge_type.to_id()) {
+                    let bridge_struct = RsBridgeStruct::try_from(bridge_type)?;
+                    e.insert(bridge_struct.implementation);
                 }
             }
         }
@@ -1,14 +1,18 @@
 use std::path::PathBuf;
 
+use craby_common::config::{ConcurrencyConfig, PanicConfig, WorkerPoolConfig};
+
 use crate::{parser::native_spec_parser::try_parse_schema, types::CodegenContext};
 
 pub fn get_codegen_context() -> CodegenContext {
     let schemas = try_parse_schema(
         "
-        import type { NativeModule, Signal } from 'craby-modules';
+        import type { NativeModule, HostObject, Signal, Int32, Int64, Float } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
+        /** Object passed across the bridge */
         export interface TestObject {
+            /** Label of the object */
             foo: string;
             bar: number;
             baz: boolean;
@@ -22,11 +26,14 @@ pub fn get_codegen_context() -> CodegenContext {
             a: string | null;
             b: number;
             c: boolean;
+            d?: number;
         };
 
         export type MaybeNumber = number | null;
 
+        /** Sample enum */
         export enum MyEnum {
+            /** The first member */
             Foo = 'foo',
             Bar = 'bar',
             Baz = 'baz',
@@ -37,19 +44,81 @@ pub fn get_codegen_context() -> CodegenContext {
             On = 1,
         }
 
+        /** @repr i8 */
+        export enum Direction {
+            Unknown = -1,
+            Up,
+            Down,
+        }
+
+        export type Mode = 'fast' | 'safe';
+
+        /** Error codes of `parseMethod` */
+        export enum ParseError {
+            InvalidJson = 'INVALID_JSON',
+            TooLarge = 'TOO_LARGE',
+        }
+
+        export type Outcome =
+            | { kind: 'ok'; value: number }
+            | { kind: 'err'; reason: string; detail: SubObject | null }
+            | { kind: 'pending' };
+
+        /** Opened database connection */
+        export interface Connection extends HostObject {
+            /** Runs the query and returns the rows */
+            query(sql: string, params: SubObject | null): string[];
+            close(): void;
+        }
+
         export interface Spec extends NativeModule {
+            getConstants(): { version: string; maxItems: number };
+            /**
+             * Returns the given number.
+             *
+             * @param arg The number to return.
+             * @returns The given number.
+             * @see integerMethod
+             */
             numericMethod(arg: number): number;
+            integerMethod(arg0: Int32, arg1: Float, arg2: Int32[]): Int64 | null;
+            /** @deprecated Use `numericMethod` instead. */
             booleanMethod(arg: boolean): boolean;
             stringMethod(arg: string): string;
             objectMethod(arg: TestObject): TestObject;
             arrayMethod(arg: number[]): number[];
-            enumMethod(arg0: MyEnum, arg1: SwitchState): string;
+            arrayBufferMethod(arg: ArrayBuffer): ArrayBuffer;
+            mapMethod(arg: Record<string, number>): { [key: string]: TestObject };
+            enumMethod(arg0: MyEnum, arg1: SwitchState, arg2: Direction): string;
+            literalUnionMethod(arg: Mode): Mode;
+            unionMethod(arg: Outcome | null, list: Outcome[]): Promise<Outcome[]>;
             nullableMethod(arg: number | null): MaybeNumber;
+            optionalMethod(arg: number, options?: SubObject): void;
+            typeLiteralMethod(options: { label: string; limit?: number }): { ok: boolean };
             promiseMethod(arg: number): Promise<number>;
+            bytesPromiseMethod(arg: Uint8Array): Promise<ArrayBuffer>;
+            abortableMethod(arg: string, signal?: AbortSignal): Promise<number>;
             camelMethod(firstArg: number, secondArg: number): number;
             PascalMethod(FirstArg: number, SecondArg: number): number;
             snakeMethod(first_arg: number, second_arg: number): number;
+            subscribe(onData: (data: TestObject) => void, onDone: () => void): void;
+            readLines(path: string): AsyncIterable<string>;
+            /** @throws {ParseError} If the value is not a valid JSON */
+            parseMethod(arg: string): TestObject | null;
+            open(path: string): Connection;
+            openAsync(path: string): Promise<Connection>;
+            /** @thread serial */
+            writeFile(path: string, data: string): Promise<void>;
+            /** @thread io */
+            readFile(path: string): Promise<string>;
+            /** @thread js */
+            peekCache(key: string): Promise<string | null>;
+            /** @async */
+            fetchData(url: string, body: ArrayBuffer): Promise<string>;
+            /** @async */
+            flush(): Promise<void>;
             onSignal: Signal;
+            onObjectSignal: Signal<TestObject>;
         }
 
         export default NativeModuleRegistry.getEnforcing<Spec>('CrabyTest');
@@ -62,5 +131,50 @@ pub fn get_codegen_context() -> CodegenContext {
         root: PathBuf::from("."),
         schemas,
         android_package_name: "rs.craby.testmodule".to_string(),
+        worker_pool: WorkerPoolConfig::default(),
+        concurrency: ConcurrencyConfig::default(),
+        panic: PanicConfig::default(),
+    }
+}
+
+/// Two modules sharing the same types (eg. types imported from the shared source file)
+pub fn get_shared_types_codegen_context() -> CodegenContext {
+    let schemas = try_parse_schema(
+        "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface User {
+            name: string;
+            role: Role;
+        }
+
+        export enum Role {
+            Admin = 'admin',
+            Member = 'member',
+        }
+
+        export interface UserSpec extends NativeModule {
+            getUser(role: Role): User;
+        }
+
+        export interface ProfileSpec extends NativeModule {
+            updateUser(user: User): User;
+        }
+
+        export const UserModule = NativeModuleRegistry.getEnforcing<UserSpec>('UserModule');
+        export const ProfileModule = NativeModuleRegistry.getEnforcing<ProfileSpec>('ProfileModule');
+        ",
+    )
+    .unwrap();
+
+    CodegenContext {
+        project_name: "test_module".to_string(),
+        root: PathBuf::from("."),
+        schemas,
+        android_package_name: "rs.craby.testmodule".to_string(),
+        worker_pool: WorkerPoolConfig::default(),
+        concurrency: ConcurrencyConfig::default(),
+        panic: PanicConfig::default(),
     }
 }
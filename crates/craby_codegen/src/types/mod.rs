@@ -1,7 +1,10 @@
 use std::{fmt::Display, hash::Hasher, path::PathBuf};
 
-use crate::parser::types::{Method, Signal, TypeAnnotation};
-use craby_common::utils::string::{flat_case, pascal_case};
+use crate::parser::types::{Handle, Method, Signal, ThreadAffinity, TypeAnnotation};
+use craby_common::{
+    config::{ConcurrencyConfig, PanicConfig, WorkerPoolConfig},
+    utils::string::{flat_case, pascal_case},
+};
 use log::debug;
 use serde::Serialize;
 use xxhash_rust::xxh3::Xxh3;
@@ -11,6 +14,9 @@ pub struct CodegenContext {
     pub root: PathBuf,
     pub schemas: Vec<Schema>,
     pub android_package_name: String,
+    pub worker_pool: WorkerPoolConfig,
+    pub concurrency: ConcurrencyConfig,
+    pub panic: PanicConfig,
 }
 
 #[derive(Debug, Serialize)]
@@ -22,6 +28,12 @@ pub struct Schema {
     pub enums: Vec<TypeAnnotation>,
     pub methods: Vec<Method>,
     pub signals: Vec<Signal>,
+    // Host object interfaces returned by the methods
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub handles: Vec<Handle>,
+    // `TypeAnnotation::ObjectTypeAnnotation` returned by `getConstants`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constants: Option<TypeAnnotation>,
 }
 
 impl Schema {
@@ -32,6 +44,80 @@ impl Schema {
         hasher.write(serialized.as_bytes());
         format!("{:016x}", hasher.finish())
     }
+    /// Returns `true` if any method takes a callback parameter.
+    pub fn has_callbacks(&self) -> bool {
+        self.methods.iter().any(|method| {
+            method
+                .params
+                .iter()
+                .any(|param| param.type_annotation.is_callback())
+        })
+    }
+
+    /// Returns `true` if any method takes an `AbortSignal` parameter.
+    pub fn has_abort_signals(&self) -> bool {
+        self.methods.iter().any(|method| {
+            method
+                .params
+                .iter()
+                .any(|param| param.type_annotation.is_abort_signal())
+        })
+    }
+
+    /// Returns `true` if any method returns a stream (`AsyncIterable<T>`).
+    pub fn has_streams(&self) -> bool {
+        self.methods
+            .iter()
+            .any(|method| matches!(method.ret_type, TypeAnnotation::Stream(..)))
+    }
+
+    /// Returns `true` if any promise method is implemented as an `async fn` (`@async` tag).
+    pub fn has_async_fns(&self) -> bool {
+        self.methods.iter().any(|method| method.async_fn)
+    }
+
+    /// Returns the lifecycle hooks of the module (default methods of the spec trait called by C++).
+    ///
+    /// - `onInvalidate`: Called before the module is torn down (invalidated or destroyed)
+    /// - `onReload`: Called on the outgoing module instance invalidated by the runtime, before `onInvalidate`
+    pub fn lifecycle_hooks() -> Vec<Method> {
+        [
+            (
+                "onInvalidate",
+                "Called when the module is invalidated or destroyed, before the module is torn down.",
+            ),
+            (
+                "onReload",
+                "Called when the module is invalidated by the React Native runtime (eg. the JS bundle is reloaded), before `on_invalidate`.",
+            ),
+        ]
+        .into_iter()
+        .map(|(name, doc)| Method {
+            name: name.to_string(),
+            params: vec![],
+            ret_type: TypeAnnotation::Void,
+            throws: false,
+            thread: None,
+            async_fn: false,
+            doc: Some(doc.to_string()),
+        })
+        .collect()
+    }
+
+    /// Returns the dedicated threads (`@thread serial` or `@thread <name>`) used by the methods.
+    pub fn dedicated_threads(&self) -> Vec<&ThreadAffinity> {
+        let mut threads = self
+            .methods
+            .iter()
+            .filter_map(|method| match &method.thread {
+                Some(thread @ (ThreadAffinity::Serial | ThreadAffinity::Named(..))) => Some(thread),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        threads.sort();
+        threads.dedup();
+        threads
+    }
 }
 
 /// Represents the C++ base namespace for the Craby project.
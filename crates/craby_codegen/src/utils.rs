@@ -20,64 +20,270 @@ pub fn indent_str(str: &str, indent_size: usize) -> String {
         .join("\n")
 }
 
+/// Returns the documentation comment lines (eg. `/// Multiplies two numbers.`).
+pub fn doc_comment(doc: &str) -> String {
+    doc.lines()
+        .map(|line| {
+            if line.is_empty() {
+                "///".to_string()
+            } else {
+                format!("/// {line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prepends the documentation comment to the code if exists.
+pub fn with_doc_comment(doc: Option<&str>, code: String) -> String {
+    match doc {
+        Some(doc) => format!("{}\n{code}", doc_comment(doc)),
+        None => code,
+    }
+}
+
+/// Rust documentation converted from the JSDoc comment.
+#[derive(Debug, Default, PartialEq)]
+pub struct RsDoc {
+    pub doc: Option<String>,
+    /// Note of the `@deprecated` tag (empty if the tag has no description)
+    pub deprecated: Option<String>,
+}
+
+/// Converts the JSDoc tags of the comment into the rustdoc sections.
+///
+/// - `@returns` (`@return`): `# Returns` section
+/// - `@throws`: `# Errors` section
+/// - `@deprecated`: [`RsDoc::deprecated`]
+/// - Other tags (eg. `@see`, `@example`) are stripped
+///
+/// The description of a tag continues until the blank line or the next tag.
+pub fn rs_doc(doc: &str) -> RsDoc {
+    enum Section {
+        Body,
+        Returns,
+        Errors,
+        Deprecated,
+        Unknown,
+    }
+
+    let mut body = vec![];
+    let mut returns = vec![];
+    let mut errors = vec![];
+    let mut deprecated: Option<Vec<&str>> = None;
+    let mut section = Section::Body;
+
+    for line in doc.lines() {
+        let tag = line
+            .strip_prefix('@')
+            .map(|tag| tag.split_once(char::is_whitespace).unwrap_or((tag, "")));
+
+        match tag {
+            Some((name, desc)) if !name.is_empty() => {
+                // Skip the type expression (eg. `{Error}`)
+                let desc = match desc.trim_start().strip_prefix('{') {
+                    Some(desc) => desc.split_once('}').map_or("", |(_, rest)| rest),
+                    None => desc,
+                };
+                let desc = desc.trim();
+
+                section = match name {
+                    "returns" | "return" => {
+                        returns.push(desc.to_string());
+                        Section::Returns
+                    }
+                    "throws" => {
+                        errors.push(desc.to_string());
+                        Section::Errors
+                    }
+                    "deprecated" => {
+                        deprecated.get_or_insert_with(Vec::new).push(desc);
+                        Section::Deprecated
+                    }
+                    _ => Section::Unknown,
+                };
+            }
+            _ if line.trim().is_empty() => {
+                section = Section::Body;
+                body.push(line);
+            }
+            _ => match section {
+                Section::Body => body.push(line),
+                Section::Returns => push_line(returns.last_mut(), line),
+                Section::Errors => push_line(errors.last_mut(), line),
+                Section::Deprecated => deprecated.get_or_insert_with(Vec::new).push(line),
+                Section::Unknown => {}
+            },
+        }
+    }
+
+    fn push_line(desc: Option<&mut String>, line: &str) {
+        if let Some(desc) = desc {
+            if !desc.is_empty() {
+                desc.push('\n');
+            }
+            desc.push_str(line);
+        }
+    }
+
+    let sections = |title: &str, descs: Vec<String>| {
+        let descs = descs
+            .into_iter()
+            .filter(|desc| !desc.is_empty())
+            .collect::<Vec<_>>();
+        (!descs.is_empty()).then(|| format!("# {title}\n\n{}", descs.join("\n\n")))
+    };
+
+    let body = body.join("\n");
+    let body = collapse_blank_lines(body.trim_matches('\n'));
+    let docs = [
+        (!body.is_empty()).then_some(body),
+        sections("Returns", returns),
+        sections("Errors", errors),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+
+    RsDoc {
+        doc: (!docs.is_empty()).then(|| docs.join("\n\n")),
+        deprecated: deprecated.map(|lines| {
+            lines
+                .iter()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }),
+    }
+}
+
+/// Collapses the consecutive blank lines left by the stripped tags.
+fn collapse_blank_lines(doc: &str) -> String {
+    let mut lines: Vec<&str> = vec![];
+    for line in doc.lines() {
+        if line.trim().is_empty() && lines.last().is_some_and(|last| last.trim().is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// Prepends the Rust documentation comment converted from the JSDoc comment (see [`rs_doc`]).
+///
+/// The `@deprecated` tag is documented as the `# Deprecated` section
+/// (the items in the cxx bridge cannot have the `#[deprecated]` attribute).
+pub fn with_rs_doc_comment(doc: Option<&str>, code: String) -> String {
+    let RsDoc { doc, deprecated } = match doc {
+        Some(doc) => rs_doc(doc),
+        None => return code,
+    };
+    let deprecated = deprecated.map(|note| match note.as_str() {
+        "" => "# Deprecated".to_string(),
+        note => format!("# Deprecated\n\n{note}"),
+    });
+    let doc = [doc, deprecated]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    with_doc_comment((!doc.is_empty()).then_some(doc.as_str()), code)
+}
+
+/// Prepends the Rust documentation comment and the `#[deprecated]` attribute of the `@deprecated` tag
+/// to the trait method.
+pub fn with_rs_method_doc_comment(doc: Option<&str>, code: String) -> String {
+    let RsDoc { doc, deprecated } = match doc {
+        Some(doc) => rs_doc(doc),
+        None => return code,
+    };
+    let code = match deprecated.as_deref() {
+        Some("") => format!("#[deprecated]\n{code}"),
+        Some(note) => format!("#[deprecated(note = {note:?})]\n{code}"),
+        None => code,
+    };
+
+    with_doc_comment(doc.as_deref(), code)
+}
+
 pub fn calc_deps_order(schema: &Schema) -> Result<Vec<String>, anyhow::Error> {
     let mut dependencies = BTreeMap::new();
     let mut visited = BTreeSet::new();
     let mut in_progress = BTreeSet::new();
     let mut result = vec![];
 
+    /// Registers the type (and its inner types) as a dependency node and returns the node name.
+    ///
+    /// - Object, Enum: type name (eg. `MyStruct`)
+    /// - Nullable, Map, Union: bridge type name (eg. `NullableMyStruct`, `MyStructMap`, `MyUnionUnion`)
+    fn collect_node(
+        type_annotation: &TypeAnnotation,
+        dependencies: &mut BTreeMap<String, Vec<String>>,
+    ) -> Result<Option<String>, anyhow::Error> {
+        match type_annotation {
+            TypeAnnotation::Object(ObjectTypeAnnotation { name, .. })
+            | TypeAnnotation::Enum(EnumTypeAnnotation { name, .. }) => Ok(Some(name.clone())),
+            TypeAnnotation::Nullable(inner) | TypeAnnotation::Map(inner) => {
+                let rs_type = type_annotation.as_rs_bridge_type()?.into_code();
+                let inner_node = collect_node(inner, dependencies)?;
+                let deps = dependencies.entry(rs_type.clone()).or_insert(vec![]);
+
+                if let Some(inner_node) = inner_node {
+                    if !deps.contains(&inner_node) {
+                        deps.push(inner_node);
+                    }
+                }
+
+                Ok(Some(rs_type))
+            }
+            TypeAnnotation::Union(union_type) => {
+                let rs_type = type_annotation.as_rs_bridge_type()?.into_code();
+                let data_nodes = union_type
+                    .variants
+                    .iter()
+                    .filter_map(|variant| variant.data.as_ref())
+                    .map(|data| data.name.clone())
+                    .collect::<Vec<_>>();
+
+                dependencies.insert(rs_type.clone(), data_nodes);
+                Ok(Some(rs_type))
+            }
+            TypeAnnotation::Array(element_type) => collect_node(element_type, dependencies),
+            TypeAnnotation::Promise(resolved_type) => collect_node(resolved_type, dependencies),
+            TypeAnnotation::Stream(item_type) => collect_node(item_type, dependencies),
+            TypeAnnotation::Callback(arg_type) => collect_node(arg_type, dependencies),
+            _ => Ok(None),
+        }
+    }
+
     for type_annotation in &schema.aliases {
         let alias_spec = type_annotation.as_object().unwrap();
 
         dependencies.insert(alias_spec.name.clone(), vec![]);
 
         for prop in &alias_spec.props {
-            match &prop.type_annotation {
-                TypeAnnotation::Object(ObjectTypeAnnotation {
-                    name: alias_name, ..
-                }) => {
-                    dependencies
-                        .get_mut(&alias_spec.name)
-                        .unwrap()
-                        .push(alias_name.clone());
-                }
-                TypeAnnotation::Enum(EnumTypeAnnotation {
-                    name: enum_name, ..
-                }) => {
-                    dependencies
-                        .get_mut(&alias_spec.name)
-                        .unwrap()
-                        .push(enum_name.clone());
-                }
-                nullable @ TypeAnnotation::Nullable(type_annotation) => {
-                    let rs_type = nullable.as_rs_bridge_type()?.into_code();
-                    dependencies.entry(rs_type.clone()).or_insert(vec![]);
-
-                    match &**type_annotation {
-                        TypeAnnotation::Object(ObjectTypeAnnotation {
-                            name: alias_name, ..
-                        }) => {
-                            dependencies
-                                .get_mut(&rs_type)
-                                .unwrap()
-                                .push(alias_name.clone());
-                        }
-                        TypeAnnotation::Enum(EnumTypeAnnotation {
-                            name: enum_name, ..
-                        }) => {
-                            dependencies
-                                .get_mut(&rs_type)
-                                .unwrap()
-                                .push(enum_name.clone());
-                        }
-                        _ => (),
-                    }
-                }
-                _ => (),
+            if let Some(node) = collect_node(&prop.type_annotation, &mut dependencies)? {
+                dependencies.get_mut(&alias_spec.name).unwrap().push(node);
             }
         }
     }
 
+    // Bridge types used in methods and signals (eg. `NullableNumber`, `NumberMap`)
+    for method in &schema.methods {
+        for param in &method.params {
+            collect_node(&param.type_annotation, &mut dependencies)?;
+        }
+        collect_node(&method.ret_type, &mut dependencies)?;
+    }
+
+    for signal in &schema.signals {
+        if let Some(payload_type) = &signal.payload_type {
+            collect_node(payload_type, &mut dependencies)?;
+        }
+    }
+
     fn visit(
         node: &str,
         dependencies: &BTreeMap<String, Vec<String>>,
@@ -135,4 +341,40 @@ mod tests {
         assert_eq!(indent_str("Hello\nWorld", 2), "  Hello\n  World");
         assert_eq!(indent_str("Hello\nWorld", 4), "    Hello\n    World");
     }
+
+    #[test]
+    fn test_rs_doc() {
+        let doc = rs_doc(
+            "Parses the value.\n\n@returns {object} The parsed value.\n@throws {Error} If the value is invalid\n  or empty.\n@throws If the parser is closed.\n@see parseAsync\n@example\nparse('{}');\n\n@deprecated Use `parseAsync` instead.",
+        );
+
+        assert_eq!(
+            doc,
+            RsDoc {
+                doc: Some(
+                    "Parses the value.\n\n# Returns\n\nThe parsed value.\n\n# Errors\n\nIf the value is invalid\n  or empty.\n\nIf the parser is closed."
+                        .to_string()
+                ),
+                deprecated: Some("Use `parseAsync` instead.".to_string()),
+            }
+        );
+        assert_eq!(
+            rs_doc("@deprecated"),
+            RsDoc {
+                doc: None,
+                deprecated: Some(String::new()),
+            }
+        );
+        assert_eq!(
+            with_rs_method_doc_comment(
+                Some("Foo.\n@deprecated Use \"bar\"."),
+                "fn foo();".to_string()
+            ),
+            "/// Foo.\n#[deprecated(note = \"Use \\\"bar\\\".\")]\nfn foo();"
+        );
+        assert_eq!(
+            with_rs_doc_comment(Some("@deprecated Use `bar`."), "bar: f64,".to_string()),
+            "/// # Deprecated\n///\n/// Use `bar`.\nbar: f64,"
+        );
+    }
 }
@@ -81,5 +81,19 @@ fn validate_config(config: &Config) -> Result<(), anyhow::Error> {
         ));
     }
 
+    let worker_pool = &config.project.worker_pool;
+    if worker_pool.size == 0 {
+        anyhow::bail!("Worker pool size must be greater than 0");
+    }
+
+    for (module_name, module) in &worker_pool.modules {
+        if module.size == Some(0) {
+            anyhow::bail!(format!(
+                "Worker pool size must be greater than 0 (module: {})",
+                module_name
+            ));
+        }
+    }
+
     Ok(())
 }
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +28,118 @@ pub struct Config {
 pub struct ProjectConfig {
     pub name: String,
     pub source_dir: String,
+    /// Worker pool of the async methods (`[project.worker_pool]`)
+    #[serde(default)]
+    pub worker_pool: WorkerPoolConfig,
+    /// Concurrent access to the module instances (`[project.concurrency]`)
+    #[serde(default)]
+    pub concurrency: ConcurrencyConfig,
+    /// Panics caught by the bindings (`[project.panic]`)
+    #[serde(default)]
+    pub panic: PanicConfig,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkerPoolConfig {
+    /// Maximum number of the worker threads
+    pub size: usize,
+    /// Whether the modules share a single worker pool
+    pub shared: bool,
+    /// Idle worker threads exit after the timeout (`0` keeps the threads alive)
+    pub idle_timeout_ms: u64,
+    /// Maximum number of the pending tasks (`0` for unbounded)
+    pub max_queue_size: usize,
+    /// Module specific worker pools (key: module name)
+    pub modules: BTreeMap<String, ModuleWorkerPoolConfig>,
+}
+
+impl Default for WorkerPoolConfig {
+    fn default() -> Self {
+        WorkerPoolConfig {
+            size: 10,
+            shared: false,
+            idle_timeout_ms: 0,
+            max_queue_size: 0,
+            modules: BTreeMap::new(),
+        }
+    }
+}
+
+impl WorkerPoolConfig {
+    /// Returns the worker pool of the module.
+    ///
+    /// Modules listed in `[project.worker_pool.modules]` always own a worker pool
+    /// (unspecified options are inherited from the project).
+    pub fn resolve(&self, module_name: &str) -> WorkerPool {
+        match self.modules.get(module_name) {
+            Some(module) => WorkerPool {
+                size: module.size.unwrap_or(self.size),
+                shared: false,
+                idle_timeout_ms: module.idle_timeout_ms.unwrap_or(self.idle_timeout_ms),
+                max_queue_size: module.max_queue_size.unwrap_or(self.max_queue_size),
+            },
+            None => WorkerPool {
+                size: self.size,
+                shared: self.shared,
+                idle_timeout_ms: self.idle_timeout_ms,
+                max_queue_size: self.max_queue_size,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ModuleWorkerPoolConfig {
+    pub size: Option<usize>,
+    pub idle_timeout_ms: Option<u64>,
+    pub max_queue_size: Option<usize>,
+}
+
+/// Resolved worker pool of the module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerPool {
+    pub size: usize,
+    pub shared: bool,
+    pub idle_timeout_ms: u64,
+    pub max_queue_size: usize,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConcurrencyConfig {
+    /// Concurrency mode of the modules
+    pub mode: ConcurrencyMode,
+    /// Module specific concurrency modes (key: module name)
+    pub modules: BTreeMap<String, ConcurrencyMode>,
+}
+
+impl ConcurrencyConfig {
+    /// Returns the concurrency mode of the module.
+    pub fn resolve(&self, module_name: &str) -> ConcurrencyMode {
+        self.modules.get(module_name).copied().unwrap_or(self.mode)
+    }
+}
+
+/// How the module instance is accessed by the JS thread and the worker threads
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConcurrencyMode {
+    /// The calls are serialized by a per-instance lock (methods take `&mut self`)
+    #[default]
+    Lock,
+    /// The calls run concurrently (methods take `&self`, the module must be `Sync`)
+    Shared,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PanicConfig {
+    /// Whether the backtrace is always captured (otherwise only if `RUST_BACKTRACE` is set)
+    pub backtrace: bool,
+    /// Whether a panic poisons the module instance (the later calls fail)
+    pub poison: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
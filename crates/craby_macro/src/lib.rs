@@ -2,10 +2,39 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, parse_quote, ImplItem, ItemImpl};
 
+/// Implements the `new` and `id` methods of the module spec (if not implemented).
+///
+/// Promise methods with the `@async` tag can be implemented as `async fn`.
+///
+/// ```rust,ignore
+/// #[craby_module]
+/// impl MyModuleSpec for MyModule {
+///     async fn fetch(&self, url: String) -> Promise<String> {
+///         let res = client::get(&url).await?;
+///         Ok(res.text().await?)
+///     }
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn craby_module(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(item as ItemImpl);
 
+    // The module is created and identified synchronously by the C++ module
+    for item in &input.items {
+        if let ImplItem::Fn(method) = item {
+            if method.sig.asyncness.is_some()
+                && (method.sig.ident == "new" || method.sig.ident == "id")
+            {
+                return syn::Error::new_spanned(
+                    method.sig.asyncness,
+                    format!("`{}` cannot be an `async fn`", method.sig.ident),
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
+    }
+
     let has_new = input
         .items
         .iter()
@@ -2,7 +2,40 @@ import { Platform, TurboModuleRegistry } from 'react-native';
 
 type NativeModule = {};
 
-type Signal = (handler: () => void) => () => void;
+type Signal<T = void> = (handler: (payload: T) => void) => () => void;
+
+/**
+ * Rust-owned object exposed to JavaScript (`jsi::HostObject`)
+ *
+ * The Rust value is dropped when the object is garbage collected.
+ */
+type HostObject = {};
+
+declare const brand: unique symbol;
+
+type Brand<T, B extends string> = T & { readonly [brand]?: B };
+
+/**
+ * 32-bit signed integer (`i32` in Rust)
+ */
+type Int32 = Brand<number, 'Int32'>;
+
+/**
+ * 64-bit signed integer (`i64` in Rust)
+ *
+ * Travels as `BigInt` so the value does not lose precision.
+ */
+type Int64 = Brand<bigint, 'Int64'>;
+
+/**
+ * 8-bit unsigned integer (`u8` in Rust)
+ */
+type UInt8 = Brand<number, 'UInt8'>;
+
+/**
+ * 32-bit floating point number (`f32` in Rust)
+ */
+type Float = Brand<number, 'Float'>;
 
 /**
  * Android JNI initialization workaround
@@ -43,4 +76,4 @@ export const NativeModuleRegistry: NativeModuleRegistry = {
   },
 };
 
-export type { NativeModule, Signal };
+export type { NativeModule, HostObject, Signal, Int32, Int64, UInt8, Float };